@@ -0,0 +1,197 @@
+use crate::error::DslError;
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// The version of the artifact format, which is bumped whenever the encoding of an artifact
+/// changes, so that an artifact written by an older version is rejected instead of misread.
+// 2: the entries of the LDM snapshots record the type tags of their keys
+// 3: the entries of the LDM snapshots record the parts that debug read them
+pub const ARTIFACT_VERSION: u32 = 3;
+
+const ARTIFACT_MAGIC: [u8; 4] = *b"RSBA";
+
+/// A value that can be saved to disk and loaded back, such as a compiled program.
+///
+/// The file starts with a header that records the format version, the kind of the value, and the
+/// SHA-256 hash of the kind and the payload, which is checked when the artifact is loaded.
+pub trait Artifact: Serialize + DeserializeOwned {
+    /// The kind of the value, which prevents an artifact of one kind from being loaded as another.
+    const KIND: &'static str;
+
+    fn to_artifact_bytes(&self) -> Result<Vec<u8>> {
+        let payload = bincode::serialize(self)?;
+        let file = ArtifactFile {
+            magic: ARTIFACT_MAGIC,
+            version: ARTIFACT_VERSION,
+            kind: Self::KIND.to_string(),
+            hash: artifact_hash(Self::KIND, &payload),
+            payload,
+        };
+        Ok(bincode::serialize(&file)?)
+    }
+
+    fn from_artifact_bytes(bytes: &[u8]) -> Result<Self> {
+        let file: ArtifactFile =
+            bincode::deserialize(bytes).context("The artifact header is malformed.")?;
+        if file.magic != ARTIFACT_MAGIC {
+            return Err(DslError::NotAnArtifact.into());
+        }
+        if file.version != ARTIFACT_VERSION {
+            return Err(DslError::ArtifactVersionMismatch {
+                found: file.version,
+                expected: ARTIFACT_VERSION,
+            }
+            .into());
+        }
+        if file.kind != Self::KIND {
+            return Err(DslError::ArtifactKindMismatch {
+                found: file.kind,
+                expected: Self::KIND,
+            }
+            .into());
+        }
+        if file.hash != artifact_hash(&file.kind, &file.payload) {
+            return Err(DslError::ArtifactCorrupted.into());
+        }
+        bincode::deserialize(&file.payload).context("The artifact payload is malformed.")
+    }
+
+    fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_artifact_bytes()?)
+            .with_context(|| format!("Cannot write the artifact to {}.", path.display()))
+    }
+
+    fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Cannot read the artifact from {}.", path.display()))?;
+        Self::from_artifact_bytes(&bytes)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArtifactFile {
+    magic: [u8; 4],
+    version: u32,
+    kind: String,
+    hash: [u8; 32],
+    payload: Vec<u8>,
+}
+
+fn artifact_hash(kind: &str, payload: &[u8]) -> [u8; 32] {
+    let mut sha256 = Sha256::new();
+    sha256.update((kind.len() as u64).to_le_bytes());
+    sha256.update(kind.as_bytes());
+    sha256.update(payload);
+    sha256.finalize().into()
+}
+
+/// Serializes a script as its bytes, for `#[serde(with = "...")]`.
+pub mod script_serde {
+    use crate::treepp::Script;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(script: &Script, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(script.as_bytes())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Script, D::Error> {
+        Ok(Script::from_bytes(Vec::<u8>::deserialize(deserializer)?))
+    }
+}
+
+/// Serializes a list of scripts as their bytes, for `#[serde(with = "...")]`.
+pub mod script_vec_serde {
+    use crate::treepp::Script;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(scripts: &[Script], serializer: S) -> Result<S::Ok, S::Error> {
+        scripts
+            .iter()
+            .map(|script| script.as_bytes())
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Script>, D::Error> {
+        Ok(Vec::<Vec<u8>>::deserialize(deserializer)?
+            .into_iter()
+            .map(Script::from_bytes)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::artifact::{Artifact, ARTIFACT_VERSION};
+    use crate::basic::i32::I32Bar;
+    use crate::bitcoin_system::{BitcoinSystemRef, Element};
+    use crate::compiler::{CompiledProgram, Compiler};
+    use crate::debugger::{execute_with_witness, Debugger};
+    use crate::error::DslError;
+    use crate::treepp::*;
+
+    fn element_to_witness(element: &Element) -> Vec<u8> {
+        convert_to_witness(script! { { element } })
+            .unwrap()
+            .remove(0)
+    }
+
+    #[test]
+    fn test_artifact_round_trip() {
+        let cs = BitcoinSystemRef::new_ref();
+        let a = I32Bar::new_hint(&cs, 3).unwrap();
+        let b = I32Bar::new_constant(&cs, 4).unwrap();
+        let c = &a + &b;
+        cs.set_program_output(&c).unwrap();
+
+        let program = Compiler::compile(cs).unwrap();
+        let bytes = program.to_artifact_bytes().unwrap();
+        let loaded = CompiledProgram::from_artifact_bytes(&bytes).unwrap();
+
+        assert_eq!(loaded.input, program.input);
+        assert_eq!(loaded.hint, program.hint);
+        assert_eq!(loaded.script, program.script);
+        assert_eq!(loaded.max_stack_items, program.max_stack_items);
+        assert!(loaded.trace.is_empty());
+        assert!(loaded.trace_offsets.is_empty());
+
+        let mut debugger = Debugger::new(&program, script! { 7 }, true).unwrap();
+        assert!(debugger.run());
+
+        // the loaded program cannot be stepped through, but it can still be executed
+        let err = Debugger::new(&loaded, script! { 7 }, true).err().unwrap();
+        assert!(matches!(
+            err.downcast_ref::<DslError>(),
+            Some(DslError::MissingDebugInfo)
+        ));
+        let witness = loaded.hint.iter().map(element_to_witness).collect();
+        assert_eq!(
+            execute_with_witness(&loaded.script, witness).unwrap(),
+            vec![vec![7]]
+        );
+
+        // any change to the payload is detected
+        let mut tampered = bytes.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert!(CompiledProgram::from_artifact_bytes(&tampered).is_err());
+
+        // an artifact of another version is rejected
+        let mut outdated = bytes.clone();
+        outdated[4..8].copy_from_slice(&(ARTIFACT_VERSION + 1).to_le_bytes());
+        assert!(CompiledProgram::from_artifact_bytes(&outdated).is_err());
+
+        let path = std::env::temp_dir().join("test_artifact_round_trip.bin");
+        program.save(&path).unwrap();
+        let loaded = CompiledProgram::load(&path).unwrap();
+        assert_eq!(loaded.script, program.script);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
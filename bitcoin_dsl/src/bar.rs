@@ -1,23 +1,30 @@
 use crate::bitcoin_system::BitcoinSystemRef;
+use crate::error::DslError;
 use crate::treepp::*;
-use anyhow::Result;
+use anyhow::{Error, Result};
 use bitcoin::opcodes::Ordinary::OP_EQUALVERIFY;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub use recursive_stwo_bitcoin_dsl_derive::{AllocBar, Bar, FixedLengthBar};
 
 /// This trait describes some core functionality that is common to high-level variables.
 pub trait Bar: Clone {
     /// Returns the underlying `BitcoinSystemRef`.
     fn cs(&self) -> BitcoinSystemRef;
 
+    /// Returns the underlying `BitcoinSystemRef`, or `DslError::EmptyBar` if the variable has no
+    /// stack element to take it from, such as an empty vector, for which `cs` panics.
+    fn try_cs(&self) -> Result<BitcoinSystemRef> {
+        Ok(self.cs())
+    }
+
     /// Returns the assigned stack elements indices.
     fn variables(&self) -> Vec<usize>;
 
-    /// Returns the length (in terms of number of elements in the stack) of the value.
-    fn length() -> usize;
-
     fn equalverify(&self, rhs: &Self) -> Result<()> {
-        let cs = self.cs().and(&rhs.cs());
+        let cs = self.try_cs()?.and(&rhs.try_cs()?);
 
         for (&self_var, &rhs_var) in self.variables().iter().zip(rhs.variables().iter()) {
             cs.insert_script(
@@ -30,6 +37,13 @@ pub trait Bar: Clone {
     }
 }
 
+/// The variables whose number of stack elements is known from their type alone, which excludes
+/// vectors.
+pub trait FixedLengthBar: Bar {
+    /// Returns the length (in terms of number of elements in the stack) of the value.
+    fn length() -> usize;
+}
+
 pub trait AllocBar: Sized {
     /// The type of the "native" value that `Self` represents in the bitcoin
     /// system.
@@ -57,18 +71,283 @@ pub trait AllocBar: Sized {
     fn new_hint(cs: &BitcoinSystemRef, data: Self::Value) -> Result<Self> {
         Self::new_variable(cs, data, AllocationMode::Hint)
     }
+
+    /// Allocates a variable and labels it, see `BitcoinSystemRef::label`.
+    fn new_variable_named(
+        cs: &BitcoinSystemRef,
+        data: Self::Value,
+        mode: AllocationMode,
+        name: impl ToString,
+    ) -> Result<Self>
+    where
+        Self: Bar,
+    {
+        let res = match mode {
+            AllocationMode::ProgramInput => Self::new_program_input(cs, data)?,
+            AllocationMode::FunctionOutput => Self::new_function_output(cs, data)?,
+            AllocationMode::Constant => Self::new_constant(cs, data)?,
+            AllocationMode::Hint => Self::new_hint(cs, data)?,
+        };
+        cs.label(&res, name);
+        Ok(res)
+    }
+
+    fn new_constant_named(
+        cs: &BitcoinSystemRef,
+        data: Self::Value,
+        name: impl ToString,
+    ) -> Result<Self>
+    where
+        Self: Bar,
+    {
+        Self::new_variable_named(cs, data, AllocationMode::Constant, name)
+    }
+
+    fn new_program_input_named(
+        cs: &BitcoinSystemRef,
+        data: Self::Value,
+        name: impl ToString,
+    ) -> Result<Self>
+    where
+        Self: Bar,
+    {
+        Self::new_variable_named(cs, data, AllocationMode::ProgramInput, name)
+    }
+
+    fn new_function_output_named(
+        cs: &BitcoinSystemRef,
+        data: Self::Value,
+        name: impl ToString,
+    ) -> Result<Self>
+    where
+        Self: Bar,
+    {
+        Self::new_variable_named(cs, data, AllocationMode::FunctionOutput, name)
+    }
+
+    fn new_hint_named(cs: &BitcoinSystemRef, data: Self::Value, name: impl ToString) -> Result<Self>
+    where
+        Self: Bar,
+    {
+        Self::new_variable_named(cs, data, AllocationMode::Hint, name)
+    }
 }
 
 pub trait CopyBar: Bar + AllocBar {
     fn copy(&self) -> Result<Self> {
-        let cs = self.cs();
-        cs.insert_script(dummy_script, self.variables())?;
+        let cs = self.try_cs()?;
+        cs.insert_pure_script(dummy_script, self.variables())?;
         Self::new_function_output(&cs, self.value()?)
     }
 }
 
 impl<T: Bar + AllocBar> CopyBar for T {}
 
+/// An empty array has no constraint system, so `cs` panics on it and `try_cs` fails.
+impl<T: Bar, const N: usize> Bar for [T; N] {
+    fn cs(&self) -> BitcoinSystemRef {
+        self.try_cs().unwrap()
+    }
+
+    fn try_cs(&self) -> Result<BitcoinSystemRef> {
+        try_cs_of_all(self.iter())
+    }
+
+    fn variables(&self) -> Vec<usize> {
+        self.iter().flat_map(|v| v.variables()).collect()
+    }
+}
+
+impl<T: FixedLengthBar, const N: usize> FixedLengthBar for [T; N] {
+    fn length() -> usize {
+        T::length() * N
+    }
+}
+
+/// The value of an array is a vector, as serde does not support arrays of any length.
+impl<T: AllocBar, const N: usize> AllocBar for [T; N] {
+    type Value = Vec<T::Value>;
+
+    fn value(&self) -> Result<Self::Value> {
+        self.iter().map(|v| v.value()).collect()
+    }
+
+    fn new_variable(
+        cs: &BitcoinSystemRef,
+        data: Self::Value,
+        mode: AllocationMode,
+    ) -> Result<Self> {
+        if data.len() != N {
+            return Err(Error::msg(format!(
+                "An array of {} elements cannot be allocated from {} values",
+                N,
+                data.len()
+            )));
+        }
+        let res = data
+            .into_iter()
+            .map(|v| T::new_variable(cs, v, mode))
+            .collect::<Result<Vec<T>>>()?;
+        Ok(res.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+}
+
+/// A vector of variables, whose length is fixed when it is allocated. As the length is not known
+/// from the type, a vector does not implement `FixedLengthBar`. An empty vector has no constraint
+/// system, so `cs` panics on it and `try_cs` fails.
+impl<T: Bar> Bar for Vec<T> {
+    fn cs(&self) -> BitcoinSystemRef {
+        self.try_cs().unwrap()
+    }
+
+    fn try_cs(&self) -> Result<BitcoinSystemRef> {
+        try_cs_of_all(self.iter())
+    }
+
+    fn variables(&self) -> Vec<usize> {
+        self.iter().flat_map(|v| v.variables()).collect()
+    }
+}
+
+impl<T: AllocBar> AllocBar for Vec<T> {
+    type Value = Vec<T::Value>;
+
+    fn value(&self) -> Result<Self::Value> {
+        self.iter().map(|v| v.value()).collect()
+    }
+
+    fn new_variable(
+        cs: &BitcoinSystemRef,
+        data: Self::Value,
+        mode: AllocationMode,
+    ) -> Result<Self> {
+        data.into_iter()
+            .map(|v| T::new_variable(cs, v, mode))
+            .collect()
+    }
+}
+
+/// A map of variables, which are allocated in the order of their keys. As for a vector, its
+/// length is only known once it is allocated, and an empty map has no constraint system.
+impl<K: Ord, T: Bar> Bar for BTreeMap<K, T> {
+    fn cs(&self) -> BitcoinSystemRef {
+        self.try_cs().unwrap()
+    }
+
+    fn try_cs(&self) -> Result<BitcoinSystemRef> {
+        try_cs_of_all(self.values())
+    }
+
+    fn variables(&self) -> Vec<usize> {
+        self.values().flat_map(|v| v.variables()).collect()
+    }
+}
+
+impl<K: Ord + Clone + Serialize + DeserializeOwned, T: AllocBar> AllocBar for BTreeMap<K, T> {
+    type Value = BTreeMap<K, T::Value>;
+
+    fn value(&self) -> Result<Self::Value> {
+        self.iter()
+            .map(|(k, v)| Ok((k.clone(), v.value()?)))
+            .collect()
+    }
+
+    fn new_variable(
+        cs: &BitcoinSystemRef,
+        data: Self::Value,
+        mode: AllocationMode,
+    ) -> Result<Self> {
+        data.into_iter()
+            .map(|(k, v)| Ok((k, T::new_variable(cs, v, mode)?)))
+            .collect()
+    }
+}
+
+/// Returns the constraint system shared by the variables, or `DslError::EmptyBar` if there are
+/// none.
+fn try_cs_of_all<'a, T: Bar + 'a>(
+    vars: impl IntoIterator<Item = &'a T>,
+) -> Result<BitcoinSystemRef> {
+    let mut vars = vars.into_iter();
+    let mut cs = vars.next().ok_or(DslError::EmptyBar)?.try_cs()?;
+    for v in vars {
+        cs = cs.and(&v.try_cs()?);
+    }
+    Ok(cs)
+}
+
+/// Returns the constraint system shared by the fields of a derived `Bar`, skipping the empty
+/// ones, or `DslError::EmptyBar` if they are all empty.
+#[doc(hidden)]
+pub fn try_cs_of_fields(
+    fields: impl IntoIterator<Item = Result<BitcoinSystemRef>>,
+) -> Result<BitcoinSystemRef> {
+    let mut res: Option<BitcoinSystemRef> = None;
+    for cs in fields {
+        match cs {
+            Ok(cs) => {
+                res = Some(match res {
+                    Some(res) => res.and(&cs),
+                    None => cs,
+                })
+            }
+            Err(err) if matches!(err.downcast_ref::<DslError>(), Some(DslError::EmptyBar)) => {}
+            Err(err) => return Err(err),
+        }
+    }
+    res.ok_or_else(|| DslError::EmptyBar.into())
+}
+
+macro_rules! impl_bar_for_tuple {
+    ($($name:ident: $idx:tt),+) => {
+        impl<$($name: Bar),+> Bar for ($($name,)+) {
+            fn cs(&self) -> BitcoinSystemRef {
+                let cs = self.0.cs();
+                $(let cs = cs.and(&self.$idx.cs());)+
+                cs
+            }
+
+            fn try_cs(&self) -> Result<BitcoinSystemRef> {
+                let cs = self.0.try_cs()?;
+                $(let cs = cs.and(&self.$idx.try_cs()?);)+
+                Ok(cs)
+            }
+
+            fn variables(&self) -> Vec<usize> {
+                let mut v = vec![];
+                $(v.extend(self.$idx.variables());)+
+                v
+            }
+        }
+
+        impl<$($name: FixedLengthBar),+> FixedLengthBar for ($($name,)+) {
+            fn length() -> usize {
+                0 $(+ $name::length())+
+            }
+        }
+
+        impl<$($name: AllocBar),+> AllocBar for ($($name,)+) {
+            type Value = ($($name::Value,)+);
+
+            fn value(&self) -> Result<Self::Value> {
+                Ok(($(self.$idx.value()?,)+))
+            }
+
+            fn new_variable(
+                cs: &BitcoinSystemRef,
+                data: Self::Value,
+                mode: AllocationMode,
+            ) -> Result<Self> {
+                Ok(($($name::new_variable(cs, data.$idx, mode)?,)+))
+            }
+        }
+    };
+}
+
+impl_bar_for_tuple!(A: 0, B: 1);
+impl_bar_for_tuple!(A: 0, B: 1, C: 2);
+impl_bar_for_tuple!(A: 0, B: 1, C: 2, D: 3);
+
 pub(crate) fn dummy_script() -> Script {
     script! {}
 }
@@ -84,3 +363,97 @@ pub enum AllocationMode {
 fn single_elem_equalverify() -> Script {
     Script::from(vec![OP_EQUALVERIFY.to_u8()])
 }
+
+#[cfg(test)]
+mod test {
+    use crate::bar::{AllocBar, Bar, FixedLengthBar};
+    use crate::basic::bool::BoolBar;
+    use crate::basic::i32::I32Bar;
+    use crate::bitcoin_system::BitcoinSystemRef;
+    use crate::error::DslError;
+    use crate::test_program;
+    use crate::treepp::*;
+    use std::collections::BTreeMap;
+
+    #[derive(Clone, Bar, FixedLengthBar, AllocBar)]
+    struct PairBar {
+        a: I32Bar,
+        b: BoolBar,
+        #[bar(skip)]
+        tag: usize,
+    }
+
+    #[test]
+    fn test_derive_bar() {
+        let cs = BitcoinSystemRef::new_ref();
+        let pair = PairBar::new_hint(
+            &cs,
+            PairValue {
+                a: 5,
+                b: true,
+                tag: 7,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(PairBar::length(), 2);
+        assert_eq!(pair.variables(), vec![pair.a.variable, pair.b.variable]);
+
+        let value = pair.value().unwrap();
+        assert_eq!((value.a, value.b, value.tag), (5, true, 7));
+
+        cs.set_program_output(&pair).unwrap();
+        test_program(cs, script! { 5 1 }).unwrap();
+    }
+
+    #[test]
+    fn test_empty_bar() {
+        let empty: Vec<I32Bar> = vec![];
+        let Some(DslError::EmptyBar) = empty.try_cs().unwrap_err().downcast_ref::<DslError>()
+        else {
+            panic!("an empty vector has a constraint system");
+        };
+        assert!(empty.equalverify(&vec![]).is_err());
+
+        let cs = BitcoinSystemRef::new_ref();
+        let a = I32Bar::new_hint(&cs, 5).unwrap();
+        assert!(vec![a.clone()].try_cs().is_ok());
+        assert!([a].try_cs().is_ok());
+    }
+
+    #[derive(Clone, Bar, AllocBar)]
+    struct ListsBar {
+        list: Vec<I32Bar>,
+        map: BTreeMap<usize, I32Bar>,
+    }
+
+    #[test]
+    fn test_derive_bar_with_empty_field() {
+        let cs = BitcoinSystemRef::new_ref();
+        let lists = ListsBar::new_hint(
+            &cs,
+            ListsValue {
+                list: vec![],
+                map: BTreeMap::from([(3, 2), (1, 1)]),
+            },
+        )
+        .unwrap();
+
+        assert!(lists.try_cs().is_ok());
+        assert_eq!(
+            lists.variables(),
+            vec![lists.map[&1].variable, lists.map[&3].variable]
+        );
+        assert_eq!(lists.value().unwrap().map, BTreeMap::from([(1, 1), (3, 2)]));
+
+        let empty = ListsBar::new_hint(
+            &cs,
+            ListsValue {
+                list: vec![],
+                map: BTreeMap::new(),
+            },
+        )
+        .unwrap();
+        assert!(empty.try_cs().is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::bar::{AllocBar, AllocationMode, Bar};
+use crate::bar::{AllocBar, AllocationMode, Bar, FixedLengthBar};
 use crate::bitcoin_system::{BitcoinSystemRef, Element};
 use crate::treepp::*;
 use anyhow::Result;
@@ -19,7 +19,9 @@ impl Bar for BoolBar {
     fn variables(&self) -> Vec<usize> {
         vec![self.variable]
     }
+}
 
+impl FixedLengthBar for BoolBar {
     fn length() -> usize {
         1
     }
@@ -50,10 +52,7 @@ impl Not for &BoolBar {
     type Output = BoolBar;
 
     fn not(self) -> Self::Output {
-        self.cs
-            .insert_script(bool_var_not, self.variables())
-            .unwrap();
-        BoolBar::new_function_output(&self.cs, !self.value).unwrap()
+        self.try_not().unwrap()
     }
 }
 
@@ -67,10 +66,7 @@ impl BitAnd<&BoolBar> for &BoolBar {
     type Output = BoolBar;
 
     fn bitand(self, rhs: &BoolBar) -> Self::Output {
-        self.cs
-            .insert_script(bool_var_and, vec![self.variable, rhs.variable])
-            .unwrap();
-        BoolBar::new_function_output(&self.cs, self.value & rhs.value).unwrap()
+        self.try_and(rhs).unwrap()
     }
 }
 
@@ -84,10 +80,7 @@ impl BitOr<&BoolBar> for &BoolBar {
     type Output = BoolBar;
 
     fn bitor(self, rhs: &BoolBar) -> Self::Output {
-        self.cs
-            .insert_script(bool_var_or, vec![self.variable, rhs.variable])
-            .unwrap();
-        BoolBar::new_function_output(&self.cs, self.value | rhs.value).unwrap()
+        self.try_or(rhs).unwrap()
     }
 }
 
@@ -101,10 +94,7 @@ impl BitXor<&BoolBar> for &BoolBar {
     type Output = BoolBar;
 
     fn bitxor(self, rhs: &BoolBar) -> Self::Output {
-        self.cs
-            .insert_script(bool_var_xor, vec![self.variable, rhs.variable])
-            .unwrap();
-        BoolBar::new_function_output(&self.cs, self.value ^ rhs.value).unwrap()
+        self.try_xor(rhs).unwrap()
     }
 }
 
@@ -117,6 +107,39 @@ fn bool_var_xor() -> Script {
 }
 
 impl BoolBar {
+    /// Negates the boolean, failing instead of panicking if the system cannot be extended, such
+    /// as when it has been finalized.
+    pub fn try_not(&self) -> Result<BoolBar> {
+        self.cs.insert_script(bool_var_not, self.variables())?;
+        BoolBar::new_function_output(&self.cs, !self.value)
+    }
+
+    /// Computes the conjunction of two booleans.
+    pub fn try_and(&self, rhs: &BoolBar) -> Result<BoolBar> {
+        self.binary_op(rhs, bool_var_and, self.value & rhs.value)
+    }
+
+    /// Computes the disjunction of two booleans.
+    pub fn try_or(&self, rhs: &BoolBar) -> Result<BoolBar> {
+        self.binary_op(rhs, bool_var_or, self.value | rhs.value)
+    }
+
+    /// Computes the exclusive disjunction of two booleans.
+    pub fn try_xor(&self, rhs: &BoolBar) -> Result<BoolBar> {
+        self.binary_op(rhs, bool_var_xor, self.value ^ rhs.value)
+    }
+
+    fn binary_op(
+        &self,
+        rhs: &BoolBar,
+        script_generator: fn() -> Script,
+        res: bool,
+    ) -> Result<BoolBar> {
+        let cs = self.cs.try_and(&rhs.cs)?;
+        cs.insert_script(script_generator, [self.variable, rhs.variable])?;
+        BoolBar::new_function_output(&cs, res)
+    }
+
     pub fn verify(self) {
         assert!(self.value);
         self.cs
@@ -1,6 +1,7 @@
-use crate::bar::{AllocBar, AllocationMode, Bar};
+use crate::bar::{AllocBar, AllocationMode, Bar, FixedLengthBar};
 use crate::basic::u8::U8Bar;
 use crate::bitcoin_system::{BitcoinSystemRef, Element};
+use crate::error::DslError;
 use crate::options::Options;
 use crate::stack::Stack;
 use crate::treepp::*;
@@ -22,7 +23,9 @@ impl Bar for I32Bar {
     fn variables(&self) -> Vec<usize> {
         vec![self.variable]
     }
+}
 
+impl FixedLengthBar for I32Bar {
     fn length() -> usize {
         1
     }
@@ -53,16 +56,7 @@ impl Add for &I32Bar {
     type Output = I32Bar;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let res = self.value.checked_add(rhs.value).unwrap();
-        assert!(res > i32::MIN);
-
-        let cs = self.cs().and(&rhs.cs);
-
-        cs.insert_script(i32_add, [self.variable, rhs.variable])
-            .unwrap();
-
-        let res_var = I32Bar::new_variable(&cs, res, AllocationMode::FunctionOutput).unwrap();
-        res_var
+        self.try_add(rhs).unwrap()
     }
 }
 
@@ -70,16 +64,7 @@ impl Add<&U8Bar> for &I32Bar {
     type Output = I32Bar;
 
     fn add(self, rhs: &U8Bar) -> Self::Output {
-        let res = self.value.checked_add(rhs.value as i32).unwrap();
-        assert!(res > i32::MIN);
-
-        let cs = self.cs().and(&rhs.cs);
-
-        cs.insert_script(i32_add, [self.variable, rhs.variable])
-            .unwrap();
-
-        let res_var = I32Bar::new_variable(&cs, res, AllocationMode::FunctionOutput).unwrap();
-        res_var
+        self.try_add_u8(rhs).unwrap()
     }
 }
 
@@ -93,16 +78,7 @@ impl Sub for &I32Bar {
     type Output = I32Bar;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let res = self.value.checked_sub(rhs.value).unwrap();
-        assert!(res > i32::MIN);
-
-        let cs = self.cs().and(&rhs.cs);
-
-        cs.insert_script(i32_sub, [self.variable, rhs.variable])
-            .unwrap();
-
-        let res_var = I32Bar::new_variable(&cs, res, AllocationMode::FunctionOutput).unwrap();
-        res_var
+        self.try_sub(rhs).unwrap()
     }
 }
 
@@ -110,16 +86,7 @@ impl Sub<&U8Bar> for &I32Bar {
     type Output = I32Bar;
 
     fn sub(self, rhs: &U8Bar) -> Self::Output {
-        let res = self.value.checked_sub(rhs.value as i32).unwrap();
-        assert!(res > i32::MIN);
-
-        let cs = self.cs().and(&rhs.cs);
-
-        cs.insert_script(i32_sub, [self.variable, rhs.variable])
-            .unwrap();
-
-        let res_var = I32Bar::new_variable(&cs, res, AllocationMode::FunctionOutput).unwrap();
-        res_var
+        self.try_sub_u8(rhs).unwrap()
     }
 }
 
@@ -130,6 +97,49 @@ fn i32_sub() -> Script {
 }
 
 impl I32Bar {
+    /// Adds two numbers, failing instead of panicking if the sum does not fit.
+    pub fn try_add(&self, rhs: &I32Bar) -> Result<I32Bar> {
+        let res = self.value.checked_add(rhs.value);
+        self.binary_op(res, &rhs.cs, rhs.variable, i32_add, "the i32 addition")
+    }
+
+    /// Adds a byte to a number, failing instead of panicking if the sum does not fit.
+    pub fn try_add_u8(&self, rhs: &U8Bar) -> Result<I32Bar> {
+        let res = self.value.checked_add(rhs.value as i32);
+        self.binary_op(res, &rhs.cs, rhs.variable, i32_add, "the i32 addition")
+    }
+
+    /// Subtracts two numbers, failing instead of panicking if the difference does not fit.
+    pub fn try_sub(&self, rhs: &I32Bar) -> Result<I32Bar> {
+        let res = self.value.checked_sub(rhs.value);
+        self.binary_op(res, &rhs.cs, rhs.variable, i32_sub, "the i32 subtraction")
+    }
+
+    /// Subtracts a byte from a number, failing instead of panicking if the difference does not
+    /// fit.
+    pub fn try_sub_u8(&self, rhs: &U8Bar) -> Result<I32Bar> {
+        let res = self.value.checked_sub(rhs.value as i32);
+        self.binary_op(res, &rhs.cs, rhs.variable, i32_sub, "the i32 subtraction")
+    }
+
+    fn binary_op(
+        &self,
+        res: Option<i32>,
+        rhs_cs: &BitcoinSystemRef,
+        rhs_variable: usize,
+        script_generator: fn() -> Script,
+        op: &'static str,
+    ) -> Result<I32Bar> {
+        // i32::MIN cannot be represented by a script number
+        let res = res
+            .filter(|&res| res > i32::MIN)
+            .ok_or(DslError::Overflow(op))?;
+
+        let cs = self.cs.try_and(rhs_cs)?;
+        cs.insert_script(script_generator, [self.variable, rhs_variable])?;
+        I32Bar::new_variable(&cs, res, AllocationMode::FunctionOutput)
+    }
+
     pub fn check_format(&self) -> Result<()> {
         self.cs.insert_script(i32_check_format, [self.variable])
     }
@@ -209,6 +219,7 @@ mod test {
     use crate::basic::i32::I32Bar;
     use crate::basic::u8::U8Bar;
     use crate::bitcoin_system::{BitcoinSystemRef, Element};
+    use crate::error::DslError;
     use crate::test_program;
     use crate::treepp::*;
     use num_traits::abs;
@@ -383,4 +394,29 @@ mod test {
             .unwrap();
         }
     }
+
+    #[test]
+    fn test_try_add_i32_overflow() {
+        let cs = BitcoinSystemRef::new_ref();
+
+        let a = I32Bar::new_constant(&cs, i32::MAX).unwrap();
+        let b = I32Bar::new_constant(&cs, 1).unwrap();
+        let err = a.try_add(&b).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DslError>(),
+            Some(DslError::Overflow(_))
+        ));
+
+        let other = BitcoinSystemRef::new_ref();
+        let c = I32Bar::new_constant(&other, 1).unwrap();
+        let err = b.try_sub(&c).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DslError>(),
+            Some(DslError::SystemMismatch)
+        ));
+
+        let c = a.try_sub(&b).unwrap();
+        cs.set_program_output(&c).unwrap();
+        test_program(cs, script! { { i32::MAX - 1 } }).unwrap();
+    }
 }
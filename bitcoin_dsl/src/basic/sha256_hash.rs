@@ -1,8 +1,6 @@
-use crate::bar::{dummy_script, AllocBar, AllocationMode, Bar};
+use crate::bar::{dummy_script, AllocBar, AllocationMode, Bar, FixedLengthBar};
 use crate::basic::str::StrBar;
 use crate::bitcoin_system::{BitcoinSystemRef, Element};
-use crate::options::Options;
-use crate::stack::Stack;
 use crate::treepp::*;
 use anyhow::Result;
 use bitcoin::opcodes::all::OP_CAT;
@@ -29,7 +27,9 @@ impl Bar for Sha256HashBar {
     fn variables(&self) -> Vec<usize> {
         vec![self.variable]
     }
+}
 
+impl FixedLengthBar for Sha256HashBar {
     fn length() -> usize {
         1
     }
@@ -59,16 +59,23 @@ impl Add for &Sha256HashBar {
     type Output = Sha256HashBar;
 
     fn add(self, rhs: Self) -> Sha256HashBar {
-        let cs = self.cs.and(&rhs.cs());
+        self.try_combine(rhs).unwrap()
+    }
+}
+
+impl Sha256HashBar {
+    /// Hashes the concatenation of `rhs` and `self`, failing instead of panicking if the system
+    /// cannot be extended.
+    pub fn try_combine(&self, rhs: &Sha256HashBar) -> Result<Sha256HashBar> {
+        let cs = self.cs.try_and(&rhs.cs)?;
 
         let mut sha256 = Sha256::new();
         Update::update(&mut sha256, rhs.value.as_ref());
         Update::update(&mut sha256, self.value.as_ref());
         let hash = sha256.finalize().to_vec();
 
-        cs.insert_script(hash_combine, [rhs.variable, self.variable])
-            .unwrap();
-        Sha256HashBar::new_function_output(&cs, hash.into()).unwrap()
+        cs.insert_script(hash_combine, [rhs.variable, self.variable])?;
+        Sha256HashBar::new_function_output(&cs, hash.into())
     }
 }
 
@@ -94,9 +101,8 @@ impl<T: Bar + AllocBar> From<&T> for Sha256HashBar {
             cur_hash = Some(sha256.finalize().to_vec());
         }
 
-        let len = variables.len() as u32;
-        let options = Options::new().with_u32("len", len);
-        cs.insert_script_complex(hash_many, variables, &options)
+        let len = variables.len();
+        cs.insert_script_with(move |_| Ok(hash_many(len)), variables)
             .unwrap();
 
         Sha256HashBar::new_function_output(&cs, cur_hash.unwrap().into()).unwrap()
@@ -134,9 +140,8 @@ impl<T: Bar> From<&[T]> for Sha256HashBar {
             cur_hash = Some(sha256.finalize().to_vec());
         }
 
-        let len = variables.len() as u32;
-        let options = Options::new().with_u32("len", len);
-        cs.insert_script_complex(hash_many, variables, &options)
+        let len = variables.len();
+        cs.insert_script_with(move |_| Ok(hash_many(len)), variables)
             .unwrap();
 
         Sha256HashBar::new_function_output(&cs, cur_hash.unwrap().into()).unwrap()
@@ -151,14 +156,13 @@ impl From<&Sha256HashBar> for StrBar {
     }
 }
 
-fn hash_many(_: &mut Stack, options: &Options) -> Result<Script> {
-    let len = options.get_u32("len")?;
-    Ok(script! {
+fn hash_many(len: usize) -> Script {
+    script! {
         OP_SHA256
         for _ in 0..len - 1 {
             OP_CAT OP_SHA256
         }
-    })
+    }
 }
 
 fn hash_combine() -> Script {
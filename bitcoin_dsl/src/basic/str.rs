@@ -1,4 +1,4 @@
-use crate::bar::{AllocBar, AllocationMode, Bar};
+use crate::bar::{AllocBar, AllocationMode, Bar, FixedLengthBar};
 use crate::basic::bool::BoolBar;
 use crate::bitcoin_system::{BitcoinSystemRef, Element};
 use crate::options::Options;
@@ -24,7 +24,9 @@ impl Bar for StrBar {
     fn variables(&self) -> Vec<usize> {
         vec![self.variable]
     }
+}
 
+impl FixedLengthBar for StrBar {
     fn length() -> usize {
         1
     }
@@ -54,19 +56,22 @@ impl Add for &StrBar {
     type Output = StrBar;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let cs = self.cs().and(&rhs.cs());
+        self.try_concat(rhs).unwrap()
+    }
+}
+
+impl StrBar {
+    /// Concatenates two strings, failing instead of panicking if the system cannot be extended.
+    pub fn try_concat(&self, rhs: &StrBar) -> Result<StrBar> {
+        let cs = self.cs.try_and(&rhs.cs)?;
 
         let mut res = self.value.clone();
         res.extend_from_slice(&rhs.value);
 
-        cs.insert_script(str_concatenate_gadget, vec![self.variable, rhs.variable])
-            .unwrap();
-
-        StrBar::new_function_output(&cs, res).unwrap()
+        cs.insert_script(str_concatenate_gadget, vec![self.variable, rhs.variable])?;
+        StrBar::new_function_output(&cs, res)
     }
-}
 
-impl StrBar {
     pub fn len_equalverify(&self, l: usize) {
         assert_eq!(self.value.len(), l);
 
@@ -1,5 +1,6 @@
-use crate::bar::{AllocBar, AllocationMode, Bar};
+use crate::bar::{AllocBar, AllocationMode, Bar, FixedLengthBar};
 use crate::bitcoin_system::{BitcoinSystemRef, Element};
+use crate::error::DslError;
 use crate::treepp::*;
 use anyhow::Result;
 use std::ops::{Add, Sub};
@@ -19,7 +20,9 @@ impl Bar for U8Bar {
     fn variables(&self) -> Vec<usize> {
         vec![self.variable]
     }
+}
 
+impl FixedLengthBar for U8Bar {
     fn length() -> usize {
         1
     }
@@ -49,15 +52,7 @@ impl Add for &U8Bar {
     type Output = U8Bar;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let res = self.value.checked_add(rhs.value).unwrap();
-
-        let cs = self.cs.and(&rhs.cs);
-
-        cs.insert_script(u8_add, [self.variable, rhs.variable])
-            .unwrap();
-
-        let res_var = U8Bar::new_variable(&cs, res, AllocationMode::FunctionOutput).unwrap();
-        res_var
+        self.try_add(rhs).unwrap()
     }
 }
 
@@ -71,15 +66,7 @@ impl Sub for &U8Bar {
     type Output = U8Bar;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let res = self.value.checked_sub(rhs.value).unwrap();
-
-        let cs = self.cs.and(&rhs.cs);
-
-        cs.insert_script(u8_sub, [self.variable, rhs.variable])
-            .unwrap();
-
-        let res_var = U8Bar::new_variable(&cs, res, AllocationMode::FunctionOutput).unwrap();
-        res_var
+        self.try_sub(rhs).unwrap()
     }
 }
 
@@ -90,6 +77,30 @@ fn u8_sub() -> Script {
 }
 
 impl U8Bar {
+    /// Adds two bytes, failing instead of panicking if the sum does not fit.
+    pub fn try_add(&self, rhs: &U8Bar) -> Result<U8Bar> {
+        let res = self
+            .value
+            .checked_add(rhs.value)
+            .ok_or(DslError::Overflow("the u8 addition"))?;
+
+        let cs = self.cs.try_and(&rhs.cs)?;
+        cs.insert_script(u8_add, [self.variable, rhs.variable])?;
+        U8Bar::new_variable(&cs, res, AllocationMode::FunctionOutput)
+    }
+
+    /// Subtracts two bytes, failing instead of panicking if the difference is negative.
+    pub fn try_sub(&self, rhs: &U8Bar) -> Result<U8Bar> {
+        let res = self
+            .value
+            .checked_sub(rhs.value)
+            .ok_or(DslError::Overflow("the u8 subtraction"))?;
+
+        let cs = self.cs.try_and(&rhs.cs)?;
+        cs.insert_script(u8_sub, [self.variable, rhs.variable])?;
+        U8Bar::new_variable(&cs, res, AllocationMode::FunctionOutput)
+    }
+
     pub fn check_format(&self) -> Result<()> {
         self.cs.insert_script(u8_check_format, [self.variable])
     }
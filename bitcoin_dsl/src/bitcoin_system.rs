@@ -1,13 +1,20 @@
-use crate::bar::{AllocationMode, Bar};
+use crate::bar::{AllocBar, AllocationMode, Bar};
+use crate::basic::bool::BoolBar;
+use crate::error::DslError;
 use crate::options::Options;
 use crate::script_generator::ScriptGenerator;
 use crate::stack::Stack;
 use crate::treepp::pushable::*;
 use crate::treepp::*;
-use anyhow::{Error, Result};
+use anyhow::Result;
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::panic::Location;
 use std::rc::Rc;
+use std::sync::Arc;
 
 /// A shared reference to a bitcoin script system that can be stored in high level
 /// variables.
@@ -29,9 +36,73 @@ pub struct BitcoinSystem {
     pub trace: Vec<TraceEntry>,
     pub num_inputs: Option<usize>,
     pub finalized: bool,
+    /// Where each trace entry comes from, in the same order as `trace`.
+    pub trace_meta: Vec<TraceMeta>,
+    /// The currently open scopes, each with its full path.
+    pub scopes: Vec<Arc<str>>,
+    /// The labels of the memory entries that have one.
+    pub labels: HashMap<usize, VariableLabel>,
+    /// Whether allocating a constant reuses an existing constant of the same value.
+    pub dedup_constants: bool,
+    /// The constants that can be reused, by value.
+    pub constants: HashMap<Element, usize>,
+    /// The blocks of constants declared once per program, by name.
+    pub constant_blocks: HashMap<String, Vec<usize>>,
+    /// The number of conditional branches that are open.
+    pub branch_depth: usize,
 }
 
+/// The minimal size of the push of a constant for it to be reused. Picking a constant costs two
+/// to four bytes, so reusing smaller constants does not make the script shorter.
+const MIN_DEDUP_PUSH_LEN: usize = 5;
+
+/// A human-readable label of a memory entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariableLabel {
+    /// The name of the variable, prefixed with the path of the scope where it was named.
+    pub name: Arc<str>,
+    /// The position of the memory entry in the variable, if the variable has several of them.
+    pub index: Option<usize>,
+}
+
+impl Display for VariableLabel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "{}[{}]", self.name, index),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// Describes a memory entry by its index and, if there is one, its label.
+pub fn describe_memory_entry(labels: &HashMap<usize, VariableLabel>, idx: usize) -> String {
+    match labels.get(&idx) {
+        Some(label) => format!("#{} (`{}`)", idx, label),
+        None => format!("#{}", idx),
+    }
+}
+
+/// Where a trace entry comes from.
 #[derive(Clone, Debug)]
+pub struct TraceMeta {
+    /// The location in the source code that created the trace entry.
+    pub location: &'static Location<'static>,
+    /// The path of the innermost scope when the trace entry was created, with the labels of the
+    /// enclosing scopes separated by `/`.
+    pub scope: Option<Arc<str>>,
+}
+
+impl TraceMeta {
+    /// Returns the scope if there is one, or the location otherwise.
+    pub fn label(&self) -> String {
+        match &self.scope {
+            Some(scope) => scope.to_string(),
+            None => format!("{}:{}", self.location.file(), self.location.line()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Element {
     Num(i32),
     Str(Vec<u8>),
@@ -53,6 +124,28 @@ pub enum TraceEntry {
     DeclareOutput(usize),
     RequestHint(usize),
     SystemOutput(usize),
+    /// Starts a conditional block on the given condition, which is consumed.
+    BeginIf(usize),
+    /// Ends the first branch, whose results are the given memory entries, and starts the second.
+    Else(Vec<usize>),
+    /// Ends the second branch, whose results are the given memory entries. The merged results
+    /// are declared as outputs right after.
+    EndIf(Vec<usize>),
+}
+
+impl TraceEntry {
+    /// Returns the memory entries that the trace entry uses or declares.
+    pub fn memory_entries(&self) -> Vec<usize> {
+        match self {
+            TraceEntry::InsertScript(_, inputs, _) => inputs.clone(),
+            TraceEntry::DeclareConstant(idx)
+            | TraceEntry::DeclareOutput(idx)
+            | TraceEntry::RequestHint(idx)
+            | TraceEntry::SystemOutput(idx)
+            | TraceEntry::BeginIf(idx) => vec![*idx],
+            TraceEntry::Else(results) | TraceEntry::EndIf(results) => results.clone(),
+        }
+    }
 }
 
 impl BitcoinSystemRef {
@@ -66,10 +159,34 @@ impl BitcoinSystemRef {
         self.clone()
     }
 
+    /// Returns the constraint system shared by two variables, or an error if they belong to
+    /// different ones.
+    pub fn try_and(&self, other: &Self) -> Result<Self> {
+        if self == other {
+            Ok(self.clone())
+        } else {
+            Err(DslError::SystemMismatch.into())
+        }
+    }
+
+    /// Finalizes the constraint system and moves it out of the reference, so that it can be sent
+    /// to another thread and compiled there. The variables that still refer to this system can no
+    /// longer be used, as the system left behind is empty and finalized.
+    pub fn detach(&self) -> BitcoinSystem {
+        let mut empty = BitcoinSystem::new();
+        empty.finalize();
+
+        let mut sys = std::mem::replace(&mut *self.0.borrow_mut(), empty);
+        sys.finalize();
+        sys
+    }
+
+    #[track_caller]
     pub fn alloc(&self, data: Element, mode: AllocationMode) -> Result<usize> {
         self.0.borrow_mut().alloc(data, mode)
     }
 
+    #[track_caller]
     pub fn insert_script_complex(
         &self,
         script_generator: fn(&mut Stack, &Options) -> Result<Script>,
@@ -81,6 +198,23 @@ impl BitcoinSystemRef {
             .insert_script_complex(script_generator, input_idxs, options)
     }
 
+    /// Inserts a script generated by a closure, which captures the parameters of the script.
+    /// Memory entries that the script locates through the stack still need to be passed as stack
+    /// references in `Options`, with `insert_script_complex`.
+    #[track_caller]
+    pub fn insert_script_with(
+        &self,
+        script_generator: impl Fn(&mut Stack) -> Result<Script> + Send + Sync + 'static,
+        input_idxs: impl IntoIterator<Item = usize>,
+    ) -> Result<()> {
+        self.0.borrow_mut().insert_script_generator(
+            ScriptGenerator::Closure(Arc::new(script_generator)),
+            input_idxs,
+            &Options::new(),
+        )
+    }
+
+    #[track_caller]
     pub fn insert_script(
         &self,
         script_generator: fn() -> Script,
@@ -91,6 +225,19 @@ impl BitcoinSystemRef {
             .insert_script(script_generator, input_idxs, &Options::new())
     }
 
+    /// Inserts a script that only computes its outputs from its inputs and never fails, which
+    /// the dead-value elimination of the compiler can remove if its outputs are unused.
+    #[track_caller]
+    pub fn insert_pure_script(
+        &self,
+        script_generator: fn() -> Script,
+        input_idxs: impl IntoIterator<Item = usize>,
+    ) -> Result<()> {
+        self.0
+            .borrow_mut()
+            .insert_script(script_generator, input_idxs, &Options::new().with_pure())
+    }
+
     pub fn get_element(&self, idx: usize) -> Result<Element> {
         let v = self.0.borrow().get_element(idx)?.clone();
         Ok(v)
@@ -100,13 +247,89 @@ impl BitcoinSystemRef {
         self.0.borrow().get_num(idx)
     }
 
+    /// Sets whether allocating a constant reuses an existing constant of the same value, which
+    /// the compiler then picks instead of pushing it again. Only the constants allocated while
+    /// this is set can be reused, and only the ones whose push is long enough to benefit.
+    pub fn set_constant_dedup(&self, enabled: bool) {
+        self.0.borrow_mut().dedup_constants = enabled;
+    }
+
+    /// Returns the block of constants with the given name, declaring it the first time. This
+    /// allows a table that many gadgets need to be declared only once per program.
+    #[track_caller]
+    pub fn get_or_alloc_constant_block(
+        &self,
+        name: &str,
+        elements: impl FnOnce() -> Vec<Element>,
+    ) -> Result<Vec<usize>> {
+        self.0
+            .borrow_mut()
+            .get_or_alloc_constant_block(name, elements)
+    }
+
     pub fn get_str(&self, idx: usize) -> Result<Vec<u8>> {
         Ok(self.0.borrow().get_str(idx)?.to_vec())
     }
 
+    #[track_caller]
     pub fn set_program_output(&self, var: &impl Bar) -> Result<()> {
         self.0.borrow_mut().set_program_output(var)
     }
+
+    /// Opens a scope, so that the trace entries created until the matching `pop_scope` are
+    /// attributed to `label`.
+    pub fn push_scope(&self, label: impl ToString) {
+        self.0.borrow_mut().push_scope(label)
+    }
+
+    pub fn pop_scope(&self) {
+        self.0.borrow_mut().pop_scope()
+    }
+
+    /// Labels the memory entries of a variable. The label is prefixed with the path of the
+    /// current scope, and indexed if the variable has several memory entries.
+    pub fn label(&self, var: &impl Bar, name: impl ToString) {
+        self.0.borrow_mut().label(&var.variables(), name)
+    }
+
+    pub fn get_label(&self, idx: usize) -> Option<VariableLabel> {
+        self.0.borrow().labels.get(&idx).cloned()
+    }
+
+    /// Runs one of two branches depending on `cond`, which the compiler turns into
+    /// `OP_IF ... OP_ELSE ... OP_ENDIF`, and returns the result of the branch that is taken.
+    ///
+    /// Both branches are traced. They can use the variables created before, but the variables
+    /// that they create cannot be used after the branch, except through the result, and they
+    /// cannot request hints, since only one of the branches is executed.
+    #[track_caller]
+    pub fn if_else<T: Bar + AllocBar>(
+        &self,
+        cond: &BoolBar,
+        then_branch: impl FnOnce() -> Result<T>,
+        else_branch: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        self.0.borrow_mut().begin_if(cond.variable)?;
+        let then_value = then_branch()?;
+        self.0.borrow_mut().else_branch(then_value.variables())?;
+        let else_value = else_branch()?;
+        self.0.borrow_mut().end_if(else_value.variables())?;
+
+        let value = if cond.value {
+            then_value.value()?
+        } else {
+            else_value.value()?
+        };
+        T::new_function_output(self, value)
+    }
+
+    /// Runs `f` inside a scope with the given label.
+    pub fn in_scope<T>(&self, label: impl ToString, f: impl FnOnce() -> T) -> T {
+        self.push_scope(label);
+        let res = f();
+        self.pop_scope();
+        res
+    }
 }
 
 impl BitcoinSystem {
@@ -117,12 +340,61 @@ impl BitcoinSystem {
             trace: vec![],
             num_inputs: None,
             finalized: false,
+            trace_meta: vec![],
+            scopes: vec![],
+            labels: HashMap::new(),
+            dedup_constants: false,
+            constants: HashMap::new(),
+            constant_blocks: HashMap::new(),
+            branch_depth: 0,
         }
     }
 
+    #[track_caller]
+    fn push_trace(&mut self, trace_entry: TraceEntry) {
+        self.trace.push(trace_entry);
+        self.trace_meta.push(TraceMeta {
+            location: Location::caller(),
+            scope: self.scopes.last().cloned(),
+        });
+    }
+
+    pub fn push_scope(&mut self, label: impl ToString) {
+        let path: Arc<str> = match self.scopes.last() {
+            Some(parent) => format!("{}/{}", parent, label.to_string()).into(),
+            None => label.to_string().into(),
+        };
+        self.scopes.push(path);
+    }
+
+    pub fn pop_scope(&mut self) {
+        assert!(self.scopes.pop().is_some(), "No scope is open");
+    }
+
+    pub fn label(&mut self, variables: &[usize], name: impl ToString) {
+        let name: Arc<str> = match self.scopes.last() {
+            Some(scope) => format!("{}/{}", scope, name.to_string()).into(),
+            None => name.to_string().into(),
+        };
+        for (i, &idx) in variables.iter().enumerate() {
+            self.labels.insert(
+                idx,
+                VariableLabel {
+                    name: name.clone(),
+                    index: (variables.len() > 1).then_some(i),
+                },
+            );
+        }
+    }
+
+    pub fn describe(&self, idx: usize) -> String {
+        describe_memory_entry(&self.labels, idx)
+    }
+
+    #[track_caller]
     pub fn alloc(&mut self, data: Element, mode: AllocationMode) -> Result<usize> {
         if self.finalized {
-            return Err(Error::msg("The constraint system has been finalized"));
+            return Err(DslError::Finalized.into());
         }
 
         if mode != AllocationMode::ProgramInput {
@@ -131,124 +403,188 @@ impl BitcoinSystem {
             }
         } else {
             if self.num_inputs.is_some() {
-                return Err(Error::msg(
-                    "Inputs can only be allocated before any execution or allocation for constants or hints",
-                ));
+                return Err(DslError::InputAfterExecution.into());
+            }
+        }
+
+        if mode == AllocationMode::Hint && self.branch_depth > 0 {
+            return Err(DslError::HintInBranch.into());
+        }
+
+        if mode == AllocationMode::Constant && self.dedup_constants && self.branch_depth == 0 {
+            if let Some(&idx) = self.constants.get(&data) {
+                return Ok(idx);
             }
+            if script! { { &data } }.len() >= MIN_DEDUP_PUSH_LEN {
+                self.constants.insert(data.clone(), self.memory_last_idx);
+            }
+        }
+
+        self.alloc_entry(data, mode)
+    }
+
+    /// Returns the block of constants with the given name, declaring it with `elements` if this
+    /// is the first time. The constants of a block are consecutive and never reused individually,
+    /// so that the block can be located through the stack. A block can be used inside a
+    /// conditional branch only if it has been declared before the branch.
+    #[track_caller]
+    pub fn get_or_alloc_constant_block(
+        &mut self,
+        name: &str,
+        elements: impl FnOnce() -> Vec<Element>,
+    ) -> Result<Vec<usize>> {
+        if let Some(variables) = self.constant_blocks.get(name) {
+            return Ok(variables.clone());
+        }
+
+        if self.finalized {
+            return Err(DslError::Finalized.into());
+        }
+        if self.branch_depth > 0 {
+            return Err(DslError::ConstantBlockInBranch(name.to_string()).into());
         }
+        if self.num_inputs.is_none() {
+            self.num_inputs = Some(self.memory_last_idx);
+        }
+
+        let mut variables = vec![];
+        for element in elements() {
+            variables.push(self.alloc_entry(element, AllocationMode::Constant)?);
+        }
+        self.constant_blocks
+            .insert(name.to_string(), variables.clone());
+        Ok(variables)
+    }
 
+    #[track_caller]
+    fn alloc_entry(&mut self, data: Element, mode: AllocationMode) -> Result<usize> {
         let idx = self.memory_last_idx;
         self.memory_last_idx += 1;
 
         if self.memory.get(&idx).is_some() {
-            return Err(Error::msg("Memory is corrupted"));
+            return Err(DslError::MemoryCorrupted(idx).into());
         }
         self.memory.insert(idx, data);
 
         if mode == AllocationMode::Constant {
-            self.trace.push(TraceEntry::DeclareConstant(idx));
+            self.push_trace(TraceEntry::DeclareConstant(idx));
         } else if mode == AllocationMode::Hint {
-            self.trace.push(TraceEntry::RequestHint(idx));
+            self.push_trace(TraceEntry::RequestHint(idx));
         } else if mode == AllocationMode::FunctionOutput {
-            self.trace.push(TraceEntry::DeclareOutput(idx));
+            self.push_trace(TraceEntry::DeclareOutput(idx));
         }
 
         Ok(idx)
     }
 
+    #[track_caller]
     pub fn set_program_output(&mut self, var: &impl Bar) -> Result<()> {
         if self.finalized {
-            return Err(Error::msg("The constraint system has been finalized"));
+            return Err(DslError::Finalized.into());
+        }
+
+        if self.branch_depth > 0 {
+            return Err(DslError::OutputInBranch.into());
         }
 
         let indices = var.variables();
         for &index in indices.iter() {
             if self.memory.get(&index).is_none() {
-                return Err(Error::msg(
-                    "Could not find the memory entry with the given index",
-                ));
+                return Err(DslError::UnknownVariable(index).into());
             }
-            self.trace.push(TraceEntry::SystemOutput(index));
+            self.push_trace(TraceEntry::SystemOutput(index));
         }
         Ok(())
     }
 
     pub fn get_num(&self, idx: usize) -> Result<i32> {
         if self.finalized {
-            return Err(Error::msg("The constraint system has been finalized"));
+            return Err(DslError::Finalized.into());
         }
 
         match self.memory.get(&idx) {
             Some(Element::Num(v)) => Ok(*v),
-            _ => Err(Error::msg(
-                "Cannot read the requested data in memory as a number",
-            )),
+            Some(_) => Err(DslError::ElementTypeMismatch {
+                idx,
+                expected: "a number",
+            }
+            .into()),
+            None => Err(DslError::UnknownVariable(idx).into()),
         }
     }
 
     pub fn get_str(&self, idx: usize) -> Result<&[u8]> {
         if self.finalized {
-            return Err(Error::msg("The constraint system has been finalized"));
+            return Err(DslError::Finalized.into());
         }
 
         match self.memory.get(&idx) {
             Some(Element::Str(v)) => Ok(v.as_slice()),
-            _ => Err(Error::msg(
-                "Cannot read the requested data in memory as a string",
-            )),
+            Some(_) => Err(DslError::ElementTypeMismatch {
+                idx,
+                expected: "a string",
+            }
+            .into()),
+            None => Err(DslError::UnknownVariable(idx).into()),
         }
     }
 
     pub fn get_element(&self, idx: usize) -> Result<&Element> {
         if self.finalized {
-            return Err(Error::msg("The constraint system has been finalized"));
+            return Err(DslError::Finalized.into());
         }
 
         match self.memory.get(&idx) {
             Some(v) => Ok(v),
-            _ => Err(Error::msg("Cannot read the requested data in memory")),
+            None => Err(DslError::UnknownVariable(idx).into()),
         }
     }
 
+    #[track_caller]
     pub fn insert_script(
         &mut self,
         script_generator: fn() -> Script,
         input_idxs: impl IntoIterator<Item = usize>,
         options: &Options,
     ) -> Result<()> {
-        if self.finalized {
-            return Err(Error::msg("The constraint system has been finalized"));
-        }
-
-        if self.num_inputs.is_none() {
-            self.num_inputs = Some(self.memory_last_idx);
-        }
-
-        self.trace.push(TraceEntry::InsertScript(
+        self.insert_script_generator(
             ScriptGenerator::Simple(script_generator),
-            input_idxs.into_iter().collect(),
-            options.clone(),
-        ));
-
-        Ok(())
+            input_idxs,
+            options,
+        )
     }
 
+    #[track_caller]
     pub fn insert_script_complex(
         &mut self,
         script_generator: fn(&mut Stack, &Options) -> Result<Script>,
         input_idxs: impl IntoIterator<Item = usize>,
         options: &Options,
+    ) -> Result<()> {
+        self.insert_script_generator(
+            ScriptGenerator::Complex(script_generator),
+            input_idxs,
+            options,
+        )
+    }
+
+    #[track_caller]
+    pub fn insert_script_generator(
+        &mut self,
+        script_generator: ScriptGenerator,
+        input_idxs: impl IntoIterator<Item = usize>,
+        options: &Options,
     ) -> Result<()> {
         if self.finalized {
-            return Err(Error::msg("The constraint system has been finalized"));
+            return Err(DslError::Finalized.into());
         }
 
         if self.num_inputs.is_none() {
             self.num_inputs = Some(self.memory_last_idx);
         }
 
-        self.trace.push(TraceEntry::InsertScript(
-            ScriptGenerator::Complex(script_generator),
+        self.push_trace(TraceEntry::InsertScript(
+            script_generator,
             input_idxs.into_iter().collect(),
             options.clone(),
         ));
@@ -259,4 +595,241 @@ impl BitcoinSystem {
     pub fn finalize(&mut self) {
         self.finalized = true;
     }
+
+    #[track_caller]
+    pub fn begin_if(&mut self, cond: usize) -> Result<()> {
+        if self.finalized {
+            return Err(DslError::Finalized.into());
+        }
+        if self.num_inputs.is_none() {
+            self.num_inputs = Some(self.memory_last_idx);
+        }
+        self.branch_depth += 1;
+        self.push_trace(TraceEntry::BeginIf(cond));
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn else_branch(&mut self, results: Vec<usize>) -> Result<()> {
+        if self.branch_depth == 0 {
+            return Err(DslError::NoOpenBranch.into());
+        }
+        self.push_trace(TraceEntry::Else(results));
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn end_if(&mut self, results: Vec<usize>) -> Result<()> {
+        if self.branch_depth == 0 {
+            return Err(DslError::NoOpenBranch.into());
+        }
+        self.branch_depth -= 1;
+        self.push_trace(TraceEntry::EndIf(results));
+        Ok(())
+    }
+
+    /// Returns a copy of the system with another trace, which must declare the same memory
+    /// entries or a subset of them.
+    pub(crate) fn with_trace(&self, trace: Vec<TraceEntry>, trace_meta: Vec<TraceMeta>) -> Self {
+        Self {
+            memory: self.memory.clone(),
+            memory_last_idx: self.memory_last_idx,
+            trace,
+            num_inputs: self.num_inputs,
+            finalized: self.finalized,
+            trace_meta,
+            scopes: self.scopes.clone(),
+            labels: self.labels.clone(),
+            dedup_constants: self.dedup_constants,
+            constants: self.constants.clone(),
+            constant_blocks: self.constant_blocks.clone(),
+            branch_depth: self.branch_depth,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bar::AllocBar;
+    use crate::basic::bool::BoolBar;
+    use crate::basic::i32::I32Bar;
+    use crate::bitcoin_system::{BitcoinSystemRef, Element};
+    use crate::compiler::Compiler;
+    use crate::error::DslError;
+    use crate::test_program;
+    use crate::treepp::*;
+
+    fn build(dedup: bool) -> BitcoinSystemRef {
+        let cs = BitcoinSystemRef::new_ref();
+        cs.set_constant_dedup(dedup);
+
+        let a = I32Bar::new_hint(&cs, 1).unwrap();
+        let mut sum = a.clone();
+        for _ in 0..5 {
+            let big = I32Bar::new_constant(&cs, 100_000_000).unwrap();
+            let small = I32Bar::new_constant(&cs, 1).unwrap();
+            sum = &(&sum + &big) + &small;
+        }
+        cs.set_program_output(&sum).unwrap();
+        cs
+    }
+
+    #[test]
+    fn test_constant_dedup() {
+        let cs = build(true);
+        let constants =
+            cs.0.borrow()
+                .memory
+                .values()
+                .filter(|&element| element == &Element::Num(100_000_000))
+                .count();
+        assert_eq!(constants, 1);
+
+        let dedup = Compiler::compile(build(true)).unwrap();
+        let plain = Compiler::compile(build(false)).unwrap();
+        assert!(dedup.script.len() < plain.script.len());
+
+        test_program(build(true), script! { 500000006 }).unwrap();
+    }
+
+    #[test]
+    fn test_constant_block() {
+        let cs = BitcoinSystemRef::new_ref();
+        let elements = || vec![Element::Num(1), Element::Num(2), Element::Num(3)];
+        let first = cs.get_or_alloc_constant_block("block", elements).unwrap();
+        let second = cs.get_or_alloc_constant_block("block", elements).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(cs.0.borrow().trace.len(), 3);
+
+        // a block first used inside a branch would only be declared on one side of it
+        let cs = BitcoinSystemRef::new_ref();
+        let c = BoolBar::new_hint(&cs, true).unwrap();
+        let err = cs
+            .if_else(
+                &c,
+                || {
+                    cs.get_or_alloc_constant_block("block", elements)?;
+                    I32Bar::new_constant(&cs, 1)
+                },
+                || I32Bar::new_constant(&cs, 2),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DslError>(),
+            Some(DslError::ConstantBlockInBranch(_))
+        ));
+
+        // a block declared before the branch can be used inside it
+        let cs = BitcoinSystemRef::new_ref();
+        let c = BoolBar::new_hint(&cs, false).unwrap();
+        let block = cs.get_or_alloc_constant_block("block", elements).unwrap();
+        let res = cs
+            .if_else(
+                &c,
+                || I32Bar::new_constant(&cs, 1),
+                || {
+                    let reused = cs.get_or_alloc_constant_block("block", elements)?;
+                    assert_eq!(reused, block);
+                    I32Bar::new_constant(&cs, 2)
+                },
+            )
+            .unwrap();
+        cs.set_program_output(&res).unwrap();
+        test_program(cs, script! { 2 }).unwrap();
+    }
+
+    #[test]
+    fn test_if_else() {
+        for cond in [true, false] {
+            let cs = BitcoinSystemRef::new_ref();
+            let c = BoolBar::new_hint(&cs, cond).unwrap();
+            let a = I32Bar::new_hint(&cs, 10).unwrap();
+            let b = I32Bar::new_hint(&cs, 20).unwrap();
+
+            let res = cs
+                .if_else(
+                    &c,
+                    || Ok(&a + &b),
+                    || {
+                        let k = I32Bar::new_constant(&cs, 3)?;
+                        Ok(&(&a - &b) + &k)
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.value, if cond { 30 } else { -7 });
+
+            let res = &res + &a;
+            cs.set_program_output(&res).unwrap();
+
+            let expected = if cond { 40 } else { 3 };
+            test_program(cs, script! { { expected } }).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_if_else_leak() {
+        let cs = BitcoinSystemRef::new_ref();
+        let c = BoolBar::new_hint(&cs, true).unwrap();
+        let a = I32Bar::new_hint(&cs, 10).unwrap();
+        let b = I32Bar::new_hint(&cs, 20).unwrap();
+
+        // a variable of the first branch used after it
+        let mut leaked = None;
+        let res = cs
+            .if_else(
+                &c,
+                || {
+                    let sum = &a + &b;
+                    leaked = Some(sum.clone());
+                    Ok(sum)
+                },
+                || Ok(a.clone()),
+            )
+            .unwrap();
+        let res = &res + &leaked.unwrap();
+        cs.set_program_output(&res).unwrap();
+
+        assert!(Compiler::compile(cs).is_err());
+
+        let cs = BitcoinSystemRef::new_ref();
+        let c = BoolBar::new_hint(&cs, true).unwrap();
+        assert!(cs
+            .if_else(&c, || I32Bar::new_hint(&cs, 1), || I32Bar::new_hint(&cs, 2))
+            .is_err());
+    }
+
+    #[test]
+    fn test_detach() {
+        let build = |v: i32| {
+            let cs = BitcoinSystemRef::new_ref();
+            let a = I32Bar::new_hint(&cs, v).unwrap();
+            let b = I32Bar::new_constant(&cs, 2 * v).unwrap();
+            cs.set_program_output(&(&a + &b)).unwrap();
+            cs
+        };
+
+        let expected = (0..4)
+            .map(|v| Compiler::compile(build(v)).unwrap().script)
+            .collect::<Vec<_>>();
+
+        let systems = (0..4).map(|v| build(v).detach()).collect::<Vec<_>>();
+        let scripts = std::thread::scope(|s| {
+            let handles = systems
+                .iter()
+                .map(|cs| s.spawn(|| Compiler::compile_system(cs, &Default::default())))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap().unwrap().script)
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(scripts, expected);
+
+        // the variables left behind cannot be used anymore
+        let cs = BitcoinSystemRef::new_ref();
+        let a = I32Bar::new_hint(&cs, 1).unwrap();
+        let _ = cs.detach();
+        assert!(I32Bar::new_hint(&cs, 2).is_err());
+        assert!(cs.set_program_output(&a).is_err());
+    }
 }
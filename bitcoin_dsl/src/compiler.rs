@@ -1,33 +1,452 @@
-use crate::bitcoin_system::{BitcoinSystemRef, Element, TraceEntry};
+use crate::artifact::Artifact;
+use crate::bitcoin_system::{
+    describe_memory_entry, BitcoinSystem, BitcoinSystemRef, Element, TraceEntry, TraceMeta,
+    VariableLabel,
+};
+use crate::dce::eliminate_dead_code;
+use crate::error::DslError;
+use crate::peephole::optimize_program;
+use crate::profiler::CostReport;
+use crate::scheduler::schedule;
 use crate::stack::Stack;
+use crate::stack_effect::check_stack_effect;
 use crate::treepp::*;
-use anyhow::Result;
-use bitcoin::opcodes::Ordinary::{OP_1SUB, OP_2DROP, OP_DEPTH, OP_DROP, OP_FROMALTSTACK, OP_ROLL};
+use anyhow::{Context, Result};
+use bitcoin::opcodes::Ordinary::{
+    OP_1SUB, OP_2DROP, OP_DEPTH, OP_DROP, OP_ELSE, OP_ENDIF, OP_FROMALTSTACK, OP_IF, OP_ROLL,
+    OP_TOALTSTACK,
+};
 use bitcoin::ScriptBuf;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
 
+/// A compiled program, which can be saved as an artifact. The trace, together with everything
+/// that refers to its entries, the labels, and the profile are debugging information that is
+/// not saved, so a loaded program can be executed but not stepped through by the debugger.
+#[derive(Serialize, Deserialize)]
 pub struct CompiledProgram {
     pub input: Vec<Element>,
     pub hint: Vec<Element>,
+    #[serde(with = "crate::artifact::script_serde")]
     pub script: Script,
+    /// The trace from which the script has been compiled.
+    #[serde(skip)]
+    pub trace: Vec<TraceEntry>,
+    /// Where each trace entry comes from.
+    #[serde(skip)]
+    pub trace_meta: Vec<TraceMeta>,
+    /// The labels of the memory entries that have one.
+    #[serde(skip)]
+    pub labels: HashMap<usize, VariableLabel>,
+    /// The offset in the script where the bytes of each trace entry start, followed by the offset
+    /// where the final cleanup of the stack starts.
+    #[serde(skip)]
+    pub trace_offsets: Vec<usize>,
+    /// The largest number of elements on the stack and the altstack between two trace entries,
+    /// as simulated by the compiler. Elements that a script uses internally are not counted.
+    pub max_stack_items: usize,
+    /// The cost of the program by gadget, if the compiler is in profiling mode.
+    #[serde(skip)]
+    pub profile: Option<CostReport>,
+    /// The memory entries rolled to the top of the stack for the last time, as pairs of the index
+    /// of the trace entry and the index of the memory entry.
+    #[serde(skip)]
+    pub pulled: Vec<(usize, usize)>,
+    /// The memory entries parked on the altstack, in the order in which they are parked, as
+    /// triples of the index of the trace entry that parks it, the index of the trace entry that
+    /// brings it back, and the index of the memory entry.
+    #[serde(skip)]
+    pub parked: Vec<(usize, usize, usize)>,
+    /// The outputs dropped right after the script that creates them, as nothing uses them.
+    #[serde(skip)]
+    pub dropped: HashSet<usize>,
+}
+
+impl Artifact for CompiledProgram {
+    const KIND: &'static str = "compiled program";
+}
+
+impl CompiledProgram {
+    /// Returns whether the program has its trace and the offsets of its trace entries, which a
+    /// program loaded from an artifact does not have.
+    pub fn has_debug_info(&self) -> bool {
+        self.trace_offsets.len() == self.trace.len() + 1
+    }
+
+    pub fn describe(&self, idx: usize) -> String {
+        describe_memory_entry(&self.labels, idx)
+    }
+
+    /// Returns the index of the trace entry that generates the byte at `offset` in the script, or
+    /// `None` if the byte belongs to the final cleanup of the stack.
+    pub fn trace_idx_at(&self, offset: usize) -> Option<usize> {
+        let idx = self.trace_offsets.partition_point(|&start| start <= offset);
+        if idx == 0 || idx == self.trace_offsets.len() {
+            None
+        } else {
+            Some(idx - 1)
+        }
+    }
+
+    /// Returns the memory entries on the main stack when the trace entry `trace_idx` starts, from
+    /// the bottom to the top. The hints that have not been requested yet, which lie below, and the
+    /// memory entries parked on the altstack are not included.
+    pub fn stack_layout(&self, trace_idx: usize) -> Vec<usize> {
+        let mut present = (0..self.input.len()).collect::<Vec<usize>>();
+        let remove = |present: &mut Vec<usize>, idx: usize| {
+            if let Some(pos) = present.iter().rposition(|&other| other == idx) {
+                present.remove(pos);
+            }
+        };
+
+        // the layouts at the start of the open branches
+        let mut branches = vec![];
+
+        // within a trace entry, the compiler first brings back the parked entries, then parks
+        // other ones, then moves the inputs, and finally declares the new entries
+        for (t, trace_entry) in self.trace.iter().enumerate().take(trace_idx) {
+            for &(_, end, idx) in self.parked.iter().rev() {
+                if end == t {
+                    present.push(idx);
+                }
+            }
+            for &(start, _, idx) in self.parked.iter() {
+                if start == t {
+                    remove(&mut present, idx);
+                }
+            }
+            for &(pulled_t, idx) in self.pulled.iter() {
+                if pulled_t == t {
+                    remove(&mut present, idx);
+                }
+            }
+            match trace_entry {
+                TraceEntry::DeclareOutput(idx) if self.dropped.contains(idx) => {}
+                TraceEntry::BeginIf(_) => branches.push(present.clone()),
+                TraceEntry::Else(_) => {
+                    if let Some(layout) = branches.last() {
+                        present = layout.clone();
+                    }
+                }
+                TraceEntry::EndIf(_) => {
+                    if let Some(layout) = branches.pop() {
+                        present = layout;
+                    }
+                }
+                TraceEntry::DeclareConstant(idx)
+                | TraceEntry::DeclareOutput(idx)
+                | TraceEntry::RequestHint(idx) => {
+                    present.push(*idx);
+                }
+                _ => {}
+            }
+        }
+        present
+    }
+
+    /// Returns the bytes generated by the trace entry with the given index, or the bytes of the
+    /// final cleanup if `trace_idx` is the length of the trace.
+    pub fn trace_entry_bytes(&self, trace_idx: usize) -> &[u8] {
+        let start = self.trace_offsets[trace_idx];
+        let end = if trace_idx + 1 < self.trace_offsets.len() {
+            self.trace_offsets[trace_idx + 1]
+        } else {
+            self.script.len()
+        };
+        &self.script.as_bytes()[start..end]
+    }
+}
+
+/// The limits that a compiled program must respect.
+#[derive(Clone, Copy, Debug)]
+pub struct CompilerBudget {
+    /// The maximal number of elements on the stack and the altstack together.
+    pub max_stack_items: usize,
+    /// The maximal size of the script, in bytes. Each byte of the script costs one weight unit
+    /// in the witness.
+    pub max_script_size: usize,
+}
+
+impl Default for CompilerBudget {
+    fn default() -> Self {
+        Self {
+            max_stack_items: 1000,
+            max_script_size: 400_000,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BudgetKind {
+    StackItems,
+    ScriptSize,
+}
+
+/// The error returned when a program does not fit in the budget of the compiler.
+#[derive(Clone, Debug)]
+pub struct BudgetExceeded {
+    pub kind: BudgetKind,
+    /// The number of stack elements or bytes that the program uses at this point.
+    pub value: usize,
+    pub limit: usize,
+    /// The index of the trace entry at which the budget is exceeded, or `None` if this happens
+    /// in the final cleanup of the stack.
+    pub trace_idx: Option<usize>,
+    pub trace_entry: Option<TraceEntry>,
+    /// The descriptions of the memory entries that the trace entry uses or declares.
+    pub variables: Vec<String>,
+}
+
+impl BudgetExceeded {
+    fn new(
+        kind: BudgetKind,
+        value: usize,
+        limit: usize,
+        trace_idx: Option<usize>,
+        cs: &BitcoinSystem,
+    ) -> Self {
+        let trace_entry = trace_idx.map(|idx| cs.trace[idx].clone());
+        let variables = trace_entry
+            .as_ref()
+            .map(|trace_entry| {
+                trace_entry
+                    .memory_entries()
+                    .into_iter()
+                    .map(|idx| cs.describe(idx))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            kind,
+            value,
+            limit,
+            trace_idx,
+            trace_entry,
+            variables,
+        }
+    }
+}
+
+impl Display for BudgetExceeded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let what = match self.kind {
+            BudgetKind::StackItems => "stack items",
+            BudgetKind::ScriptSize => "script bytes",
+        };
+        match (&self.trace_idx, &self.trace_entry) {
+            (Some(trace_idx), Some(trace_entry)) => write!(
+                f,
+                "The program uses {} {} (limit: {}) at the trace entry {} on {}: {:?}",
+                self.value,
+                what,
+                self.limit,
+                trace_idx,
+                self.variables.join(", "),
+                trace_entry
+            ),
+            _ => write!(
+                f,
+                "The program uses {} {} (limit: {}) in the final cleanup of the stack",
+                self.value, what, self.limit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+/// The configuration of the compiler.
+#[derive(Clone, Debug)]
+pub struct CompilerConfig {
+    /// If set, the compilation fails with `DslError::BudgetExceeded` when the program does not
+    /// fit.
+    pub budget: Option<CompilerBudget>,
+    /// If set, the compiler attributes the cost of the script to the gadgets. The report
+    /// describes the script before the peephole optimization.
+    pub profile: bool,
+    /// If set, the compiler rewrites the script with the peephole optimizer.
+    pub peephole: bool,
+    /// If set, the compiler reorders the independent trace entries before compiling them. The
+    /// trace of the compiled program is then the reordered one, with the memory entries
+    /// renumbered in the order of their declaration.
+    pub schedule: bool,
+    /// If set, the compiler parks the memory entries that are not used for a long time on the
+    /// altstack, so that they do not lengthen the moves of the other ones.
+    pub altstack: bool,
+    /// If set, the compiler removes the pure scripts whose outputs are unused, and drops the
+    /// other unused outputs right after the script that creates them.
+    pub dead_values: bool,
+    /// If set, the compiler runs the script of each gadget on the values of its inputs and fails
+    /// with `DslError::StackEffectMismatch` if the script does not leave exactly the declared
+    /// outputs. The gadgets inside branches are skipped, as the values of their inputs are only
+    /// meaningful when the branch is taken.
+    pub check_stack_effects: bool,
+}
+
+impl Default for CompilerConfig {
+    fn default() -> Self {
+        Self {
+            budget: None,
+            profile: false,
+            peephole: false,
+            schedule: false,
+            altstack: false,
+            dead_values: false,
+            check_stack_effects: false,
+        }
+    }
+}
+
+impl CompilerConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_budget(mut self, budget: CompilerBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    pub fn with_profiling(mut self) -> Self {
+        self.profile = true;
+        self
+    }
+
+    pub fn with_peephole(mut self) -> Self {
+        self.peephole = true;
+        self
+    }
+
+    pub fn with_scheduling(mut self) -> Self {
+        self.schedule = true;
+        self
+    }
+
+    pub fn with_altstack(mut self) -> Self {
+        self.altstack = true;
+        self
+    }
+
+    pub fn with_dead_value_elimination(mut self) -> Self {
+        self.dead_values = true;
+        self
+    }
+
+    pub fn with_stack_effect_check(mut self) -> Self {
+        self.check_stack_effects = true;
+        self
+    }
+}
+
+/// Tracks the resources used by the program during the compilation.
+struct BudgetTracker {
+    budget: Option<CompilerBudget>,
+    max_stack_items: usize,
+}
+
+impl BudgetTracker {
+    fn check_stack(
+        &mut self,
+        stack_items: usize,
+        trace_idx: Option<usize>,
+        cs: &BitcoinSystem,
+    ) -> Result<()> {
+        self.max_stack_items = self.max_stack_items.max(stack_items);
+        match self.budget {
+            Some(budget) if stack_items > budget.max_stack_items => {
+                Err(DslError::BudgetExceeded(BudgetExceeded::new(
+                    BudgetKind::StackItems,
+                    stack_items,
+                    budget.max_stack_items,
+                    trace_idx,
+                    cs,
+                ))
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_script(
+        &self,
+        script_size: usize,
+        trace_idx: Option<usize>,
+        cs: &BitcoinSystem,
+    ) -> Result<()> {
+        match self.budget {
+            Some(budget) if script_size > budget.max_script_size => {
+                Err(DslError::BudgetExceeded(BudgetExceeded::new(
+                    BudgetKind::ScriptSize,
+                    script_size,
+                    budget.max_script_size,
+                    trace_idx,
+                    cs,
+                ))
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 pub struct Compiler;
 
 impl Compiler {
     pub fn compile(cs: BitcoinSystemRef) -> Result<CompiledProgram> {
-        let cs = cs.0.borrow_mut();
+        Self::compile_with_config(cs, &CompilerConfig::default())
+    }
+
+    pub fn compile_with_config(
+        cs: BitcoinSystemRef,
+        config: &CompilerConfig,
+    ) -> Result<CompiledProgram> {
+        let cs = cs.0.borrow();
+        Self::compile_system(&cs, config)
+    }
+
+    pub fn compile_system(cs: &BitcoinSystem, config: &CompilerConfig) -> Result<CompiledProgram> {
+        let pruned = config.dead_values.then(|| eliminate_dead_code(cs));
+        let cs = pruned.as_ref().unwrap_or(cs);
+
+        let scheduled = if config.schedule {
+            Some(schedule(cs)?)
+        } else {
+            None
+        };
+        let cs = scheduled.as_ref().unwrap_or(cs);
+
+        Self::generate(cs, config)
+    }
 
+    fn generate(cs: &BitcoinSystem, config: &CompilerConfig) -> Result<CompiledProgram> {
         // step 1: count the last visit of all the memory entries
         let num_memory_entries = cs.memory_last_idx;
         let mut last_visit = vec![-1isize; num_memory_entries];
+        let mut all_stack_refs = HashSet::new();
 
         let mut cur_time = 0;
         for trace_entry in cs.trace.iter() {
             match trace_entry {
-                TraceEntry::InsertScript(_, inputs, _) => {
+                TraceEntry::InsertScript(_, inputs, options) => {
                     for &i in inputs.iter() {
                         last_visit[i] = cur_time;
                     }
+                    // elements located through the stack must stay there until the script is run
+                    for i in options.stack_refs() {
+                        last_visit[i] = cur_time;
+                        all_stack_refs.insert(i);
+                    }
+                    cur_time += 1;
+                }
+                TraceEntry::BeginIf(i) => {
+                    last_visit[*i] = cur_time;
+                    cur_time += 1;
+                }
+                TraceEntry::Else(results) | TraceEntry::EndIf(results) => {
+                    for &i in results.iter() {
+                        last_visit[i] = cur_time;
+                    }
                     cur_time += 1;
                 }
                 _ => {}
@@ -69,20 +488,79 @@ impl Compiler {
         let mut hint = Vec::<Element>::new();
 
         let mut cur_time = 0;
+        let mut trace_offsets = Vec::with_capacity(cs.trace.len() + 1);
+        let mut move_bytes = vec![0; cs.trace.len()];
+        let mut pulled = vec![];
 
-        for trace_entry in cs.trace.iter() {
+        let parked = if config.altstack {
+            plan_parking(cs, input.len())
+        } else {
+            vec![]
+        };
+        let mut next_parked = 0;
+        let mut parked_until = vec![];
+        let mut dropped = HashSet::new();
+        let mut branches = Vec::<Branch>::new();
+
+        // the hints are all on the stack from the beginning, until they are requested
+        let mut remaining_hints = cs
+            .trace
+            .iter()
+            .filter(|trace_entry| matches!(trace_entry, TraceEntry::RequestHint(_)))
+            .count();
+        let mut tracker = BudgetTracker {
+            budget: config.budget,
+            max_stack_items: 0,
+        };
+        tracker.check_stack(
+            stack.get_num_elements_in_stack()? + remaining_hints,
+            None,
+            cs,
+        )?;
+
+        for (trace_idx, trace_entry) in cs.trace.iter().enumerate() {
+            trace_offsets.push(script.len());
             match trace_entry {
                 TraceEntry::InsertScript(script_generator, inputs, options) => {
+                    // bring back the parked entries that the script uses, and then park the ones
+                    // that the next scripts do not use for a long time
+                    while parked_until.last() == Some(&trace_idx) {
+                        parked_until.pop();
+                        stack.unpark()?;
+                        script.push(OP_FROMALTSTACK.to_u8());
+                    }
+                    while let Some(&(start, end, idx)) = parked.get(next_parked) {
+                        if start != trace_idx {
+                            break;
+                        }
+                        let pos = stack.get_relative_position(idx)?;
+                        script.extend_from_slice(roll_script(pos).as_bytes());
+                        script.push(OP_TOALTSTACK.to_u8());
+                        stack.park(idx)?;
+                        parked_until.push(end);
+                        next_parked += 1;
+                    }
+
+                    let stack_refs = options.stack_refs();
                     for (i, &input_idx) in inputs.iter().enumerate() {
-                        let pos = stack.get_relative_position(input_idx)?;
+                        let pos = stack.get_relative_position(input_idx).with_context(|| {
+                            format!(
+                                "Cannot locate {} for the trace entry {}",
+                                cs.describe(input_idx),
+                                trace_idx
+                            )
+                        })?;
                         let distance = pos + i;
 
                         if last_visit[input_idx] == cur_time
                             && !(i < inputs.len() - 1 && inputs[i + 1..].contains(&input_idx))
                             && !output.contains(&input_idx)
+                            && !stack_refs.contains(&input_idx)
+                            && !is_outer(&branches, input_idx)
                         {
                             // roll
                             stack.pull(input_idx)?;
+                            pulled.push((trace_idx, input_idx));
                             script.extend_from_slice(roll_script(distance).as_bytes());
                         } else {
                             // pick
@@ -90,13 +568,62 @@ impl Compiler {
                         }
                     }
 
-                    script
-                        .extend_from_slice(script_generator.run(&mut stack, &options)?.as_bytes());
+                    move_bytes[trace_idx] = script.len() - trace_offsets[trace_idx];
+
+                    // the inputs are now on the top of the stack, outside of the model
+                    tracker.check_stack(
+                        stack.get_num_elements_in_stack()?
+                            + stack.get_num_elements_in_altstack()
+                            + remaining_hints
+                            + inputs.len(),
+                        Some(trace_idx),
+                        cs,
+                    )?;
+
+                    // gadgets that locate memory entries through the stack can reach below
+                    // their inputs, so they are checked on the whole stack
+                    let check_stack_effect_here = config.check_stack_effects && branches.is_empty();
+                    let below = if check_stack_effect_here && !stack_refs.is_empty() {
+                        stack.main_stack()
+                    } else {
+                        vec![]
+                    };
+
+                    let gadget = script_generator.run(&mut stack, &options)?;
+                    script.extend_from_slice(gadget.as_bytes());
+
+                    let outputs = cs.trace[trace_idx + 1..]
+                        .iter()
+                        .map_while(|trace_entry| match trace_entry {
+                            TraceEntry::DeclareOutput(idx) => Some(*idx),
+                            _ => None,
+                        })
+                        .collect::<Vec<usize>>();
+
+                    if check_stack_effect_here {
+                        check_stack_effect(cs, trace_idx, &below, inputs, &outputs, &gadget)?;
+                    }
+
+                    // drop the outputs of the script that are never used, from the top
+                    if config.dead_values {
+                        let mut distance = 0;
+                        for &idx in outputs.iter().rev() {
+                            if last_visit[idx] < 0 && !output.contains(&idx) {
+                                script.extend_from_slice(drop_script(distance).as_bytes());
+                                dropped.insert(idx);
+                            } else {
+                                distance += 1;
+                            }
+                        }
+                    }
 
                     cur_time += 1;
                 }
                 TraceEntry::DeclareConstant(idx) => {
                     stack.push_to_stack(*idx)?;
+                    if let Some(branch) = branches.last_mut() {
+                        branch.locals.insert(*idx);
+                    }
 
                     script.extend_from_slice(
                         script! {
@@ -106,18 +633,151 @@ impl Compiler {
                     );
                 }
                 TraceEntry::DeclareOutput(idx) => {
-                    stack.push_to_stack(*idx)?;
+                    if !dropped.contains(idx) {
+                        stack.push_to_stack(*idx)?;
+                        if let Some(branch) = branches.last_mut() {
+                            branch.locals.insert(*idx);
+                        }
+                    }
                 }
                 TraceEntry::RequestHint(idx) => {
                     hint.push(cs.memory.get(idx).unwrap().clone());
                     stack.push_to_stack(*idx)?;
+                    remaining_hints -= 1;
 
                     script.push(OP_DEPTH as u8);
                     script.push(OP_1SUB as u8);
                     script.push(OP_ROLL as u8);
                 }
                 TraceEntry::SystemOutput(_) => {}
+                TraceEntry::BeginIf(cond) => {
+                    let pos = stack.get_relative_position(*cond).with_context(|| {
+                        format!(
+                            "Cannot locate the condition {} for the trace entry {}",
+                            cs.describe(*cond),
+                            trace_idx
+                        )
+                    })?;
+                    if last_visit[*cond] == cur_time
+                        && !output.contains(cond)
+                        && !all_stack_refs.contains(cond)
+                        && !is_outer(&branches, *cond)
+                    {
+                        stack.pull(*cond)?;
+                        pulled.push((trace_idx, *cond));
+                        script.extend_from_slice(roll_script(pos).as_bytes());
+                    } else {
+                        script.extend_from_slice(pick_script(pos).as_bytes());
+                    }
+                    move_bytes[trace_idx] = script.len() - trace_offsets[trace_idx];
+                    script.push(OP_IF.to_u8());
+
+                    branches.push(Branch {
+                        num_elements: stack.get_num_elements_in_stack()?,
+                        num_parked: stack.get_num_elements_in_altstack(),
+                        locals: HashSet::new(),
+                        num_results: None,
+                    });
+                    cur_time += 1;
+                }
+                TraceEntry::Else(results) | TraceEntry::EndIf(results) => {
+                    if branches.is_empty() {
+                        return Err(DslError::InvalidBranch(format!(
+                            "The trace entry {} ends a branch that has not started",
+                            trace_idx
+                        ))
+                        .into());
+                    }
+
+                    // move the results to the top of the stack
+                    for (i, &idx) in results.iter().enumerate() {
+                        let pos = stack.get_relative_position(idx).with_context(|| {
+                            format!(
+                                "Cannot locate the result {} for the trace entry {}",
+                                cs.describe(idx),
+                                trace_idx
+                            )
+                        })?;
+                        let distance = pos + i;
+
+                        if last_visit[idx] == cur_time
+                            && !results[i + 1..].contains(&idx)
+                            && !output.contains(&idx)
+                            && !all_stack_refs.contains(&idx)
+                            && !is_outer(&branches, idx)
+                        {
+                            stack.pull(idx)?;
+                            pulled.push((trace_idx, idx));
+                            script.extend_from_slice(roll_script(distance).as_bytes());
+                        } else {
+                            script.extend_from_slice(pick_script(distance).as_bytes());
+                        }
+                    }
+
+                    // drop the other memory entries created in the branch, from the top
+                    let branch = branches.last_mut().unwrap();
+                    let mut locals = branch.locals.drain().collect::<Vec<usize>>();
+                    locals.sort_unstable_by(|a, b| b.cmp(a));
+                    for idx in locals {
+                        if !stack.is_present(idx)? {
+                            continue;
+                        }
+                        if last_visit[idx] > cur_time {
+                            return Err(DslError::InvalidBranch(format!(
+                                "{} is used outside of the branch that creates it",
+                                cs.describe(idx)
+                            ))
+                            .into());
+                        }
+                        let pos = stack.get_relative_position(idx)?;
+                        script.extend_from_slice(drop_script(pos + results.len()).as_bytes());
+                        stack.pull(idx)?;
+                        pulled.push((trace_idx, idx));
+                    }
+
+                    if stack.get_num_elements_in_stack()? != branch.num_elements
+                        || stack.get_num_elements_in_altstack() != branch.num_parked
+                    {
+                        return Err(DslError::InvalidBranch(format!(
+                            "The branch ending at the trace entry {} does not keep the stack layout",
+                            trace_idx
+                        )).into());
+                    }
+                    move_bytes[trace_idx] = script.len() - trace_offsets[trace_idx];
+
+                    if matches!(trace_entry, TraceEntry::Else(_)) {
+                        branch.num_results = Some(results.len());
+                        script.push(OP_ELSE.to_u8());
+                    } else {
+                        if branch.num_results != Some(results.len()) {
+                            return Err(DslError::InvalidBranch(format!(
+                                "The two branches ending at the trace entry {} have results of different lengths",
+                                trace_idx
+                            )).into());
+                        }
+                        script.push(OP_ENDIF.to_u8());
+                        branches.pop();
+                    }
+                    cur_time += 1;
+                }
             }
+
+            tracker.check_stack(
+                stack.get_num_elements_in_stack()?
+                    + stack.get_num_elements_in_altstack()
+                    + remaining_hints,
+                Some(trace_idx),
+                cs,
+            )?;
+            tracker.check_script(script.len(), Some(trace_idx), cs)?;
+        }
+
+        trace_offsets.push(script.len());
+
+        if !branches.is_empty() {
+            return Err(
+                DslError::InvalidBranch("A conditional branch is not closed".to_string()).into(),
+            );
         }
 
         // step 4: move the desired output to the altstack
@@ -133,7 +793,9 @@ impl Compiler {
             //
             // the list is reversed with the mind that doing so may reduce the pull/roll distance and save the script length
 
-            let pos = stack.get_relative_position(idx)?;
+            let pos = stack
+                .get_relative_position(idx)
+                .with_context(|| format!("Cannot locate the output {}", cs.describe(idx)))?;
 
             if output_list_rev[i..].contains(&idx) {
                 // pick
@@ -172,12 +834,149 @@ impl Compiler {
             script.push(OP_FROMALTSTACK.to_u8());
         }
 
-        Ok(CompiledProgram {
+        // moving the outputs to the altstack and back does not change the total number of
+        // elements, only picking an output that appears twice does
+        tracker.check_stack(elements_in_stack + output_total_len, None, cs)?;
+        tracker.check_script(script.len(), None, cs)?;
+
+        let profile = config.profile.then(|| {
+            CostReport::new(
+                &cs.trace,
+                &cs.trace_meta,
+                &cs.labels,
+                &trace_offsets,
+                &move_bytes,
+                script.len(),
+            )
+        });
+
+        let mut program = CompiledProgram {
             input,
             script: ScriptBuf::from_bytes(script),
             hint,
-        })
+            trace: cs.trace.clone(),
+            trace_meta: cs.trace_meta.clone(),
+            labels: cs.labels.clone(),
+            trace_offsets,
+            max_stack_items: tracker.max_stack_items,
+            profile,
+            pulled,
+            parked,
+            dropped,
+        };
+
+        if config.peephole {
+            optimize_program(&mut program);
+        }
+
+        Ok(program)
+    }
+}
+
+/// A conditional branch that the compiler is in.
+struct Branch {
+    /// The number of elements on the stack and on the altstack when the branch starts, which must
+    /// be the same when it ends.
+    num_elements: usize,
+    num_parked: usize,
+    /// The memory entries created in the branch that may still be on the stack.
+    locals: HashSet<usize>,
+    /// The number of results of the first branch, once it has ended.
+    num_results: Option<usize>,
+}
+
+/// Returns whether a memory entry has been created outside of the innermost open branch, in
+/// which case the branch can only pick it, so that the stack layout does not depend on the
+/// branch that is taken.
+fn is_outer(branches: &[Branch], idx: usize) -> bool {
+    branches
+        .last()
+        .is_some_and(|branch| !branch.locals.contains(&idx))
+}
+
+/// The minimal number of scripts during which a memory entry must stay unused for the compiler
+/// to park it on the altstack.
+const PARK_GAP: usize = 16;
+
+/// Chooses the memory entries to park on the altstack, as triples of the index of the script
+/// that parks it, the index of the script that uses it next, and the index of the memory entry.
+///
+/// Since the altstack is last-in first-out, the chosen intervals are nested, so that the entries
+/// are brought back in the reverse order in which they are parked. The memory entries located
+/// through the stack are never parked, as their blocks must stay consecutive.
+fn plan_parking(cs: &BitcoinSystem, num_inputs: usize) -> Vec<(usize, usize, usize)> {
+    let script_idxs = cs
+        .trace
+        .iter()
+        .enumerate()
+        .filter(|(_, trace_entry)| matches!(trace_entry, TraceEntry::InsertScript(..)))
+        .map(|(trace_idx, _)| trace_idx)
+        .collect::<Vec<usize>>();
+
+    // the first script, counted among the scripts, before which each memory entry is available
+    let mut available_from = vec![None; cs.memory_last_idx];
+    for available in available_from.iter_mut().take(num_inputs) {
+        *available = Some(0);
+    }
+
+    let mut stack_refs = HashSet::new();
+    let mut candidates = vec![];
+    let mut num_scripts = 0;
+    for (trace_idx, trace_entry) in cs.trace.iter().enumerate() {
+        match trace_entry {
+            TraceEntry::InsertScript(_, inputs, options) => {
+                stack_refs.extend(options.stack_refs());
+                for idx in inputs.iter().copied().collect::<BTreeSet<usize>>() {
+                    if let Some(from) = available_from[idx] {
+                        if num_scripts - from >= PARK_GAP {
+                            candidates.push((script_idxs[from], trace_idx, idx));
+                        }
+                    }
+                    available_from[idx] = Some(num_scripts + 1);
+                }
+                num_scripts += 1;
+            }
+            TraceEntry::DeclareConstant(idx)
+            | TraceEntry::DeclareOutput(idx)
+            | TraceEntry::RequestHint(idx) => {
+                available_from[*idx] = Some(num_scripts);
+            }
+            TraceEntry::SystemOutput(_) => {}
+            TraceEntry::BeginIf(idx) => available_from[*idx] = Some(num_scripts),
+            TraceEntry::Else(results) | TraceEntry::EndIf(results) => {
+                for &idx in results.iter() {
+                    available_from[idx] = Some(num_scripts);
+                }
+            }
+        }
+    }
+
+    // an entry cannot be parked and brought back in different branches
+    let mut num_branch_entries = Vec::with_capacity(cs.trace.len() + 1);
+    num_branch_entries.push(0);
+    for trace_entry in cs.trace.iter() {
+        let is_branch_entry = matches!(
+            trace_entry,
+            TraceEntry::BeginIf(_) | TraceEntry::Else(_) | TraceEntry::EndIf(_)
+        );
+        num_branch_entries.push(num_branch_entries.last().unwrap() + is_branch_entry as usize);
+    }
+    candidates.retain(|&(start, end, _)| num_branch_entries[start] == num_branch_entries[end]);
+    candidates.retain(|(_, _, idx)| !stack_refs.contains(idx));
+    candidates.sort_by_key(|&(start, end, idx)| (start, std::cmp::Reverse(end), idx));
+
+    let mut open = vec![];
+    let mut parked = vec![];
+    for (start, end, idx) in candidates {
+        while open.last().is_some_and(|&open_end| open_end <= start) {
+            open.pop();
+        }
+        if open.last().is_none_or(|&open_end| end <= open_end) {
+            open.push(end);
+            parked.push((start, end, idx));
+        }
     }
+    parked
 }
 
 fn roll_script(distance: usize) -> Script {
@@ -200,6 +999,22 @@ fn roll_script(distance: usize) -> Script {
     }
 }
 
+fn drop_script(distance: usize) -> Script {
+    if distance == 0 {
+        script! {
+            OP_DROP
+        }
+    } else if distance == 1 {
+        script! {
+            OP_NIP
+        }
+    } else {
+        script! {
+            { distance } OP_ROLL OP_DROP
+        }
+    }
+}
+
 fn pick_script(distance: usize) -> Script {
     if distance == 0 {
         script! {
@@ -215,3 +1030,86 @@ fn pick_script(distance: usize) -> Script {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::bar::AllocBar;
+    use crate::basic::i32::I32Bar;
+    use crate::bitcoin_system::{BitcoinSystemRef, TraceEntry};
+    use crate::compiler::{BudgetKind, Compiler, CompilerBudget, CompilerConfig};
+    use crate::debugger::Debugger;
+    use crate::error::DslError;
+    use crate::treepp::*;
+
+    #[test]
+    fn test_budget() {
+        let cs = BitcoinSystemRef::new_ref();
+        let mut values = vec![];
+        for i in 0..10 {
+            values.push(I32Bar::new_constant(&cs, i).unwrap());
+        }
+        let mut sum = values[0].clone();
+        for value in values.iter().skip(1) {
+            sum = &sum + value;
+        }
+        cs.set_program_output(&sum).unwrap();
+
+        let program = Compiler::compile(cs.clone()).unwrap();
+        assert_eq!(program.max_stack_items, 10);
+
+        let config = CompilerConfig::new().with_budget(CompilerBudget {
+            max_stack_items: 8,
+            max_script_size: 10_000,
+        });
+        let err = Compiler::compile_with_config(cs.clone(), &config).unwrap_err();
+        let Some(DslError::BudgetExceeded(err)) = err.downcast_ref::<DslError>() else {
+            panic!("unexpected error: {}", err);
+        };
+        assert_eq!(err.kind, BudgetKind::StackItems);
+        assert_eq!(err.trace_idx, Some(8));
+        assert!(matches!(
+            err.trace_entry,
+            Some(TraceEntry::DeclareConstant(_))
+        ));
+
+        let config = CompilerConfig::new().with_budget(CompilerBudget {
+            max_stack_items: 1000,
+            max_script_size: 5,
+        });
+        let err = Compiler::compile_with_config(cs, &config).unwrap_err();
+        let Some(DslError::BudgetExceeded(err)) = err.downcast_ref::<DslError>() else {
+            panic!("unexpected error: {}", err);
+        };
+        assert_eq!(err.kind, BudgetKind::ScriptSize);
+        assert_eq!(err.trace_idx, Some(5));
+    }
+
+    #[test]
+    fn test_altstack() {
+        let cs = BitcoinSystemRef::new_ref();
+        let a = I32Bar::new_hint(&cs, 5).unwrap();
+        let b = I32Bar::new_hint(&cs, 1).unwrap();
+        let mut sum = &a + &b;
+        for i in 0..20 {
+            let c = I32Bar::new_constant(&cs, i).unwrap();
+            sum = &sum + &c;
+        }
+        // `a` is not used for a long time
+        let res = &sum + &a;
+        cs.set_program_output(&res).unwrap();
+
+        let program =
+            Compiler::compile_with_config(cs, &CompilerConfig::new().with_altstack()).unwrap();
+        let &(start, end, _) = program
+            .parked
+            .iter()
+            .find(|&&(_, _, idx)| idx == a.variable)
+            .unwrap();
+        assert!(program.stack_layout(start).contains(&a.variable));
+        assert!(!program.stack_layout(start + 1).contains(&a.variable));
+        assert!(!program.stack_layout(end).contains(&a.variable));
+
+        let mut debugger = Debugger::new(&program, script! { 201 }, true).unwrap();
+        assert!(debugger.run());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::bitcoin_system::{BitcoinSystem, TraceEntry};
+use std::collections::HashSet;
+
+/// Removes the pure scripts whose outputs are all unused, together with the outputs they declare,
+/// and the constants that are then unused. Removing a script can make the outputs of earlier
+/// scripts unused in turn, which a single pass from the end of the trace takes into account.
+///
+/// The scripts that are not marked as pure, such as the ones that verify their inputs, and the
+/// hints are always kept. The memory entries are not renumbered, as the compiler does not require
+/// all of them to be declared.
+pub fn eliminate_dead_code(cs: &BitcoinSystem) -> BitcoinSystem {
+    // the script that declares each output
+    let mut owner = vec![None; cs.trace.len()];
+    let mut outputs = vec![vec![]; cs.trace.len()];
+    let mut last_script = None;
+    for (trace_idx, trace_entry) in cs.trace.iter().enumerate() {
+        match trace_entry {
+            TraceEntry::InsertScript(..) => last_script = Some(trace_idx),
+            TraceEntry::DeclareOutput(idx) => {
+                if let Some(script_idx) = last_script {
+                    owner[trace_idx] = Some(script_idx);
+                    outputs[script_idx].push(*idx);
+                }
+            }
+            _ => last_script = None,
+        }
+    }
+
+    let mut used = HashSet::new();
+    let mut keep = vec![true; cs.trace.len()];
+    for (trace_idx, trace_entry) in cs.trace.iter().enumerate().rev() {
+        match trace_entry {
+            TraceEntry::InsertScript(_, inputs, options) => {
+                if options.pure && !outputs[trace_idx].iter().any(|idx| used.contains(idx)) {
+                    keep[trace_idx] = false;
+                } else {
+                    used.extend(inputs.iter().copied());
+                    used.extend(options.stack_refs());
+                }
+            }
+            TraceEntry::DeclareConstant(idx) => keep[trace_idx] = used.contains(idx),
+            TraceEntry::SystemOutput(idx) | TraceEntry::BeginIf(idx) => {
+                used.insert(*idx);
+            }
+            TraceEntry::Else(results) | TraceEntry::EndIf(results) => {
+                used.extend(results.iter().copied());
+            }
+            TraceEntry::DeclareOutput(_) | TraceEntry::RequestHint(_) => {}
+        }
+    }
+    for (trace_idx, script_idx) in owner.iter().enumerate() {
+        if let Some(script_idx) = script_idx {
+            keep[trace_idx] = keep[*script_idx];
+        }
+    }
+
+    let mut trace = vec![];
+    let mut trace_meta = vec![];
+    for (trace_idx, trace_entry) in cs.trace.iter().enumerate() {
+        if keep[trace_idx] {
+            trace.push(trace_entry.clone());
+            if let Some(meta) = cs.trace_meta.get(trace_idx) {
+                trace_meta.push(meta.clone());
+            }
+        }
+    }
+    cs.with_trace(trace, trace_meta)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bar::{AllocBar, CopyBar};
+    use crate::basic::i32::I32Bar;
+    use crate::bitcoin_system::{BitcoinSystemRef, TraceEntry};
+    use crate::compiler::{Compiler, CompilerConfig};
+    use crate::debugger::Debugger;
+    use crate::treepp::*;
+
+    #[test]
+    fn test_dead_value_elimination() {
+        let build = || {
+            let cs = BitcoinSystemRef::new_ref();
+            let a = I32Bar::new_hint(&cs, 1).unwrap();
+            let b = I32Bar::new_hint(&cs, 2).unwrap();
+
+            // a pure script whose output is unused, and whose input is then unused
+            let c = I32Bar::new_constant(&cs, 100_000).unwrap();
+            let unused_copy = c.copy().unwrap();
+
+            // a script that may fail, whose output is unused
+            let unused_sum = &a + &b;
+
+            let mut sum = &a + &b;
+            for _ in 0..4 {
+                sum = &sum + &b;
+            }
+            cs.set_program_output(&sum).unwrap();
+            (cs, c.variable, unused_copy.variable, unused_sum.variable)
+        };
+
+        let (cs, _, _, _) = build();
+        let program = Compiler::compile(cs).unwrap();
+
+        let (cs, c, unused_copy, unused_sum) = build();
+        let optimized =
+            Compiler::compile_with_config(cs, &CompilerConfig::new().with_dead_value_elimination())
+                .unwrap();
+
+        assert!(!optimized
+            .trace
+            .iter()
+            .any(|trace_entry| matches!(trace_entry, TraceEntry::DeclareOutput(idx) if *idx == unused_copy)));
+        assert!(!optimized.trace.iter().any(
+            |trace_entry| matches!(trace_entry, TraceEntry::DeclareConstant(idx) if *idx == c)
+        ));
+        assert!(optimized.dropped.contains(&unused_sum));
+        assert!(optimized.max_stack_items < program.max_stack_items);
+        assert!(optimized.script.len() < program.script.len());
+
+        let mut debugger = Debugger::new(&optimized, script! { 11 }, true).unwrap();
+        assert!(debugger.run());
+    }
+}
@@ -0,0 +1,392 @@
+use crate::bitcoin_system::{describe_memory_entry, TraceEntry, TraceMeta, VariableLabel};
+use crate::compiler::CompiledProgram;
+use crate::error::DslError;
+use crate::treepp::*;
+use anyhow::Result;
+use bitcoin::hashes::Hash;
+use bitcoin::opcodes::all::OP_NOP;
+use bitcoin::opcodes::OP_TRUE;
+use bitcoin::{TapLeafHash, Transaction};
+use bitcoin_scriptexec::{Exec, ExecCtx, FmtStack, Options, TxTemplate};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// Executes a compiled program opcode by opcode, keeping track of the trace entry that each
+/// opcode comes from.
+///
+/// The executed script first pushes the hints and the inputs, then runs the program, and finally
+/// checks the stack against the expected one, in the same way as `test_program`.
+pub struct Debugger<'a> {
+    program: &'a CompiledProgram,
+    exec: Exec,
+    /// The length of the part of the script that pushes the hints and the inputs.
+    prefix_len: usize,
+    script_len: usize,
+    last_offset: usize,
+    /// The number of opcodes of the program that have been executed.
+    num_opcodes: usize,
+    max_stack_depth: usize,
+    max_altstack_depth: usize,
+    /// The stack when the program ends, before the check against the expected one.
+    final_stack: Option<Vec<Vec<u8>>>,
+}
+
+/// Where the execution of a program is.
+#[derive(Clone, Debug)]
+pub struct DebugLocation {
+    /// The offset in the script of the program, or `None` if the execution is pushing the hints
+    /// and the inputs or checking the final stack.
+    pub offset: Option<usize>,
+    pub trace_idx: Option<usize>,
+    pub trace_entry: Option<TraceEntry>,
+    pub meta: Option<TraceMeta>,
+    /// The memory entries on the stack when the trace entry starts, from the bottom to the top.
+    pub stack_layout: Vec<usize>,
+    /// The labels of the memory entries of the trace entry and of the stack layout.
+    pub labels: HashMap<usize, VariableLabel>,
+}
+
+impl Display for DebugLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (Some(offset), Some(trace_idx), Some(trace_entry)) =
+            (self.offset, self.trace_idx, &self.trace_entry)
+        else {
+            return match self.offset {
+                Some(offset) => write!(f, "in the final cleanup of the stack (offset {})", offset),
+                None => write!(f, "outside of the program"),
+            };
+        };
+
+        match &self.meta {
+            Some(meta) => write!(f, "inside `{}`", meta.label())?,
+            None => write!(f, "inside an unknown gadget")?,
+        }
+        write!(f, " at trace entry #{} (offset {})", trace_idx, offset)?;
+
+        match trace_entry {
+            TraceEntry::InsertScript(_, inputs, _) => {
+                let inputs = inputs
+                    .iter()
+                    .map(|&idx| describe_memory_entry(&self.labels, idx))
+                    .collect::<Vec<_>>();
+                write!(f, ", with inputs {}", inputs.join(", "))
+            }
+            TraceEntry::DeclareConstant(idx) => write!(
+                f,
+                ", declaring constant {}",
+                describe_memory_entry(&self.labels, *idx)
+            ),
+            TraceEntry::DeclareOutput(idx) => write!(
+                f,
+                ", declaring output {}",
+                describe_memory_entry(&self.labels, *idx)
+            ),
+            TraceEntry::RequestHint(idx) => write!(
+                f,
+                ", requesting hint {}",
+                describe_memory_entry(&self.labels, *idx)
+            ),
+            TraceEntry::SystemOutput(idx) => write!(
+                f,
+                ", outputting {}",
+                describe_memory_entry(&self.labels, *idx)
+            ),
+            TraceEntry::BeginIf(idx) => write!(
+                f,
+                ", branching on {}",
+                describe_memory_entry(&self.labels, *idx)
+            ),
+            TraceEntry::Else(results) | TraceEntry::EndIf(results) => {
+                let results = results
+                    .iter()
+                    .map(|&idx| describe_memory_entry(&self.labels, idx))
+                    .collect::<Vec<_>>();
+                write!(f, ", ending a branch with results {}", results.join(", "))
+            }
+        }
+    }
+}
+
+impl<'a> Debugger<'a> {
+    pub fn new(program: &'a CompiledProgram, expected_stack: Script, opcat: bool) -> Result<Self> {
+        let mut options = Options::default();
+        if !opcat {
+            options.experimental.op_cat = false;
+        };
+        Self::new_with_options(program, expected_stack, options)
+    }
+
+    /// Creates a debugger that executes the program with the given script flags. The program must
+    /// have its trace, so a program loaded from an artifact is rejected with
+    /// `DslError::MissingDebugInfo`.
+    pub fn new_with_options(
+        program: &'a CompiledProgram,
+        expected_stack: Script,
+        options: Options,
+    ) -> Result<Self> {
+        if !program.has_debug_info() {
+            return Err(DslError::MissingDebugInfo.into());
+        }
+
+        let mut script = script! {
+            for elem in program.hint.iter() {
+                { elem }
+            }
+            for elem in program.input.iter() {
+                { elem }
+            }
+        }
+        .to_bytes();
+        let prefix_len = script.len();
+        script.extend_from_slice(program.script.as_bytes());
+
+        let expected_final_stack = convert_to_witness(expected_stack)
+            .map_err(|x| anyhow::Error::msg(format!("final stack parsing error: {:?}", x)))?;
+        for elem in expected_final_stack.iter().rev() {
+            script.extend_from_slice(
+                script! {
+                    { elem.to_vec() }
+                    OP_EQUALVERIFY
+                }
+                .as_bytes(),
+            );
+        }
+
+        script.push(OP_TRUE.to_u8());
+
+        let script = Script::from_bytes(script);
+        let script_len = script.len();
+
+        let exec = new_exec(script, options)?;
+
+        Ok(Self {
+            program,
+            exec,
+            prefix_len,
+            script_len,
+            last_offset: 0,
+            num_opcodes: 0,
+            max_stack_depth: 0,
+            max_altstack_depth: 0,
+            final_stack: None,
+        })
+    }
+
+    /// Returns the size of the whole script, including the pushes of the hints and the inputs and
+    /// the check of the final stack.
+    pub fn script_len(&self) -> usize {
+        self.script_len
+    }
+
+    /// Executes the next opcode, and returns false if the execution has ended.
+    pub fn step(&mut self) -> bool {
+        if self.exec.result().is_some() {
+            return false;
+        }
+        self.last_offset = self.script_len - self.exec.remaining_script().len();
+        let program_end = self.prefix_len + self.program.script.len();
+        if (self.prefix_len..program_end).contains(&self.last_offset) {
+            self.num_opcodes += 1;
+        } else if self.last_offset == program_end && self.final_stack.is_none() {
+            let stack = self.exec.stack();
+            self.final_stack = Some((0..stack.len()).map(|i| stack.get(i)).collect());
+        }
+        let res = self.exec.exec_next().is_ok();
+        self.max_stack_depth = self.max_stack_depth.max(self.exec.stack().len());
+        self.max_altstack_depth = self.max_altstack_depth.max(self.exec.altstack().len());
+        res
+    }
+
+    /// Executes the program until it ends, and returns whether it succeeds.
+    pub fn run(&mut self) -> bool {
+        while self.step() {}
+        self.is_success()
+    }
+
+    /// Executes the program until it reaches the trace entry `trace_idx` or ends.
+    pub fn run_to_trace_entry(&mut self, trace_idx: usize) -> bool {
+        let target = self.prefix_len + self.program.trace_offsets[trace_idx];
+        while self.script_len - self.exec.remaining_script().len() < target {
+            if !self.step() {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.exec.result().is_some_and(|res| res.success)
+    }
+
+    /// Returns the location of the last executed opcode, which is the failing one if the
+    /// execution has failed.
+    pub fn location(&self) -> DebugLocation {
+        self.location_at(self.last_offset)
+    }
+
+    fn location_at(&self, script_offset: usize) -> DebugLocation {
+        let offset = script_offset
+            .checked_sub(self.prefix_len)
+            .filter(|&offset| offset < self.program.script.len());
+        let trace_idx = offset.and_then(|offset| self.program.trace_idx_at(offset));
+        let trace_entry = trace_idx.map(|idx| self.program.trace[idx].clone());
+        let stack_layout = trace_idx
+            .map(|idx| self.program.stack_layout(idx))
+            .unwrap_or_default();
+
+        let mut labels = HashMap::new();
+        let memory_entries = trace_entry
+            .as_ref()
+            .map(TraceEntry::memory_entries)
+            .unwrap_or_default();
+        for idx in memory_entries
+            .into_iter()
+            .chain(stack_layout.iter().copied())
+        {
+            if let Some(label) = self.program.labels.get(&idx) {
+                labels.insert(idx, label.clone());
+            }
+        }
+
+        DebugLocation {
+            offset,
+            trace_idx,
+            trace_entry,
+            meta: trace_idx.and_then(|idx| self.program.trace_meta.get(idx).cloned()),
+            stack_layout,
+            labels,
+        }
+    }
+
+    pub fn exec(&self) -> &Exec {
+        &self.exec
+    }
+
+    /// Returns the number of opcodes of the program executed so far, excluding the pushes of the
+    /// hints and the inputs and the check of the final stack.
+    pub fn num_opcodes(&self) -> usize {
+        self.num_opcodes
+    }
+
+    /// Returns the largest numbers of elements on the main stack and on the altstack so far.
+    pub fn max_stack_depths(&self) -> (usize, usize) {
+        (self.max_stack_depth, self.max_altstack_depth)
+    }
+
+    /// Returns the stack when the program ends, from the bottom to the top, or `None` if the
+    /// execution has not reached the end of the program.
+    pub fn final_stack(&self) -> Option<&[Vec<u8>]> {
+        self.final_stack.as_deref()
+    }
+
+    /// Prints the current stack and, if the execution has failed, the error and where it
+    /// happens.
+    pub fn print_state(&self) {
+        println!("{:8}", FmtStack(self.exec.stack().clone()));
+        if let Some(res) = self.exec.result() {
+            if !res.success {
+                println!("{:?}", res.error);
+                println!("failed {}", self.location());
+            }
+        }
+    }
+}
+
+/// Executes a script with the given witness, whose first element is at the bottom of the stack,
+/// and returns the stack when the script ends, from the bottom to the top. Unlike a spending
+/// transaction, the script is not required to leave a single true element.
+pub fn execute_with_witness(script: &Script, witness: Vec<Vec<u8>>) -> Result<Vec<Vec<u8>>> {
+    let mut bytes = script.to_bytes();
+    // a marker that the execution stops at, so that the stack can be inspected
+    bytes.push(OP_NOP.to_u8());
+
+    let mut exec = new_exec_with_witness(Script::from_bytes(bytes), Options::default(), witness)?;
+    while exec.remaining_script().len() > 1 {
+        if exec.exec_next().is_err() {
+            break;
+        }
+    }
+    if let Some(res) = exec.result() {
+        return Err(DslError::ScriptFailed(format!("{:?}", res.error)).into());
+    }
+
+    let stack = exec.stack();
+    Ok((0..stack.len()).map(|i| stack.get(i)).collect())
+}
+
+/// Creates an executor for a tapscript that is spent by an otherwise empty transaction.
+pub(crate) fn new_exec(script: Script, options: Options) -> Result<Exec> {
+    new_exec_with_witness(script, options, vec![])
+}
+
+fn new_exec_with_witness(script: Script, options: Options, witness: Vec<Vec<u8>>) -> Result<Exec> {
+    Exec::new(
+        ExecCtx::Tapscript,
+        options,
+        TxTemplate {
+            tx: Transaction {
+                version: bitcoin::transaction::Version::TWO,
+                lock_time: bitcoin::locktime::absolute::LockTime::ZERO,
+                input: vec![],
+                output: vec![],
+            },
+            prevouts: vec![],
+            input_idx: 0,
+            taproot_annex_scriptleaf: Some((TapLeafHash::all_zeros(), None)),
+        },
+        script,
+        witness,
+    )
+    .map_err(|x| anyhow::Error::msg(format!("error creating exec: {:?}", x)))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bar::{AllocBar, Bar};
+    use crate::basic::i32::I32Bar;
+    use crate::bitcoin_system::{BitcoinSystemRef, TraceEntry};
+    use crate::compiler::Compiler;
+    use crate::debugger::Debugger;
+    use crate::treepp::*;
+
+    #[test]
+    fn test_debugger() {
+        let cs = BitcoinSystemRef::new_ref();
+        let a = I32Bar::new_hint(&cs, 1).unwrap();
+        let b = I32Bar::new_constant(&cs, 2).unwrap();
+        let c = &a + &b;
+
+        // a wrong hint for the sum
+        let d = I32Bar::new_hint_named(&cs, 4, "claimed_sum").unwrap();
+        cs.in_scope("check_sum", || c.equalverify(&d)).unwrap();
+
+        let program = Compiler::compile(cs).unwrap();
+        let mut debugger = Debugger::new(&program, script! {}, true).unwrap();
+        assert!(!debugger.run());
+
+        let location = debugger.location();
+        assert_eq!(location.meta.as_ref().unwrap().label(), "check_sum");
+        assert!(matches!(
+            location.trace_entry,
+            Some(TraceEntry::InsertScript(_, ref inputs, _)) if inputs == &vec![c.variable, d.variable]
+        ));
+        assert_eq!(location.stack_layout, vec![c.variable, d.variable]);
+        assert!(location.to_string().contains("`claimed_sum`"));
+        assert!(location
+            .to_string()
+            .starts_with("inside `check_sum` at trace entry #"));
+
+        let program = Compiler::compile({
+            let cs = BitcoinSystemRef::new_ref();
+            let a = I32Bar::new_hint(&cs, 1).unwrap();
+            let b = I32Bar::new_constant(&cs, 2).unwrap();
+            cs.set_program_output(&(&a + &b)).unwrap();
+            cs
+        })
+        .unwrap();
+        let mut debugger = Debugger::new(&program, script! { 3 }, true).unwrap();
+        assert!(debugger.run_to_trace_entry(2));
+        assert_eq!(debugger.exec().stack().len(), 2);
+        assert!(debugger.run());
+    }
+}
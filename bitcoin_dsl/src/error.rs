@@ -0,0 +1,192 @@
+use crate::compiler::BudgetExceeded;
+use std::fmt::{Display, Formatter};
+
+/// The errors of the DSL. The functions of the DSL return them inside an `anyhow::Error`, from
+/// which the caller can recover them with `downcast_ref::<DslError>()`.
+#[derive(Clone, Debug)]
+pub enum DslError {
+    /// The constraint system has been finalized and can no longer be used.
+    Finalized,
+    /// An input is allocated after the program has started to run.
+    InputAfterExecution,
+    /// A hint is requested inside a conditional branch.
+    HintInBranch,
+    /// The program output is set inside a conditional branch.
+    OutputInBranch,
+    /// A block of constants is declared for the first time inside a conditional branch, where it
+    /// would only exist on one side.
+    ConstantBlockInBranch(String),
+    /// A conditional branch is ended or switched while none is open.
+    NoOpenBranch,
+    /// The conditional branches of the trace are not well-formed.
+    InvalidBranch(String),
+    /// No memory entry has this index.
+    UnknownVariable(usize),
+    /// The memory entry does not hold a value of the expected type.
+    ElementTypeMismatch { idx: usize, expected: &'static str },
+    /// The memory has been modified in an unexpected way.
+    MemoryCorrupted(usize),
+    /// The stack model does not agree with the operation that is applied to it.
+    StackCorrupted(&'static str),
+    /// The script generator requires an option that is not given.
+    MissingOption(String),
+    /// The option is given, but with another type.
+    OptionTypeMismatch {
+        name: String,
+        expected: &'static str,
+    },
+    /// The variables belong to different constraint systems.
+    SystemMismatch,
+    /// The variable has no stack element, such as an empty vector, so it has no constraint
+    /// system.
+    EmptyBar,
+    /// The value of the result of an operation does not fit in its type.
+    Overflow(&'static str),
+    /// The program does not fit in the budget of the compiler.
+    BudgetExceeded(BudgetExceeded),
+    /// A successful execution uses more of a resource than the budget or the standardness rules
+    /// allow.
+    ExecutionOverBudget {
+        resource: &'static str,
+        value: usize,
+        limit: usize,
+    },
+    /// The script of a gadget does not consume its inputs and leave its declared outputs.
+    StackEffectMismatch { trace_idx: usize, reason: String },
+    /// The program has no trace, as it has been loaded from an artifact, so it cannot be stepped
+    /// through.
+    MissingDebugInfo,
+    /// The execution of a script fails.
+    ScriptFailed(String),
+    /// A script that is meant to only push elements cannot be turned into a witness.
+    InvalidWitness(String),
+    /// The file does not start with the header of an artifact.
+    NotAnArtifact,
+    /// The artifact has been written with another version of the format.
+    ArtifactVersionMismatch { found: u32, expected: u32 },
+    /// The artifact contains another kind of value.
+    ArtifactKindMismatch {
+        found: String,
+        expected: &'static str,
+    },
+    /// The integrity hash of the artifact does not match its content.
+    ArtifactCorrupted,
+    /// The LDM is used before `init` has bound it to a constraint system.
+    LdmUnbound,
+    /// The LDM has no entry with this name.
+    UnknownLdmEntry(String),
+    /// The LDM entry is read with another type tag than the one it has been written with.
+    LdmTypeMismatch {
+        name: String,
+        written: String,
+        read: &'static str,
+    },
+}
+
+impl Display for DslError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DslError::Finalized => write!(f, "The constraint system has been finalized"),
+            DslError::InputAfterExecution => write!(
+                f,
+                "Inputs can only be allocated before any execution or allocation for constants or hints"
+            ),
+            DslError::HintInBranch => {
+                write!(f, "Hints cannot be requested inside a conditional branch")
+            }
+            DslError::OutputInBranch => write!(
+                f,
+                "The program output cannot be set inside a conditional branch"
+            ),
+            DslError::ConstantBlockInBranch(name) => write!(
+                f,
+                "The constant block `{}` must be declared before any conditional branch that uses it",
+                name
+            ),
+            DslError::NoOpenBranch => write!(f, "No conditional branch is open"),
+            DslError::InvalidBranch(msg) => write!(f, "{}", msg),
+            DslError::UnknownVariable(idx) => {
+                write!(f, "Could not find the memory entry with index {}", idx)
+            }
+            DslError::ElementTypeMismatch { idx, expected } => write!(
+                f,
+                "Cannot read the memory entry with index {} as {}",
+                idx, expected
+            ),
+            DslError::MemoryCorrupted(idx) => {
+                write!(f, "Memory is corrupted at the memory entry {}", idx)
+            }
+            DslError::StackCorrupted(msg) => write!(f, "{}", msg),
+            DslError::MissingOption(name) => write!(f, "The option {} is missing", name),
+            DslError::OptionTypeMismatch { name, expected } => {
+                write!(f, "The option {} must be {}", name, expected)
+            }
+            DslError::SystemMismatch => write!(
+                f,
+                "The variables belong to different constraint systems"
+            ),
+            DslError::EmptyBar => write!(
+                f,
+                "An empty variable has no constraint system to take the reference from"
+            ),
+            DslError::Overflow(op) => write!(f, "The result of {} overflows", op),
+            DslError::BudgetExceeded(err) => write!(f, "{}", err),
+            DslError::ExecutionOverBudget {
+                resource,
+                value,
+                limit,
+            } => write!(
+                f,
+                "The execution uses {} {}, over the limit of {}",
+                value, resource, limit
+            ),
+            DslError::StackEffectMismatch { trace_idx, reason } => write!(
+                f,
+                "The script of the trace entry {} has a wrong stack effect: {}",
+                trace_idx, reason
+            ),
+            DslError::MissingDebugInfo => write!(
+                f,
+                "The program has no trace, as it has been loaded from an artifact"
+            ),
+            DslError::ScriptFailed(err) => write!(f, "The script execution fails: {}", err),
+            DslError::InvalidWitness(err) => write!(f, "Cannot parse the witness: {}", err),
+            DslError::NotAnArtifact => write!(f, "The file is not an artifact"),
+            DslError::ArtifactVersionMismatch { found, expected } => write!(
+                f,
+                "The artifact has version {}, but version {} is expected",
+                found, expected
+            ),
+            DslError::ArtifactKindMismatch { found, expected } => write!(
+                f,
+                "The artifact contains {}, but {} is expected",
+                found, expected
+            ),
+            DslError::ArtifactCorrupted => {
+                write!(f, "The integrity hash of the artifact does not match")
+            }
+            DslError::LdmUnbound => write!(
+                f,
+                "The LDM is not bound to a constraint system, as `init` has not been called"
+            ),
+            DslError::UnknownLdmEntry(name) => write!(f, "The LDM has no entry named {}", name),
+            DslError::LdmTypeMismatch {
+                name,
+                written,
+                read,
+            } => write!(
+                f,
+                "The LDM entry {} is written as {}, but read as {}",
+                name, written, read
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DslError {}
+
+impl From<BudgetExceeded> for DslError {
+    fn from(err: BudgetExceeded) -> Self {
+        DslError::BudgetExceeded(err)
+    }
+}
@@ -1,12 +1,19 @@
+use crate::artifact::Artifact;
 use crate::bar::{AllocBar, Bar};
 use crate::basic::sha256_hash::Sha256HashBar;
 use crate::bitcoin_system::BitcoinSystemRef;
+use crate::error::DslError;
 use anyhow::Result;
-use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use sha2::Digest;
-use std::collections::HashMap;
-
-#[derive(Default)]
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::path::Path;
+use stwo_prover::core::vcs::sha256_hash::Sha256Hash;
+
+#[derive(Clone, Default)]
 pub struct LDM {
     pub name_to_id: HashMap<String, usize>,
     pub value_map: Vec<Vec<u8>>,
@@ -15,6 +22,219 @@ pub struct LDM {
     pub cs: Option<BitcoinSystemRef>,
     pub hash_var: Option<Sha256HashBar>,
     pub log: Vec<usize>,
+    /// The hash of the memory when it has been resumed from a snapshot, until it is bound to a
+    /// constraint system.
+    pub resumed_hash: Option<Sha256Hash>,
+    /// The number of parts that have used the memory, which is the number of calls to `init`.
+    pub num_parts: usize,
+    /// The type of each entry and the parts that write and read it.
+    pub schema: Vec<LDMEntry>,
+    /// The names that have been read while no entry had them, with the part that reads them.
+    pub missing_reads: Vec<(String, usize)>,
+}
+
+/// The name of an entry of the `LDM` together with the type of its variable, so that reading it
+/// as another type does not compile.
+///
+/// The key also declares a tag for the type, which the `LDM` records with the entry and checks on
+/// every read. Unlike the name of the Rust type, the tag is part of the format of the snapshots,
+/// so it must not change when the type is renamed or moved.
+pub struct LDMKey<T> {
+    name: Cow<'static, str>,
+    tag: &'static str,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> LDMKey<T> {
+    pub const fn new(name: &'static str, tag: &'static str) -> Self {
+        Self {
+            name: Cow::Borrowed(name),
+            tag,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a key whose name is only known at runtime.
+    pub fn named(name: impl ToString, tag: &'static str) -> Self {
+        Self {
+            name: Cow::Owned(name.to_string()),
+            tag,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the key of the entry with this index in a family of entries, whose name is the
+    /// name of the family followed by `_` and the index. The index can also be a label, such as
+    /// the name of another entry.
+    pub fn indexed(&self, idx: impl Display) -> Self {
+        Self {
+            name: Cow::Owned(format!("{}_{}", self.name, idx)),
+            tag: self.tag,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+}
+
+impl<T> Clone for LDMKey<T> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            tag: self.tag,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Display for LDMKey<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// An entry of the `LDM`, with the part that writes it and the parts that read it. The parts are
+/// numbered in the order in which they call `init`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LDMEntry {
+    pub name: String,
+    /// The type tag declared by the key of the entry.
+    pub tag: String,
+    pub writer: usize,
+    pub readers: Vec<usize>,
+    /// The parts that read the entry with `debug_read`, outside of the constraint system.
+    pub debug_readers: Vec<usize>,
+}
+
+/// The entries of an `LDM`, which displays as a table.
+pub struct LDMTable<'a>(pub &'a [LDMEntry]);
+
+impl Display for LDMTable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for entry in self.0.iter() {
+            let readers = entry
+                .readers
+                .iter()
+                .map(|part| part.to_string())
+                .collect::<Vec<_>>();
+            let debug_readers = entry
+                .debug_readers
+                .iter()
+                .map(|part| part.to_string())
+                .collect::<Vec<_>>();
+            let readers = match (readers.is_empty(), debug_readers.is_empty()) {
+                (true, true) => "never read".to_string(),
+                (false, true) => format!("read by {}", readers.join(", ")),
+                (true, false) => format!("debug read by {}", debug_readers.join(", ")),
+                (false, false) => format!(
+                    "read by {}, debug read by {}",
+                    readers.join(", "),
+                    debug_readers.join(", ")
+                ),
+            };
+            writeln!(
+                f,
+                "{}: {}, written by {}, {}",
+                entry.name, entry.tag, entry.writer, readers
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A read of a name that no entry has at the time.
+#[derive(Clone, Debug)]
+pub struct LDMMissingRead {
+    pub name: String,
+    /// The part that reads the name.
+    pub part: usize,
+    /// The part that writes the name afterwards, if any, in which case the read comes too
+    /// early.
+    pub written_later: Option<usize>,
+}
+
+/// The mistakes in the use of an `LDM` across the parts of a program.
+#[derive(Clone, Debug, Default)]
+pub struct LDMAuditReport {
+    /// The entries that no part reads. An entry that is overwritten is reported as a double
+    /// write instead.
+    pub unread_writes: Vec<LDMEntry>,
+    /// The names written more than once, with the parts that write them, of which only the last
+    /// write can be read.
+    pub double_writes: Vec<(String, Vec<usize>)>,
+    pub missing_reads: Vec<LDMMissingRead>,
+}
+
+impl LDMAuditReport {
+    pub fn is_clean(&self) -> bool {
+        self.unread_writes.is_empty()
+            && self.double_writes.is_empty()
+            && self.missing_reads.is_empty()
+    }
+}
+
+impl Display for LDMAuditReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for entry in self.unread_writes.iter() {
+            writeln!(
+                f,
+                "unread write: {} written by {} is never read",
+                entry.name, entry.writer
+            )?;
+        }
+        for (name, parts) in self.double_writes.iter() {
+            let parts = parts
+                .iter()
+                .map(|part| part.to_string())
+                .collect::<Vec<_>>();
+            writeln!(
+                f,
+                "double write: {} is written by {}",
+                name,
+                parts.join(", ")
+            )?;
+        }
+        for read in self.missing_reads.iter() {
+            match read.written_later {
+                Some(writer) => writeln!(
+                    f,
+                    "read before write: {} is read by {} but written by {}",
+                    read.name, read.part, writer
+                )?,
+                None => writeln!(
+                    f,
+                    "missing read: {} is read by {} but never written",
+                    read.name, read.part
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The state of an `LDM` between two parts of a program, which can be saved to a file so that a
+/// later part can be generated without generating the earlier ones again.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LDMSnapshot {
+    pub name_to_id: BTreeMap<String, usize>,
+    pub value_map: Vec<Vec<u8>>,
+    pub hash_map: Vec<Vec<u8>>,
+    pub log: Vec<usize>,
+    /// The current hash of the memory, or `None` if the memory has never been used.
+    pub hash: Option<Vec<u8>>,
+    pub num_parts: usize,
+    pub schema: Vec<LDMEntry>,
+    pub missing_reads: Vec<(String, usize)>,
+}
+
+impl Artifact for LDMSnapshot {
+    const KIND: &'static str = "ldm snapshot";
 }
 
 impl LDM {
@@ -23,7 +243,11 @@ impl LDM {
     }
 
     pub fn init(&mut self, cs: &BitcoinSystemRef) -> Result<()> {
-        if self.cs.is_some() {
+        self.num_parts += 1;
+        if let Some(read_hash) = self.resumed_hash.take() {
+            self.cs = Some(cs.clone());
+            self.hash_var = Some(Sha256HashBar::new_program_input(&cs, read_hash)?);
+        } else if self.cs.is_some() {
             let read_hash = self.hash_var.as_ref().unwrap().value.clone();
             self.cs = Some(cs.clone());
             self.hash_var = Some(Sha256HashBar::new_program_input(&cs, read_hash)?);
@@ -37,14 +261,22 @@ impl LDM {
         Ok(())
     }
 
-    pub fn write<T: Bar + AllocBar>(&mut self, name: impl ToString, value: &T) -> Result<()> {
-        assert!(
-            self.cs.is_some(),
-            "The WORMMemory is not bound to a constraint system."
-        );
+    /// Writes an entry, which replaces any earlier entry with the same name, and fails if the
+    /// memory is not bound to a constraint system.
+    pub fn write_key<T: Bar + AllocBar>(&mut self, key: &LDMKey<T>, value: &T) -> Result<()> {
+        if self.cs.is_none() {
+            return Err(DslError::LdmUnbound.into());
+        }
 
         let idx = self.value_map.len();
-        self.name_to_id.insert(name.to_string(), idx);
+        self.name_to_id.insert(key.name().to_string(), idx);
+        self.schema.push(LDMEntry {
+            name: key.name().to_string(),
+            tag: key.tag().to_string(),
+            writer: self.num_parts - 1,
+            readers: vec![],
+            debug_readers: vec![],
+        });
 
         self.value_map.push(bincode::serialize(&value.value()?)?);
 
@@ -57,11 +289,25 @@ impl LDM {
         Ok(())
     }
 
-    pub fn read<T: Bar + AllocBar>(&mut self, name: impl ToString) -> Result<T> {
-        let idx = self.name_to_id[&name.to_string()];
+    /// Reads an entry as a hint, and fails if the memory is not bound to a constraint system, or
+    /// if the entry does not exist or has been written with another type tag.
+    pub fn read_key<T: Bar + AllocBar>(&mut self, key: &LDMKey<T>) -> Result<T> {
+        let Some(cs) = self.cs.clone() else {
+            return Err(DslError::LdmUnbound.into());
+        };
+
+        let idx = self.typed_entry_idx(key).inspect_err(|_| {
+            self.missing_reads
+                .push((key.name().to_string(), self.num_parts.saturating_sub(1)));
+        })?;
+        let part = self.num_parts - 1;
+        let entry = &mut self.schema[idx];
+        if entry.readers.last() != Some(&part) {
+            entry.readers.push(part);
+        }
 
         let value: T::Value = bincode::deserialize(&self.value_map[idx])?;
-        let v = T::new_hint(self.cs.as_ref().unwrap(), value)?;
+        let v = T::new_hint(&cs, value)?;
 
         self.hash_var = Some(self.hash_var.as_ref().unwrap() + &Sha256HashBar::from(&v));
         self.log.push(idx);
@@ -69,23 +315,155 @@ impl LDM {
         Ok(v)
     }
 
-    pub fn debug_read<T: DeserializeOwned>(&mut self, name: impl ToString) -> Result<T> {
-        let idx = self.name_to_id[&name.to_string()];
+    /// Returns the value of an entry without reading it into the constraint system. The read is
+    /// recorded in the schema apart from the reads of `read_key`, so that the audit does not
+    /// report the entry as unread.
+    pub fn debug_read<T: AllocBar>(&mut self, key: &LDMKey<T>) -> Result<T::Value> {
+        let part = self.num_parts.saturating_sub(1);
+        let idx = self.typed_entry_idx(key).inspect_err(|_| {
+            self.missing_reads.push((key.name().to_string(), part));
+        })?;
+        let entry = &mut self.schema[idx];
+        if entry.debug_readers.last() != Some(&part) {
+            entry.debug_readers.push(part);
+        }
         Ok(bincode::deserialize(&self.value_map[idx])?)
     }
 
+    /// Returns the table of the entries with their types and the parts that write and read
+    /// them.
+    pub fn table(&self) -> LDMTable<'_> {
+        LDMTable(&self.schema)
+    }
+
+    /// Walks the entries written and read so far, and reports the entries that are never read,
+    /// the names that are written more than once, and the reads of names that are not written
+    /// at the time.
+    pub fn audit(&self) -> LDMAuditReport {
+        let mut report = LDMAuditReport::default();
+
+        let mut writers = HashMap::<&str, Vec<usize>>::new();
+        for entry in self.schema.iter() {
+            writers
+                .entry(entry.name.as_str())
+                .or_default()
+                .push(entry.writer);
+        }
+
+        for (idx, entry) in self.schema.iter().enumerate() {
+            let overwritten = self.name_to_id.get(&entry.name) != Some(&idx);
+            if entry.readers.is_empty() && entry.debug_readers.is_empty() && !overwritten {
+                report.unread_writes.push(entry.clone());
+            }
+        }
+
+        let mut overwritten = writers
+            .into_iter()
+            .filter(|(_, parts)| parts.len() > 1)
+            .map(|(name, parts)| (name.to_string(), parts))
+            .collect::<Vec<_>>();
+        overwritten.sort();
+        report.double_writes = overwritten;
+
+        for (name, part) in self.missing_reads.iter() {
+            let written_later = self
+                .schema
+                .iter()
+                .find(|entry| &entry.name == name)
+                .map(|entry| entry.writer);
+            report.missing_reads.push(LDMMissingRead {
+                name: name.clone(),
+                part: *part,
+                written_later,
+            });
+        }
+
+        report
+    }
+
+    fn typed_entry_idx<T>(&self, key: &LDMKey<T>) -> Result<usize> {
+        let Some(&idx) = self.name_to_id.get(key.name()) else {
+            return Err(DslError::UnknownLdmEntry(key.name().to_string()).into());
+        };
+        let entry = &self.schema[idx];
+        if entry.tag != key.tag() {
+            return Err(DslError::LdmTypeMismatch {
+                name: entry.name.clone(),
+                written: entry.tag.clone(),
+                read: key.tag(),
+            }
+            .into());
+        }
+        Ok(idx)
+    }
+
+    /// Captures the state of the memory, which is meant to be taken after a part has been
+    /// generated and its constraint system saved.
+    pub fn snapshot(&self) -> LDMSnapshot {
+        let hash = match &self.hash_var {
+            Some(hash_var) => Some(hash_var.value.as_ref().to_vec()),
+            None => self
+                .resumed_hash
+                .as_ref()
+                .map(|hash| hash.as_ref().to_vec()),
+        };
+        LDMSnapshot {
+            name_to_id: self.name_to_id.clone().into_iter().collect(),
+            value_map: self.value_map.clone(),
+            hash_map: self.hash_map.clone(),
+            log: self.log.clone(),
+            hash,
+            num_parts: self.num_parts,
+            schema: self.schema.clone(),
+            missing_reads: self.missing_reads.clone(),
+        }
+    }
+
+    /// Restores the memory from a snapshot. The next call to `init` then reads the hash of the
+    /// snapshot as a program input, in the same way as if the earlier parts had just been
+    /// generated.
+    pub fn resume(snapshot: LDMSnapshot) -> LDM {
+        Self {
+            name_to_id: snapshot.name_to_id.into_iter().collect(),
+            value_map: snapshot.value_map,
+            hash_map: snapshot.hash_map,
+            log: snapshot.log,
+            resumed_hash: snapshot.hash.map(Sha256Hash::from),
+            num_parts: snapshot.num_parts,
+            schema: snapshot.schema,
+            missing_reads: snapshot.missing_reads,
+            ..Default::default()
+        }
+    }
+
+    /// Writes a snapshot of the memory to a versioned file.
+    pub fn save_snapshot(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.snapshot().save(path)
+    }
+
+    /// Resumes the memory from a snapshot written by `save_snapshot`.
+    pub fn load_snapshot(path: impl AsRef<Path>) -> Result<LDM> {
+        Ok(Self::resume(LDMSnapshot::load(path)?))
+    }
+
+    /// Outputs the hash of the memory, and fails if the memory is not bound to a constraint
+    /// system.
     pub fn save(&self) -> Result<()> {
-        self.cs
-            .as_ref()
-            .unwrap()
-            .set_program_output(self.hash_var.as_ref().unwrap())?;
+        let (Some(cs), Some(hash_var)) = (self.cs.as_ref(), self.hash_var.as_ref()) else {
+            return Err(DslError::LdmUnbound.into());
+        };
+        cs.set_program_output(hash_var)?;
         Ok(())
     }
 
+    /// Checks the hash of the memory against the entries read as hints, and fails if the memory
+    /// is not bound to a constraint system.
     pub fn check(&self) -> Result<()> {
+        let (Some(cs), Some(hash_var)) = (self.cs.as_ref(), self.hash_var.as_ref()) else {
+            return Err(DslError::LdmUnbound.into());
+        };
         let mut next_index_to_load = 0;
         let mut map = Vec::<Sha256HashBar>::new();
-        let cs = self.cs.as_ref().unwrap();
 
         let default_hash = sha2::Sha256::digest(b"ldm").to_vec();
         let mut recomputed_hash_var = Sha256HashBar::new_constant(&cs, default_hash.into())?;
@@ -108,10 +486,7 @@ impl LDM {
             }
         }
 
-        self.hash_var
-            .as_ref()
-            .unwrap()
-            .equalverify(&recomputed_hash_var)?;
+        hash_var.equalverify(&recomputed_hash_var)?;
 
         Ok(())
     }
@@ -119,10 +494,14 @@ impl LDM {
 
 #[cfg(test)]
 mod test {
+    use crate::artifact::Artifact;
     use crate::bar::AllocBar;
+    use crate::basic::bool::BoolBar;
+    use crate::basic::i32::I32Bar;
     use crate::basic::sha256_hash::Sha256HashBar;
     use crate::bitcoin_system::BitcoinSystemRef;
-    use crate::ldm::LDM;
+    use crate::error::DslError;
+    use crate::ldm::{LDMKey, LDMSnapshot, LDM};
     use crate::test_program;
     use crate::treepp::*;
     use bitcoin_script::script;
@@ -131,6 +510,8 @@ mod test {
 
     #[test]
     fn test_ldm() {
+        const C: LDMKey<Sha256HashBar> = LDMKey::new("c", "sha256_hash");
+
         let mut prng = ChaCha20Rng::seed_from_u64(0);
 
         let a_val: [u8; 32] = prng.gen();
@@ -147,7 +528,7 @@ mod test {
         let c = &a + &b;
         let c_val = c.value().unwrap();
 
-        ldm.write("c", &c).unwrap();
+        ldm.write_key(&C, &c).unwrap();
         ldm.save().unwrap();
 
         test_program(
@@ -161,7 +542,7 @@ mod test {
         let cs = BitcoinSystemRef::new_ref();
         ldm.init(&cs).unwrap();
 
-        let c = ldm.read::<Sha256HashBar>("c").unwrap();
+        let c = ldm.read_key(&C).unwrap();
         assert_eq!(c.value().unwrap(), c_val);
 
         ldm.check().unwrap();
@@ -175,4 +556,216 @@ mod test {
         )
         .unwrap();
     }
+
+    #[test]
+    fn test_ldm_groups() {
+        const VALS: LDMKey<[I32Bar; 4]> = LDMKey::new("vals", "[i32; 4]");
+        const PAIR: LDMKey<(I32Bar, BoolBar)> = LDMKey::new("pair", "(i32, bool)");
+
+        let mut ldm = LDM::new();
+
+        let cs = BitcoinSystemRef::new_ref();
+        ldm.init(&cs).unwrap();
+
+        let vals = [1, 2, 3, 4].map(|v| I32Bar::new_constant(&cs, v).unwrap());
+        let pair = (
+            I32Bar::new_constant(&cs, 5).unwrap(),
+            BoolBar::new_constant(&cs, true).unwrap(),
+        );
+        ldm.write_key(&VALS, &vals).unwrap();
+        ldm.write_key(&PAIR, &pair).unwrap();
+        ldm.save().unwrap();
+
+        test_program(
+            cs,
+            script! {
+                { ldm.hash_var.as_ref().unwrap().value.clone() }
+            },
+        )
+        .unwrap();
+
+        let cs = BitcoinSystemRef::new_ref();
+        ldm.init(&cs).unwrap();
+
+        let vals = ldm.read_key(&VALS).unwrap();
+        assert_eq!(vals.value().unwrap(), vec![1, 2, 3, 4]);
+        let pair = ldm.read_key(&PAIR).unwrap();
+        assert_eq!(pair.value().unwrap(), (5, true));
+        let shorter = LDMKey::<[I32Bar; 3]>::new("vals", "[i32; 3]");
+        assert!(ldm.read_key(&shorter).is_err());
+
+        ldm.check().unwrap();
+        ldm.save().unwrap();
+
+        test_program(
+            cs,
+            script! {
+                { ldm.hash_var.as_ref().unwrap().value.clone() }
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_ldm_snapshot() {
+        const A: LDMKey<I32Bar> = LDMKey::new("a", "i32");
+
+        let mut ldm = LDM::new();
+
+        let cs = BitcoinSystemRef::new_ref();
+        ldm.init(&cs).unwrap();
+        let a = I32Bar::new_constant(&cs, 7).unwrap();
+        ldm.write_key(&A, &a).unwrap();
+        ldm.save().unwrap();
+        test_program(
+            cs,
+            script! {
+                { ldm.hash_var.as_ref().unwrap().value.clone() }
+            },
+        )
+        .unwrap();
+
+        let bytes = ldm.snapshot().to_artifact_bytes().unwrap();
+        let mut resumed = LDM::resume(LDMSnapshot::from_artifact_bytes(&bytes).unwrap());
+
+        // the resumed memory generates the next part in the same way as the original one
+        let mut results = vec![];
+        for ldm in [&mut ldm, &mut resumed] {
+            let cs = BitcoinSystemRef::new_ref();
+            ldm.init(&cs).unwrap();
+            let a = ldm.read_key(&A).unwrap();
+            assert_eq!(a.value, 7);
+            ldm.check().unwrap();
+            ldm.save().unwrap();
+
+            let hash = ldm.hash_var.as_ref().unwrap().value.clone();
+            test_program(
+                cs,
+                script! {
+                    { hash.clone() }
+                },
+            )
+            .unwrap();
+            results.push(hash);
+        }
+        assert_eq!(results[0], results[1]);
+    }
+
+    #[test]
+    fn test_ldm_keys() {
+        const A: LDMKey<I32Bar> = LDMKey::new("a", "i32");
+        const FLAGS: LDMKey<BoolBar> = LDMKey::new("flag", "bool");
+
+        let mut ldm = LDM::new();
+
+        let cs = BitcoinSystemRef::new_ref();
+        let err = ldm
+            .write_key(&A, &I32Bar::new_constant(&cs, 7).unwrap())
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DslError>(),
+            Some(DslError::LdmUnbound)
+        ));
+        for err in [ldm.save().unwrap_err(), ldm.check().unwrap_err()] {
+            assert!(matches!(
+                err.downcast_ref::<DslError>(),
+                Some(DslError::LdmUnbound)
+            ));
+        }
+
+        ldm.init(&cs).unwrap();
+        ldm.write_key(&A, &I32Bar::new_constant(&cs, 7).unwrap())
+            .unwrap();
+        for i in 0..2 {
+            ldm.write_key(
+                &FLAGS.indexed(i),
+                &BoolBar::new_constant(&cs, i == 1).unwrap(),
+            )
+            .unwrap();
+        }
+        ldm.save().unwrap();
+
+        let cs = BitcoinSystemRef::new_ref();
+        ldm.init(&cs).unwrap();
+        assert_eq!(ldm.read_key(&A).unwrap().value, 7);
+        assert!(ldm.read_key(&FLAGS.indexed(1)).unwrap().value);
+
+        let err = ldm
+            .read_key(&LDMKey::<I32Bar>::new("b", "i32"))
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DslError>(),
+            Some(DslError::UnknownLdmEntry(_))
+        ));
+        let err = ldm
+            .read_key(&LDMKey::<BoolBar>::new("a", "bool"))
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DslError>(),
+            Some(DslError::LdmTypeMismatch { .. })
+        ));
+
+        let table = ldm.table().to_string();
+        assert_eq!(ldm.schema[0].writer, 0);
+        assert_eq!(ldm.schema[0].readers, vec![1]);
+        assert!(ldm.schema[1].readers.is_empty());
+        assert!(table.contains("flag_0: bool, written by 0"));
+        assert!(table.contains("never read"));
+    }
+
+    #[test]
+    fn test_ldm_audit() {
+        let key = |name| LDMKey::<I32Bar>::new(name, "i32");
+
+        let mut ldm = LDM::new();
+
+        let cs = BitcoinSystemRef::new_ref();
+        ldm.init(&cs).unwrap();
+        ldm.write_key(&key("a"), &I32Bar::new_constant(&cs, 1).unwrap())
+            .unwrap();
+        ldm.write_key(&key("b"), &I32Bar::new_constant(&cs, 2).unwrap())
+            .unwrap();
+        ldm.write_key(&key("a"), &I32Bar::new_constant(&cs, 3).unwrap())
+            .unwrap();
+        ldm.save().unwrap();
+
+        let cs = BitcoinSystemRef::new_ref();
+        ldm.init(&cs).unwrap();
+        assert_eq!(ldm.read_key(&key("a")).unwrap().value, 3);
+        assert!(ldm.read_key(&key("c")).is_err());
+        assert!(ldm.read_key(&key("d")).is_err());
+        ldm.write_key(&key("c"), &I32Bar::new_constant(&cs, 4).unwrap())
+            .unwrap();
+        ldm.write_key(&key("e"), &I32Bar::new_constant(&cs, 5).unwrap())
+            .unwrap();
+        ldm.save().unwrap();
+
+        // a debug read counts as a read, but is listed apart from the reads of the parts
+        assert_eq!(ldm.debug_read(&key("e")).unwrap(), 5);
+        assert_eq!(ldm.schema[4].debug_readers, vec![1]);
+        assert!(ldm
+            .table()
+            .to_string()
+            .contains("e: i32, written by 1, debug read by 1"));
+
+        let report = ldm.audit();
+        assert!(!report.is_clean());
+        let text = report.to_string();
+        assert!(text.contains("unread write: b written by 0 is never read"));
+        assert!(!text.contains("unread write: e"));
+
+        let unread = report
+            .unread_writes
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(unread, vec!["b", "c"]);
+        assert_eq!(report.double_writes, vec![("a".to_string(), vec![0, 0])]);
+
+        assert_eq!(report.missing_reads.len(), 2);
+        assert_eq!(report.missing_reads[0].name, "c");
+        assert_eq!(report.missing_reads[0].written_later, Some(1));
+        assert_eq!(report.missing_reads[1].name, "d");
+        assert_eq!(report.missing_reads[1].written_later, None);
+    }
 }
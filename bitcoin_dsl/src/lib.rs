@@ -1,12 +1,11 @@
 use crate::bitcoin_system::BitcoinSystemRef;
 use crate::compiler::Compiler;
+use crate::debugger::Debugger;
+use crate::report::ExecutionReport;
 use crate::treepp::pushable::{Builder, Pushable};
 use crate::treepp::*;
 use anyhow::{Error, Result};
-use bitcoin::hashes::Hash;
-use bitcoin::opcodes::OP_TRUE;
-use bitcoin::{TapLeafHash, Transaction};
-use bitcoin_scriptexec::{convert_to_witness, Exec, ExecCtx, FmtStack, Options, TxTemplate};
+use bitcoin_scriptexec::Options;
 use rand::{Rng, RngCore};
 use stwo_prover::core::circle::CirclePoint;
 use stwo_prover::core::fields::cm31::CM31;
@@ -14,6 +13,9 @@ use stwo_prover::core::fields::m31::M31;
 use stwo_prover::core::fields::qm31::QM31;
 use stwo_prover::core::vcs::sha256_hash::Sha256Hash;
 
+// lets the derive macros refer to this crate by name inside it
+extern crate self as recursive_stwo_bitcoin_dsl;
+
 pub mod bar;
 
 pub mod bitcoin_system;
@@ -30,6 +32,35 @@ pub mod compiler;
 
 pub mod ldm;
 
+pub mod splitter;
+
+pub mod profiler;
+
+pub mod debugger;
+
+pub mod peephole;
+
+pub mod scheduler;
+
+pub mod dce;
+
+pub mod artifact;
+
+pub mod error;
+
+pub mod report;
+
+pub mod tamper;
+
+pub mod stack_effect;
+
+/// The dependencies that the code generated by the derive macros refers to.
+#[doc(hidden)]
+pub mod __private {
+    pub use anyhow;
+    pub use serde;
+}
+
 #[allow(missing_docs)]
 pub mod treepp {
     pub use bitcoin_script::{define_pushable, script};
@@ -80,93 +111,34 @@ impl Pushable for CirclePoint<QM31> {
     }
 }
 
-pub fn test_program(cs: BitcoinSystemRef, expected_stack: Script) -> Result<usize> {
-    test_program_generic(cs, expected_stack, true)
+pub fn test_program(cs: BitcoinSystemRef, expected_stack: Script) -> Result<ExecutionReport> {
+    test_program_with_options(cs, expected_stack, Options::default())
 }
 
-pub fn test_program_without_opcat(cs: BitcoinSystemRef, expected_stack: Script) -> Result<usize> {
-    test_program_generic(cs, expected_stack, false)
+pub fn test_program_without_opcat(
+    cs: BitcoinSystemRef,
+    expected_stack: Script,
+) -> Result<ExecutionReport> {
+    let mut options = Options::default();
+    options.experimental.op_cat = false;
+    test_program_with_options(cs, expected_stack, options)
 }
 
-fn test_program_generic(
+/// Runs the program with the given script flags and checks that it leaves the expected stack.
+pub fn test_program_with_options(
     cs: BitcoinSystemRef,
     expected_stack: Script,
-    opcat: bool,
-) -> Result<usize> {
+    options: Options,
+) -> Result<ExecutionReport> {
     let program = Compiler::compile(cs)?;
+    let mut debugger = Debugger::new_with_options(&program, expected_stack, options)?;
 
-    let mut script = script! {
-        for elem in program.hint.iter() {
-            { elem }
-        }
-        for elem in program.input.iter() {
-            { elem }
-        }
-    }
-    .to_bytes();
-    script.extend_from_slice(program.script.as_bytes());
-
-    let expected_final_stack = convert_to_witness(expected_stack)
-        .map_err(|x| anyhow::Error::msg(format!("final stack parsing error: {:?}", x)))?;
-    for elem in expected_final_stack.iter().rev() {
-        script.extend_from_slice(
-            script! {
-                { elem.to_vec() }
-                OP_EQUALVERIFY
-            }
-            .as_bytes(),
-        );
+    if !debugger.run() {
+        debugger.print_state();
+        return Err(Error::msg("Script execution is not successful"));
     }
 
-    script.push(OP_TRUE.to_u8());
-
-    let script = Script::from_bytes(script);
-
-    let script_len = script.len();
-    println!("script size: {}", script_len);
-
-    let mut options = Options::default();
-    if !opcat {
-        options.experimental.op_cat = false;
-    };
-
-    let mut exec = Exec::new(
-        ExecCtx::Tapscript,
-        options,
-        TxTemplate {
-            tx: Transaction {
-                version: bitcoin::transaction::Version::TWO,
-                lock_time: bitcoin::locktime::absolute::LockTime::ZERO,
-                input: vec![],
-                output: vec![],
-            },
-            prevouts: vec![],
-            input_idx: 0,
-            taproot_annex_scriptleaf: Some((TapLeafHash::all_zeros(), None)),
-        },
-        script,
-        vec![],
-    )
-    .expect("error creating exec");
-
-    loop {
-        if exec.exec_next().is_err() {
-            break;
-        }
-    }
-    let res = exec.result().unwrap();
-    if !res.success {
-        println!("{:8}", FmtStack(exec.stack().clone()));
-        println!("{:?}", res.error);
-    }
-
-    println!("max stack size: {}", exec.stats().max_nb_stack_items);
-
-    if res.success {
-        Ok(script_len)
-    } else {
-        Err(Error::msg("Script execution is not successful"))
-    }
+    ExecutionReport::new(&program, &debugger)
 }
 
 pub fn rand_m31<R: RngCore>(prng: &mut R) -> M31 {
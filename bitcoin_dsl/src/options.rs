@@ -1,9 +1,13 @@
+use crate::error::DslError;
 use anyhow::{Error, Result};
 use std::collections::HashMap;
 
 #[derive(Clone, Debug)]
 pub struct Options {
     pub map: HashMap<String, OptionsEntry>,
+    /// Whether the script only computes its outputs from its inputs, without any verification
+    /// that could make the program fail, so that it can be removed if its outputs are unused.
+    pub pure: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -15,15 +19,24 @@ pub enum OptionsEntry {
     MultiU32(Vec<u32>),
     U64(u64),
     MultiU64(Vec<u64>),
+    /// A block of consecutive memory entries, given by the first index and the length, that the
+    /// script generator locates through the stack rather than receiving them as inputs.
+    StackRef(usize, usize),
 }
 
 impl Options {
     pub fn new() -> Options {
         Options {
             map: HashMap::new(),
+            pure: false,
         }
     }
 
+    pub fn with_pure(mut self) -> Options {
+        self.pure = true;
+        self
+    }
+
     pub fn with_entry(mut self, name: impl ToString, entry: OptionsEntry) -> Options {
         self.map.insert(name.to_string(), entry);
         self
@@ -69,58 +82,108 @@ impl Options {
         self
     }
 
+    pub fn with_stack_ref(mut self, name: impl ToString, idx: usize, len: usize) -> Options {
+        self.map
+            .insert(name.to_string(), OptionsEntry::StackRef(idx, len));
+        self
+    }
+
     pub fn exists(&self, name: &str) -> bool {
         self.map.contains_key(name)
     }
 
     pub fn get_string(&self, name: impl ToString) -> Result<&String> {
-        match self.map.get(&name.to_string()) {
+        let name = name.to_string();
+        match self.map.get(&name) {
             Some(OptionsEntry::String(v)) => Ok(v),
-            _ => Err(Error::msg("The corresponding option must be a string")),
+            entry => Err(option_error(name, entry, "a string")),
         }
     }
 
     pub fn get_binary(&self, name: impl ToString) -> Result<&[u8]> {
-        match self.map.get(&name.to_string()) {
+        let name = name.to_string();
+        match self.map.get(&name) {
             Some(OptionsEntry::Binary(v)) => Ok(v),
-            _ => Err(Error::msg("The corresponding option must be a binary")),
+            entry => Err(option_error(name, entry, "a binary")),
         }
     }
 
     pub fn get_multi_binary(&self, name: impl ToString) -> Result<&[Vec<u8>]> {
-        match self.map.get(&name.to_string()) {
+        let name = name.to_string();
+        match self.map.get(&name) {
             Some(OptionsEntry::MultiBinary(v)) => Ok(v),
-            _ => Err(Error::msg(
-                "The corresponding option must be a multi binary",
-            )),
+            entry => Err(option_error(name, entry, "a multi binary")),
         }
     }
 
     pub fn get_u32(&self, name: impl ToString) -> Result<u32> {
-        match self.map.get(&name.to_string()) {
+        let name = name.to_string();
+        match self.map.get(&name) {
             Some(OptionsEntry::U32(v)) => Ok(*v),
-            _ => Err(Error::msg("The corresponding option must be a u32")),
+            entry => Err(option_error(name, entry, "a u32")),
         }
     }
 
     pub fn get_multi_u32(&self, name: impl ToString) -> Result<&[u32]> {
-        match self.map.get(&name.to_string()) {
+        let name = name.to_string();
+        match self.map.get(&name) {
             Some(OptionsEntry::MultiU32(v)) => Ok(v),
-            _ => Err(Error::msg("The corresponding option must be a multi u32")),
+            entry => Err(option_error(name, entry, "a multi u32")),
         }
     }
 
     pub fn get_u64(&self, name: impl ToString) -> Result<u64> {
-        match self.map.get(&name.to_string()) {
+        let name = name.to_string();
+        match self.map.get(&name) {
             Some(OptionsEntry::U64(v)) => Ok(*v),
-            _ => Err(Error::msg("The corresponding option must be a u64")),
+            entry => Err(option_error(name, entry, "a u64")),
         }
     }
 
     pub fn get_multi_u64(&self, name: impl ToString) -> Result<&[u64]> {
-        match self.map.get(&name.to_string()) {
+        let name = name.to_string();
+        match self.map.get(&name) {
             Some(OptionsEntry::MultiU64(v)) => Ok(v),
-            _ => Err(Error::msg("The corresponding option must be a multi u64")),
+            entry => Err(option_error(name, entry, "a multi u64")),
         }
     }
+
+    pub fn get_stack_ref(&self, name: impl ToString) -> Result<usize> {
+        let name = name.to_string();
+        match self.map.get(&name) {
+            Some(OptionsEntry::StackRef(v, _)) => Ok(*v),
+            entry => Err(option_error(name, entry, "a stack reference")),
+        }
+    }
+
+    /// Returns all the memory entries referenced through the stack.
+    pub fn stack_refs(&self) -> Vec<usize> {
+        let mut refs = vec![];
+        for entry in self.map.values() {
+            if let OptionsEntry::StackRef(idx, len) = entry {
+                refs.extend(*idx..*idx + *len);
+            }
+        }
+        refs.sort_unstable();
+        refs
+    }
+
+    /// Rewrites the first index of all the stack references with the given map. The caller is
+    /// responsible for keeping each referenced block consecutive.
+    pub fn map_stack_refs(&self, mut f: impl FnMut(usize) -> Result<usize>) -> Result<Options> {
+        let mut res = self.clone();
+        for entry in res.map.values_mut() {
+            if let OptionsEntry::StackRef(idx, _) = entry {
+                *idx = f(*idx)?;
+            }
+        }
+        Ok(res)
+    }
+}
+
+fn option_error(name: String, entry: Option<&OptionsEntry>, expected: &'static str) -> Error {
+    match entry {
+        Some(_) => DslError::OptionTypeMismatch { name, expected }.into(),
+        None => DslError::MissingOption(name).into(),
+    }
 }
@@ -0,0 +1,387 @@
+use crate::compiler::CompiledProgram;
+use crate::treepp::*;
+use bitcoin::opcodes::all::*;
+use bitcoin::opcodes::Opcode;
+use bitcoin::script::{read_scriptint, Instruction};
+
+/// An instruction of the script, with its original encoding and the offset in the original
+/// script where it comes from.
+#[derive(Clone, Debug)]
+struct Item {
+    instruction: Token,
+    bytes: Vec<u8>,
+    origin: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Push(Vec<u8>),
+    Op(Opcode),
+}
+
+/// Rewrites short sequences of opcodes that the compiler produces when it concatenates the
+/// moves of the inputs and the scripts of the gadgets, such as `OP_DUP OP_DROP` or a pick
+/// immediately followed by a roll of the same element, into shorter equivalent ones.
+///
+/// The rewritten sequences are equivalent for every execution in which the original script does
+/// not fail because the stack is too short, which is the case for the scripts of the compiler.
+pub fn optimize(script: &Script) -> Script {
+    let items = optimize_items(parse(script, None));
+    Script::from_bytes(items.into_iter().flat_map(|item| item.bytes).collect())
+}
+
+/// Optimizes the script of a compiled program, keeping the offsets of the trace entries
+/// consistent. A sequence rewritten across two trace entries is attributed to the first one.
+pub fn optimize_program(program: &mut CompiledProgram) {
+    let items = optimize_items(parse(&program.script, None));
+
+    let mut trace_offsets = Vec::with_capacity(program.trace_offsets.len());
+    let mut script = Vec::with_capacity(program.script.len());
+
+    let mut old_offsets = program.trace_offsets.iter().peekable();
+    for item in items.into_iter() {
+        while old_offsets
+            .peek()
+            .is_some_and(|&&old_offset| old_offset <= item.origin)
+        {
+            old_offsets.next();
+            trace_offsets.push(script.len());
+        }
+        script.extend_from_slice(&item.bytes);
+    }
+    for _ in old_offsets {
+        trace_offsets.push(script.len());
+    }
+
+    program.script = Script::from_bytes(script);
+    program.trace_offsets = trace_offsets;
+}
+
+fn parse(script: &Script, origin: Option<usize>) -> Vec<Item> {
+    let bytes = script.as_bytes();
+    let indices = script
+        .instruction_indices()
+        .map(|res| res.expect("the script must be well-formed"))
+        .collect::<Vec<_>>();
+
+    let mut items = Vec::with_capacity(indices.len());
+    for (i, (start, instruction)) in indices.iter().enumerate() {
+        let end = indices.get(i + 1).map_or(bytes.len(), |(next, _)| *next);
+        let instruction = match instruction {
+            Instruction::PushBytes(data) => Token::Push(data.as_bytes().to_vec()),
+            Instruction::Op(op) => Token::Op(*op),
+        };
+        items.push(Item {
+            instruction,
+            bytes: bytes[*start..end].to_vec(),
+            origin: origin.unwrap_or(*start),
+        });
+    }
+    items
+}
+
+fn optimize_items(items: Vec<Item>) -> Vec<Item> {
+    let mut out = Vec::<Item>::with_capacity(items.len());
+    for item in items.into_iter() {
+        out.push(item);
+        while reduce_tail(&mut out) {}
+    }
+    out
+}
+
+/// Tries to rewrite a sequence at the end of `out`, and returns whether it did so.
+fn reduce_tail(out: &mut Vec<Item>) -> bool {
+    let len = out.len();
+    if len == 0 {
+        return false;
+    }
+    let op = |i: usize| match out.get(i).map(|item| &item.instruction) {
+        Some(Token::Op(op)) => Some(*op),
+        _ => None,
+    };
+    let Some(last) = op(len - 1) else {
+        return false;
+    };
+    let prev = if len >= 2 { op(len - 2) } else { None };
+
+    // a pick or a push followed by a drop does nothing
+    if last == OP_DROP && len >= 2 {
+        if let Some(pick_len) = pick_at_end(out, len - 1).map(|(_, pick_len)| pick_len) {
+            out.truncate(len - 1 - pick_len);
+            return true;
+        }
+        if small_num(&out[len - 2]).is_some() || matches!(out[len - 2].instruction, Token::Push(_))
+        {
+            out.truncate(len - 2);
+            return true;
+        }
+    }
+
+    // sequences that cancel each other
+    if let Some(prev) = prev {
+        if (prev == OP_SWAP && last == OP_SWAP)
+            || (prev == OP_2SWAP && last == OP_2SWAP)
+            || (prev == OP_TOALTSTACK && last == OP_FROMALTSTACK)
+            || (prev == OP_FROMALTSTACK && last == OP_TOALTSTACK)
+        {
+            out.truncate(len - 2);
+            return true;
+        }
+    }
+
+    // shorter forms of the same operation
+    if let Some(prev) = prev {
+        let origin = out[len - 2].origin;
+        let replacement = if prev == OP_DROP && last == OP_DROP {
+            Some(script! { OP_2DROP })
+        } else if prev == OP_OVER && last == OP_OVER {
+            Some(script! { OP_2DUP })
+        } else {
+            None
+        };
+        if let Some(replacement) = replacement {
+            out.truncate(len - 2);
+            out.extend(parse(&replacement, Some(origin)));
+            return true;
+        }
+    }
+    if len >= 2 && (last == OP_PICK || last == OP_ROLL) {
+        if let Some(n) = small_num(&out[len - 2]) {
+            let replacement = match (last == OP_PICK, n) {
+                (true, 0) => Some(script! { OP_DUP }),
+                (true, 1) => Some(script! { OP_OVER }),
+                (false, 0) => Some(script! {}),
+                (false, 1) => Some(script! { OP_SWAP }),
+                (false, 2) => Some(script! { OP_ROT }),
+                _ => None,
+            };
+            if let Some(replacement) = replacement {
+                let origin = out[len - 2].origin;
+                out.truncate(len - 2);
+                out.extend(parse(&replacement, Some(origin)));
+                return true;
+            }
+        }
+    }
+
+    // a pick of the element at depth `n` followed by a roll of the same element, now at depth
+    // `n + 1`, is a roll of this element and a copy of it
+    if let Some((m, roll_len)) = roll_at_end(out, len) {
+        if m >= 1 && len > roll_len {
+            if let Some((n, pick_len)) = pick_at_end(out, len - roll_len) {
+                if n + 1 == m {
+                    let start = len - roll_len - pick_len;
+                    let old_bytes = out[start..]
+                        .iter()
+                        .map(|item| item.bytes.len())
+                        .sum::<usize>();
+                    let replacement = script! {
+                        { roll_script(n) }
+                        OP_DUP
+                    };
+                    if replacement.len() < old_bytes {
+                        let origin = out[start].origin;
+                        out.truncate(start);
+                        out.extend(parse(&replacement, Some(origin)));
+                        return true;
+                    }
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// Recognizes a pick that ends right before `end`, and returns the depth of the picked element
+/// and the number of instructions of the pick.
+fn pick_at_end(items: &[Item], end: usize) -> Option<(i64, usize)> {
+    if end >= 1 {
+        match items[end - 1].instruction {
+            Token::Op(op) if op == OP_DUP => return Some((0, 1)),
+            Token::Op(op) if op == OP_OVER => return Some((1, 1)),
+            Token::Op(op) if op == OP_PICK && end >= 2 => {
+                return small_num(&items[end - 2])
+                    .filter(|&n| n >= 0)
+                    .map(|n| (n, 2));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Recognizes a roll that ends right before `end`, and returns the depth of the rolled element
+/// and the number of instructions of the roll.
+fn roll_at_end(items: &[Item], end: usize) -> Option<(i64, usize)> {
+    if end >= 1 {
+        match items[end - 1].instruction {
+            Token::Op(op) if op == OP_SWAP => return Some((1, 1)),
+            Token::Op(op) if op == OP_ROT => return Some((2, 1)),
+            Token::Op(op) if op == OP_ROLL && end >= 2 => {
+                return small_num(&items[end - 2])
+                    .filter(|&n| n >= 0)
+                    .map(|n| (n, 2));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn roll_script(n: i64) -> Script {
+    match n {
+        0 => script! {},
+        1 => script! { OP_SWAP },
+        2 => script! { OP_ROT },
+        _ => script! { { n } OP_ROLL },
+    }
+}
+
+/// Returns the number pushed by the instruction, if it is a push of a small number.
+fn small_num(item: &Item) -> Option<i64> {
+    match &item.instruction {
+        Token::Push(data) if data.len() <= 4 => read_scriptint(data).ok(),
+        Token::Op(op) if *op == OP_PUSHNUM_NEG1 => Some(-1),
+        Token::Op(op) if (OP_PUSHNUM_1.to_u8()..=OP_PUSHNUM_16.to_u8()).contains(&op.to_u8()) => {
+            Some((op.to_u8() - OP_PUSHNUM_1.to_u8() + 1) as i64)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::peephole::optimize;
+    use crate::treepp::*;
+    use bitcoin::hashes::Hash;
+    use bitcoin::{TapLeafHash, Transaction};
+    use bitcoin_scriptexec::{Exec, ExecCtx, FmtStack, Options, TxTemplate};
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+
+    /// Runs the script and returns the final stack.
+    fn run(script: Script) -> String {
+        let mut exec = Exec::new(
+            ExecCtx::Tapscript,
+            Options::default(),
+            TxTemplate {
+                tx: Transaction {
+                    version: bitcoin::transaction::Version::TWO,
+                    lock_time: bitcoin::locktime::absolute::LockTime::ZERO,
+                    input: vec![],
+                    output: vec![],
+                },
+                prevouts: vec![],
+                input_idx: 0,
+                taproot_annex_scriptleaf: Some((TapLeafHash::all_zeros(), None)),
+            },
+            script,
+            vec![],
+        )
+        .expect("error creating exec");
+
+        loop {
+            if exec.exec_next().is_err() {
+                break;
+            }
+        }
+        format!("{}", FmtStack(exec.stack().clone()))
+    }
+
+    #[test]
+    fn test_peephole_patterns() {
+        assert_eq!(optimize(&script! { OP_DUP OP_DROP }).len(), 0);
+        assert_eq!(optimize(&script! { OP_SWAP OP_SWAP }).len(), 0);
+        assert_eq!(optimize(&script! { 1234 OP_DROP }).len(), 0);
+        assert_eq!(optimize(&script! { 5 OP_PICK OP_DROP }).len(), 0);
+        assert_eq!(
+            optimize(&script! { 5 OP_PICK 6 OP_ROLL }),
+            script! { 5 OP_ROLL OP_DUP }
+        );
+        assert_eq!(optimize(&script! { OP_DUP OP_SWAP }), script! { OP_DUP });
+        assert_eq!(optimize(&script! { 1 OP_PICK }), script! { OP_OVER });
+        assert_eq!(optimize(&script! { 2 OP_ROLL }), script! { OP_ROT });
+        assert_eq!(optimize(&script! { OP_DROP OP_DROP }), script! { OP_2DROP });
+        // the removal of the inner pair exposes the outer one
+        assert_eq!(
+            optimize(&script! { OP_SWAP OP_DUP OP_DROP OP_SWAP }).len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_peephole_random() {
+        let mut prng = ChaCha20Rng::seed_from_u64(0);
+
+        for _ in 0..200 {
+            let mut depth = 16i64;
+            let mut alt_depth = 0i64;
+
+            let mut body = script! {}.to_bytes();
+            for _ in 0..40 {
+                let snippet = match prng.gen_range(0..10) {
+                    0 => {
+                        let n = prng.gen_range(0..depth - 1);
+                        depth += 1;
+                        script! { { n } OP_PICK { n + 1 } OP_ROLL }
+                    }
+                    1 => script! { OP_DUP OP_DROP },
+                    2 => script! { OP_SWAP OP_SWAP },
+                    3 => script! { { prng.gen::<i32>() } OP_DROP },
+                    4 => {
+                        let n = prng.gen_range(0..depth);
+                        depth += 1;
+                        script! { { n } OP_PICK }
+                    }
+                    5 => {
+                        let n = prng.gen_range(0..depth);
+                        script! { { n } OP_ROLL }
+                    }
+                    6 => {
+                        depth -= 1;
+                        alt_depth += 1;
+                        script! { OP_TOALTSTACK }
+                    }
+                    7 if alt_depth > 0 => {
+                        depth += 1;
+                        alt_depth -= 1;
+                        script! { OP_FROMALTSTACK }
+                    }
+                    8 => {
+                        depth -= 1;
+                        script! { OP_DROP }
+                    }
+                    _ => {
+                        depth += 1;
+                        script! { { prng.gen_range(-1000..1000) } }
+                    }
+                };
+                body.extend_from_slice(snippet.as_bytes());
+                if depth < 4 {
+                    body.extend_from_slice(script! { 1 2 3 }.as_bytes());
+                    depth += 3;
+                }
+            }
+
+            let mut prefix = script! {}.to_bytes();
+            for _ in 0..16 {
+                prefix.extend_from_slice(script! { { prng.gen::<i32>() } }.as_bytes());
+            }
+
+            let body = Script::from_bytes(body);
+            let optimized = optimize(&body);
+            assert!(optimized.len() <= body.len());
+
+            let mut before = prefix.clone();
+            before.extend_from_slice(body.as_bytes());
+            let mut after = prefix.clone();
+            after.extend_from_slice(optimized.as_bytes());
+
+            assert_eq!(
+                run(Script::from_bytes(before)),
+                run(Script::from_bytes(after))
+            );
+        }
+    }
+}
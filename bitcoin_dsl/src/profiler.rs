@@ -0,0 +1,189 @@
+use crate::bitcoin_system::{TraceEntry, TraceMeta, VariableLabel};
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// The cost of the trace entries that share the same label.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CostEntry {
+    /// The name of the variable for the constants and hints that are labeled, otherwise the scope
+    /// of the trace entries, or the location in the source code that created them.
+    pub label: String,
+    pub num_scripts: usize,
+    /// The bytes spent on moving the inputs of the scripts to the top of the stack.
+    pub move_bytes: usize,
+    /// The bytes of the scripts themselves.
+    pub body_bytes: usize,
+    pub num_constants: usize,
+    pub constant_bytes: usize,
+    pub num_hints: usize,
+    /// The bytes spent on moving the hints from the bottom of the stack.
+    pub hint_bytes: usize,
+}
+
+impl CostEntry {
+    pub fn total_bytes(&self) -> usize {
+        self.move_bytes + self.body_bytes + self.constant_bytes + self.hint_bytes
+    }
+}
+
+/// A report of where the bytes of a compiled script are spent.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CostReport {
+    /// The entries, from the most expensive to the cheapest.
+    pub entries: Vec<CostEntry>,
+    /// The bytes spent on the final cleanup of the stack.
+    pub cleanup_bytes: usize,
+    pub total_bytes: usize,
+}
+
+impl CostReport {
+    pub(crate) fn new(
+        trace: &[TraceEntry],
+        trace_meta: &[TraceMeta],
+        labels: &HashMap<usize, VariableLabel>,
+        trace_offsets: &[usize],
+        move_bytes: &[usize],
+        script_len: usize,
+    ) -> Self {
+        let mut map = IndexMap::<String, CostEntry>::new();
+
+        for (trace_idx, trace_entry) in trace.iter().enumerate() {
+            let variable_label = match trace_entry {
+                TraceEntry::DeclareConstant(idx) | TraceEntry::RequestHint(idx) => labels.get(idx),
+                _ => None,
+            };
+            let label = match variable_label {
+                Some(variable_label) => variable_label.name.to_string(),
+                None => trace_meta
+                    .get(trace_idx)
+                    .map_or("unknown".to_string(), TraceMeta::label),
+            };
+            let bytes = trace_offsets[trace_idx + 1] - trace_offsets[trace_idx];
+
+            let entry = map.entry(label.clone()).or_insert_with(|| CostEntry {
+                label,
+                ..Default::default()
+            });
+            match trace_entry {
+                TraceEntry::InsertScript(..) => {
+                    entry.num_scripts += 1;
+                    entry.move_bytes += move_bytes[trace_idx];
+                    entry.body_bytes += bytes - move_bytes[trace_idx];
+                }
+                TraceEntry::DeclareConstant(_) => {
+                    entry.num_constants += 1;
+                    entry.constant_bytes += bytes;
+                }
+                TraceEntry::RequestHint(_) => {
+                    entry.num_hints += 1;
+                    entry.hint_bytes += bytes;
+                }
+                TraceEntry::BeginIf(_) | TraceEntry::Else(_) | TraceEntry::EndIf(_) => {
+                    entry.move_bytes += bytes;
+                }
+                TraceEntry::DeclareOutput(_) | TraceEntry::SystemOutput(_) => {}
+            }
+        }
+
+        let mut entries = map
+            .into_values()
+            .filter(|entry| entry.total_bytes() != 0 || entry.num_hints != 0)
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| b.total_bytes().cmp(&a.total_bytes()));
+
+        Self {
+            entries,
+            cleanup_bytes: script_len - trace_offsets[trace.len()],
+            total_bytes: script_len,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl Display for CostReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:>10} {:>7} {:>10} {:>10} {:>10} {:>7}  label",
+            "total", "%", "moves", "body", "constants", "hints"
+        )?;
+        for entry in self.entries.iter() {
+            writeln!(
+                f,
+                "{:>10} {:>6.2}% {:>10} {:>10} {:>10} {:>7}  {}",
+                entry.total_bytes(),
+                entry.total_bytes() as f64 * 100.0 / self.total_bytes.max(1) as f64,
+                entry.move_bytes,
+                entry.body_bytes,
+                entry.constant_bytes,
+                entry.num_hints,
+                entry.label
+            )?;
+        }
+        writeln!(f, "{:>10} {:>7}  cleanup", self.cleanup_bytes, "")?;
+        write!(f, "{:>10} {:>7}  total", self.total_bytes, "")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bar::AllocBar;
+    use crate::basic::i32::I32Bar;
+    use crate::bitcoin_system::BitcoinSystemRef;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    #[test]
+    fn test_cost_report() {
+        let cs = BitcoinSystemRef::new_ref();
+
+        let a = I32Bar::new_hint_named(&cs, 1, "a").unwrap();
+        let b = I32Bar::new_constant(&cs, 1000).unwrap();
+        let c = cs.in_scope("sum", || &(&a + &b) + &a);
+        cs.set_program_output(&c).unwrap();
+
+        let program =
+            Compiler::compile_with_config(cs, &CompilerConfig::new().with_profiling()).unwrap();
+        let report = program.profile.unwrap();
+
+        assert_eq!(report.total_bytes, program.script.len());
+        assert_eq!(
+            report
+                .entries
+                .iter()
+                .map(|entry| entry.total_bytes())
+                .sum::<usize>()
+                + report.cleanup_bytes,
+            report.total_bytes
+        );
+
+        let sum = report
+            .entries
+            .iter()
+            .find(|entry| entry.label == "sum")
+            .unwrap();
+        assert_eq!(sum.num_scripts, 2);
+        assert_eq!(sum.num_hints, 0);
+        assert!(sum.move_bytes > 0);
+
+        let a = report
+            .entries
+            .iter()
+            .find(|entry| entry.label == "a")
+            .unwrap();
+        assert_eq!(a.num_hints, 1);
+
+        let table = report.to_string();
+        assert_eq!(table.lines().count(), report.entries.len() + 3);
+        assert!(table.lines().any(|line| line.ends_with("  sum")));
+        assert!(table.ends_with(&format!("{:>10} {:>7}  total", report.total_bytes, "")));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["total_bytes"], report.total_bytes);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::compiler::{CompiledProgram, CompilerBudget};
+use crate::debugger::Debugger;
+use crate::error::DslError;
+use crate::treepp::*;
+use anyhow::Result;
+
+/// The maximal weight of a standard transaction.
+pub const MAX_STANDARD_TX_WEIGHT: usize = 400_000;
+
+/// The weight of the non-witness part of a transaction that spends one taproot input to one
+/// taproot output, together with the segwit marker and flag.
+const BASE_TX_WEIGHT: usize = 4 * (4 + 1 + 41 + 1 + 43 + 4) + 2;
+
+/// The size of the control block of a script spent as the only leaf of a taproot tree.
+const CONTROL_BLOCK_SIZE: usize = 33;
+
+/// What a successful execution of a program costs, as measured by `test_program`.
+#[derive(Clone, Debug)]
+pub struct ExecutionReport {
+    /// The size of the script of the program, which excludes the pushes of the hints and the
+    /// inputs and the check of the final stack.
+    pub script_bytes: usize,
+    /// The size of the witness elements that carry the hints and the inputs, including their
+    /// length prefixes.
+    pub witness_bytes: usize,
+    /// The number of hints.
+    pub num_hints: usize,
+    /// The largest number of elements on the main stack during the execution.
+    pub max_stack_depth: usize,
+    /// The largest number of elements on the altstack during the execution.
+    pub max_altstack_depth: usize,
+    /// The number of opcodes of the program that have been executed.
+    pub num_opcodes: usize,
+    /// The stack when the program ends, from the bottom to the top.
+    pub final_stack: Vec<Vec<u8>>,
+    /// The estimated weight of a transaction that spends a taproot output with this script as
+    /// the only leaf and the hints and the inputs as the witness.
+    pub tx_weight: usize,
+}
+
+impl ExecutionReport {
+    /// Collects the report of a program from a debugger that has run it to the end.
+    pub fn new(program: &CompiledProgram, debugger: &Debugger) -> Result<Self> {
+        let witness = convert_to_witness(script! {
+            for elem in program.hint.iter() {
+                { elem }
+            }
+            for elem in program.input.iter() {
+                { elem }
+            }
+        })
+        .map_err(|x| DslError::InvalidWitness(format!("{:?}", x)))?;
+
+        let script_bytes = program.script.len();
+        let witness_bytes = witness
+            .iter()
+            .map(|elem| compact_size_len(elem.len()) + elem.len())
+            .sum::<usize>();
+        let (max_stack_depth, max_altstack_depth) = debugger.max_stack_depths();
+
+        let tx_weight = BASE_TX_WEIGHT
+            + compact_size_len(witness.len() + 2)
+            + witness_bytes
+            + compact_size_len(script_bytes)
+            + script_bytes
+            + compact_size_len(CONTROL_BLOCK_SIZE)
+            + CONTROL_BLOCK_SIZE;
+
+        Ok(Self {
+            script_bytes,
+            witness_bytes,
+            num_hints: program.hint.len(),
+            max_stack_depth,
+            max_altstack_depth,
+            num_opcodes: debugger.num_opcodes(),
+            final_stack: debugger.final_stack().unwrap_or_default().to_vec(),
+            tx_weight,
+        })
+    }
+
+    /// Checks that the execution fits in the budget and in a standard transaction.
+    pub fn check_budget(&self, budget: &CompilerBudget) -> Result<()> {
+        let checks = [
+            (
+                "stack items",
+                self.max_stack_depth + self.max_altstack_depth,
+                budget.max_stack_items,
+            ),
+            ("script bytes", self.script_bytes, budget.max_script_size),
+            ("transaction WU", self.tx_weight, MAX_STANDARD_TX_WEIGHT),
+        ];
+        for (resource, value, limit) in checks {
+            if value > limit {
+                return Err(DslError::ExecutionOverBudget {
+                    resource,
+                    value,
+                    limit,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn compact_size_len(n: usize) -> usize {
+    match n {
+        0..=0xfc => 1,
+        0xfd..=0xffff => 3,
+        0x10000..=0xffff_ffff => 5,
+        _ => 9,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::basic::i32::I32Bar;
+    use crate::bitcoin_system::BitcoinSystemRef;
+    use crate::compiler::CompilerBudget;
+    use crate::error::DslError;
+    use crate::test_program;
+    use crate::treepp::*;
+
+    #[test]
+    fn test_execution_report() {
+        let cs = BitcoinSystemRef::new_ref();
+        let a = I32Bar::new_hint(&cs, 3).unwrap();
+        let b = I32Bar::new_hint(&cs, 200).unwrap();
+        let c = I32Bar::new_constant(&cs, 4).unwrap();
+        cs.set_program_output(&(&(&a + &b) + &c)).unwrap();
+
+        let report = test_program(cs, script! { 207 }).unwrap();
+        assert_eq!(report.num_hints, 2);
+        // each element has a length byte, and 3 takes one byte and 200 takes two
+        assert_eq!(report.witness_bytes, 2 + 3);
+        assert_eq!(report.final_stack, vec![vec![207, 0]]);
+        assert!(report.num_opcodes > 0);
+        assert!(report.max_stack_depth >= 3);
+        assert!(report.tx_weight > report.script_bytes + report.witness_bytes);
+        report.check_budget(&CompilerBudget::default()).unwrap();
+
+        let tight = CompilerBudget {
+            max_stack_items: 1,
+            ..Default::default()
+        };
+        let err = report.check_budget(&tight).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DslError>(),
+            Some(DslError::ExecutionOverBudget {
+                resource: "stack items",
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,462 @@
+use crate::bitcoin_system::{BitcoinSystem, TraceEntry};
+use crate::options::OptionsEntry;
+use crate::stack::Stack;
+use anyhow::{Error, Result};
+use indexmap::IndexMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// The number of ready scripts, in the order of the original trace, among which the scheduler
+/// picks the next one. Keeping it small preserves the locality of the original trace.
+const WINDOW: usize = 32;
+
+/// How many bytes of moves an additional element on the stack is worth.
+const GROWTH_WEIGHT: usize = 2;
+
+/// A group of trace entries that the scheduler moves together.
+struct Unit {
+    /// The indices of the trace entries, in their original order.
+    entries: Vec<usize>,
+    /// The memory entries that the unit uses, either as inputs or through stack references.
+    uses: Vec<usize>,
+    /// The memory entries that the unit declares.
+    defs: Vec<usize>,
+    is_script: bool,
+}
+
+/// Reorders the independent trace entries of a system to reduce the distance of the moves and
+/// the number of elements on the stack, and returns the reordered system.
+///
+/// A script and the outputs it declares are kept together, and the constants and the hints are
+/// deferred until the first script that uses them. Among the scripts whose inputs are available,
+/// the scheduler greedily picks the one with the cheapest moves, favoring the ones that consume
+/// the last use of their inputs.
+///
+/// Since the compiler expects the memory entries to be pushed in the order of their indices, the
+/// memory entries of the returned system are renumbered in the order of their declaration. If
+/// the trace has a shape that the scheduler does not understand, the order is left unchanged.
+pub fn schedule(cs: &BitcoinSystem) -> Result<BitcoinSystem> {
+    let order = match build_units(cs) {
+        Some((units, outputs)) => schedule_units(cs, &units, &outputs)?,
+        None => (0..cs.trace.len()).collect(),
+    };
+    reorder(cs, &order)
+}
+
+fn num_inputs(cs: &BitcoinSystem) -> usize {
+    cs.num_inputs.unwrap_or(cs.memory_last_idx)
+}
+
+/// Splits the trace into units, and returns them together with the indices of the trace entries
+/// for the system outputs, or `None` if the trace cannot be split.
+fn build_units(cs: &BitcoinSystem) -> Option<(Vec<Unit>, Vec<usize>)> {
+    // the blocks of memory entries located through the stack must be declared together
+    let mut block_of = HashMap::<usize, (usize, usize)>::new();
+    for trace_entry in cs.trace.iter() {
+        if let TraceEntry::InsertScript(_, _, options) = trace_entry {
+            for entry in options.map.values() {
+                if let OptionsEntry::StackRef(first, len) = entry {
+                    for idx in *first..*first + *len {
+                        if *block_of.entry(idx).or_insert((*first, *len)) != (*first, *len) {
+                            return None;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut units = Vec::<Unit>::new();
+    let mut outputs = vec![];
+    for (trace_idx, trace_entry) in cs.trace.iter().enumerate() {
+        let follows_last = trace_idx > 0
+            && units
+                .last()
+                .is_some_and(|unit| unit.entries.last() == Some(&(trace_idx - 1)));
+        match trace_entry {
+            TraceEntry::InsertScript(_, inputs, options) => {
+                let mut uses = inputs.clone();
+                uses.extend(options.stack_refs());
+                units.push(Unit {
+                    entries: vec![trace_idx],
+                    uses,
+                    defs: vec![],
+                    is_script: true,
+                });
+            }
+            TraceEntry::DeclareOutput(idx) => {
+                let unit = units.last_mut().filter(|unit| unit.is_script)?;
+                if !follows_last {
+                    return None;
+                }
+                unit.entries.push(trace_idx);
+                unit.defs.push(*idx);
+            }
+            TraceEntry::DeclareConstant(idx) | TraceEntry::RequestHint(idx) => {
+                let block = block_of.get(idx).copied();
+                let extends_block = block.is_some_and(|(first, _)| {
+                    *idx != first
+                        && follows_last
+                        && units.last().is_some_and(|unit| {
+                            !unit.is_script && unit.defs.last() == Some(&(idx - 1))
+                        })
+                });
+                if extends_block {
+                    let unit = units.last_mut().unwrap();
+                    unit.entries.push(trace_idx);
+                    unit.defs.push(*idx);
+                } else {
+                    units.push(Unit {
+                        entries: vec![trace_idx],
+                        uses: vec![],
+                        defs: vec![*idx],
+                        is_script: false,
+                    });
+                }
+            }
+            TraceEntry::SystemOutput(_) => outputs.push(trace_idx),
+            // the entries cannot move in or out of a conditional branch
+            TraceEntry::BeginIf(_) | TraceEntry::Else(_) | TraceEntry::EndIf(_) => return None,
+        }
+    }
+
+    // every block must be declared by a single unit, unless it consists of program inputs
+    for unit in units.iter().filter(|unit| !unit.is_script) {
+        if let Some(&(first, len)) = block_of.get(&unit.defs[0]) {
+            if unit.defs.len() != len || unit.defs[0] != first {
+                return None;
+            }
+        }
+    }
+
+    Some((units, outputs))
+}
+
+/// Returns the new order of the trace entries.
+fn schedule_units(cs: &BitcoinSystem, units: &[Unit], outputs: &[usize]) -> Result<Vec<usize>> {
+    let num_inputs = num_inputs(cs);
+
+    let system_outputs = outputs
+        .iter()
+        .filter_map(|&trace_idx| match cs.trace[trace_idx] {
+            TraceEntry::SystemOutput(idx) => Some(idx),
+            _ => None,
+        })
+        .collect::<HashSet<usize>>();
+    let stack_refs = cs
+        .trace
+        .iter()
+        .flat_map(|trace_entry| match trace_entry {
+            TraceEntry::InsertScript(_, _, options) => options.stack_refs(),
+            _ => vec![],
+        })
+        .collect::<HashSet<usize>>();
+
+    let mut def_unit = HashMap::<usize, usize>::new();
+    for (unit_idx, unit) in units.iter().enumerate() {
+        for &idx in unit.defs.iter() {
+            def_unit.insert(idx, unit_idx);
+        }
+    }
+
+    // the number of scripts that remain to use each memory entry, and the number of scripts
+    // that each script waits for
+    let mut remaining_uses = vec![0usize; cs.memory_last_idx];
+    let mut pending = vec![0usize; units.len()];
+    let mut consumers = vec![vec![]; units.len()];
+    for (unit_idx, unit) in units.iter().enumerate() {
+        let distinct = unit.uses.iter().copied().collect::<BTreeSet<usize>>();
+        for &idx in distinct.iter() {
+            remaining_uses[idx] += 1;
+            if let Some(&producer) = def_unit.get(&idx) {
+                if units[producer].is_script {
+                    pending[unit_idx] += 1;
+                    consumers[producer].push(unit_idx);
+                }
+            }
+        }
+    }
+
+    let mut ready = (0..units.len())
+        .filter(|&unit_idx| units[unit_idx].is_script && pending[unit_idx] == 0)
+        .collect::<BTreeSet<usize>>();
+
+    // the stack is modeled with the new indices, which follow the order of the pushes
+    let mut state = State {
+        stack: Stack::new(cs.memory_last_idx),
+        new_idx: vec![None; cs.memory_last_idx],
+        next_idx: num_inputs,
+        emitted: vec![false; units.len()],
+        order: Vec::with_capacity(cs.trace.len()),
+    };
+    for (idx, new) in state.new_idx.iter_mut().enumerate().take(num_inputs) {
+        *new = Some(idx);
+        state.stack.push_to_stack(idx)?;
+    }
+
+    while !ready.is_empty() {
+        let mut best = None;
+        for &unit_idx in ready.iter().take(WINDOW) {
+            let unit = &units[unit_idx];
+            let TraceEntry::InsertScript(_, inputs, _) = &cs.trace[unit.entries[0]] else {
+                unreachable!()
+            };
+
+            let mut cost = 0;
+            let mut growth = unit.defs.len() as isize;
+            let mut materialized = HashSet::new();
+            for (i, &idx) in inputs.iter().enumerate() {
+                match state.new_idx[idx] {
+                    Some(new) => cost += move_len(state.stack.get_relative_position(new)? + i),
+                    None => {
+                        if materialized.insert(def_unit[&idx]) {
+                            growth += units[def_unit[&idx]].defs.len() as isize;
+                        }
+                    }
+                }
+            }
+            for idx in unit.uses.iter().copied().collect::<BTreeSet<usize>>() {
+                if remaining_uses[idx] == 1
+                    && !system_outputs.contains(&idx)
+                    && !stack_refs.contains(&idx)
+                {
+                    growth -= 1;
+                }
+            }
+
+            let score = cost as isize + GROWTH_WEIGHT as isize * growth;
+            let better = match best {
+                Some((best_score, _)) => score < best_score,
+                None => true,
+            };
+            if better {
+                best = Some((score, unit_idx));
+            }
+        }
+
+        let (_, unit_idx) = best.unwrap();
+        ready.remove(&unit_idx);
+        let unit = &units[unit_idx];
+
+        for &idx in unit.uses.iter() {
+            if state.new_idx[idx].is_none() {
+                state.emit(def_unit[&idx], &units[def_unit[&idx]])?;
+            }
+        }
+
+        // mirror the rolls of the compiler
+        let TraceEntry::InsertScript(_, inputs, _) = &cs.trace[unit.entries[0]] else {
+            unreachable!()
+        };
+        for (i, &idx) in inputs.iter().enumerate() {
+            if remaining_uses[idx] == 1
+                && !inputs[i + 1..].contains(&idx)
+                && !system_outputs.contains(&idx)
+                && !stack_refs.contains(&idx)
+            {
+                state.stack.pull(state.new_idx[idx].unwrap())?;
+            }
+        }
+        for idx in unit.uses.iter().copied().collect::<BTreeSet<usize>>() {
+            remaining_uses[idx] -= 1;
+        }
+
+        state.emit(unit_idx, unit)?;
+
+        for &consumer in consumers[unit_idx].iter() {
+            pending[consumer] -= 1;
+            if pending[consumer] == 0 {
+                ready.insert(consumer);
+            }
+        }
+    }
+
+    for (unit_idx, unit) in units.iter().enumerate() {
+        if unit.is_script && !state.emitted[unit_idx] {
+            return Err(Error::msg("The trace has a cyclic dependency"));
+        }
+    }
+
+    // the constants and the hints that no script uses
+    for (unit_idx, unit) in units.iter().enumerate() {
+        if !state.emitted[unit_idx] {
+            state.emit(unit_idx, unit)?;
+        }
+    }
+    state.order.extend_from_slice(outputs);
+
+    Ok(state.order)
+}
+
+/// The state of the scheduler while it emits the units.
+struct State {
+    /// The stack, modeled with the new indices of the memory entries, which follow the order of
+    /// the pushes.
+    stack: Stack,
+    new_idx: Vec<Option<usize>>,
+    next_idx: usize,
+    emitted: Vec<bool>,
+    order: Vec<usize>,
+}
+
+impl State {
+    fn emit(&mut self, unit_idx: usize, unit: &Unit) -> Result<()> {
+        self.emitted[unit_idx] = true;
+        self.order.extend_from_slice(&unit.entries);
+        for &idx in unit.defs.iter() {
+            self.new_idx[idx] = Some(self.next_idx);
+            self.stack.push_to_stack(self.next_idx)?;
+            self.next_idx += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Estimates the number of bytes to move an element at the given distance to the top.
+fn move_len(distance: usize) -> usize {
+    if distance <= 2 {
+        1
+    } else if distance <= 16 {
+        2
+    } else {
+        3
+    }
+}
+
+/// Rebuilds the system with the trace entries in the given order, renumbering the memory entries
+/// in the order of their declaration.
+fn reorder(cs: &BitcoinSystem, order: &[usize]) -> Result<BitcoinSystem> {
+    let num_inputs = num_inputs(cs);
+
+    let mut new_idx = vec![None; cs.memory_last_idx];
+    for (idx, new) in new_idx.iter_mut().enumerate().take(num_inputs) {
+        *new = Some(idx);
+    }
+    let mut next_idx = num_inputs;
+    for &trace_idx in order.iter() {
+        match &cs.trace[trace_idx] {
+            TraceEntry::DeclareConstant(idx)
+            | TraceEntry::DeclareOutput(idx)
+            | TraceEntry::RequestHint(idx) => {
+                new_idx[*idx] = Some(next_idx);
+                next_idx += 1;
+            }
+            _ => {}
+        }
+    }
+    if next_idx != cs.memory_last_idx {
+        return Err(Error::msg(
+            "Some memory entries are neither inputs nor declared in the trace",
+        ));
+    }
+    let map = |idx: usize| new_idx[idx].ok_or_else(|| Error::msg("Unknown memory entry"));
+
+    let mut old_idx = vec![0; cs.memory_last_idx];
+    for (idx, new) in new_idx.iter().enumerate() {
+        old_idx[new.unwrap()] = idx;
+    }
+    let mut memory = IndexMap::with_capacity(cs.memory_last_idx);
+    for (new, &idx) in old_idx.iter().enumerate() {
+        memory.insert(new, cs.memory.get(&idx).unwrap().clone());
+    }
+
+    let mut trace = Vec::with_capacity(order.len());
+    let mut trace_meta = Vec::with_capacity(order.len());
+    for &trace_idx in order.iter() {
+        trace.push(match &cs.trace[trace_idx] {
+            TraceEntry::InsertScript(script_generator, inputs, options) => {
+                for entry in options.map.values() {
+                    if let OptionsEntry::StackRef(first, len) = entry {
+                        for k in 0..*len {
+                            if map(first + k)? != map(*first)? + k {
+                                return Err(Error::msg(
+                                    "A block referenced through the stack is no longer consecutive",
+                                ));
+                            }
+                        }
+                    }
+                }
+                TraceEntry::InsertScript(
+                    script_generator.clone(),
+                    inputs.iter().map(|&idx| map(idx)).collect::<Result<_>>()?,
+                    options.map_stack_refs(map)?,
+                )
+            }
+            TraceEntry::DeclareConstant(idx) => TraceEntry::DeclareConstant(map(*idx)?),
+            TraceEntry::DeclareOutput(idx) => TraceEntry::DeclareOutput(map(*idx)?),
+            TraceEntry::RequestHint(idx) => TraceEntry::RequestHint(map(*idx)?),
+            TraceEntry::SystemOutput(idx) => TraceEntry::SystemOutput(map(*idx)?),
+            TraceEntry::BeginIf(idx) => TraceEntry::BeginIf(map(*idx)?),
+            TraceEntry::Else(results) => {
+                TraceEntry::Else(results.iter().map(|&idx| map(idx)).collect::<Result<_>>()?)
+            }
+            TraceEntry::EndIf(results) => {
+                TraceEntry::EndIf(results.iter().map(|&idx| map(idx)).collect::<Result<_>>()?)
+            }
+        });
+        if let Some(meta) = cs.trace_meta.get(trace_idx) {
+            trace_meta.push(meta.clone());
+        }
+    }
+
+    let mut labels = HashMap::with_capacity(cs.labels.len());
+    for (&idx, label) in cs.labels.iter() {
+        labels.insert(map(idx)?, label.clone());
+    }
+
+    Ok(BitcoinSystem {
+        memory,
+        memory_last_idx: cs.memory_last_idx,
+        trace,
+        num_inputs: cs.num_inputs,
+        finalized: cs.finalized,
+        trace_meta,
+        scopes: cs.scopes.clone(),
+        labels,
+        dedup_constants: cs.dedup_constants,
+        constants: HashMap::new(),
+        constant_blocks: HashMap::new(),
+        branch_depth: cs.branch_depth,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bar::AllocBar;
+    use crate::basic::i32::I32Bar;
+    use crate::bitcoin_system::BitcoinSystemRef;
+    use crate::compiler::{Compiler, CompilerConfig};
+    use crate::debugger::Debugger;
+    use crate::treepp::*;
+
+    #[test]
+    fn test_schedule() {
+        let build = || {
+            let cs = BitcoinSystemRef::new_ref();
+
+            // all the hints are requested first, far from their use
+            let mut values = vec![];
+            for i in 0..20 {
+                values.push(I32Bar::new_hint(&cs, i).unwrap());
+            }
+            let offset = I32Bar::new_constant(&cs, 1000).unwrap();
+
+            let mut sum = &values[0] + &offset;
+            for value in values.iter().skip(1) {
+                sum = &sum + value;
+            }
+            cs.set_program_output(&sum).unwrap();
+            cs
+        };
+
+        let program = Compiler::compile(build()).unwrap();
+        let scheduled =
+            Compiler::compile_with_config(build(), &CompilerConfig::new().with_scheduling())
+                .unwrap();
+
+        assert!(scheduled.script.len() < program.script.len());
+        assert!(scheduled.max_stack_items < program.max_stack_items);
+
+        let mut debugger = Debugger::new(&scheduled, script! { 1190 }, true).unwrap();
+        assert!(debugger.run());
+    }
+}
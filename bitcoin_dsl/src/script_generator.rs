@@ -2,11 +2,31 @@ use crate::options::Options;
 use crate::stack::Stack;
 use crate::treepp::Script;
 use anyhow::Result;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
 
-#[derive(Clone, Debug)]
+/// A script generator that captures its parameters, which are therefore typed and checked at
+/// compile time, instead of reading them from `Options`.
+pub type ClosureGenerator = Arc<dyn Fn(&mut Stack) -> Result<Script> + Send + Sync>;
+
+#[derive(Clone)]
 pub enum ScriptGenerator {
     Simple(fn() -> Script),
     Complex(fn(&mut Stack, &Options) -> Result<Script>),
+    Closure(ClosureGenerator),
+}
+
+impl Debug for ScriptGenerator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScriptGenerator::Simple(g) => f.debug_tuple("Simple").field(g).finish(),
+            ScriptGenerator::Complex(g) => f.debug_tuple("Complex").field(g).finish(),
+            ScriptGenerator::Closure(g) => f
+                .debug_tuple("Closure")
+                .field(&Arc::as_ptr(g).cast::<()>())
+                .finish(),
+        }
+    }
 }
 
 impl ScriptGenerator {
@@ -14,6 +34,7 @@ impl ScriptGenerator {
         match self {
             ScriptGenerator::Simple(f) => Ok(f()),
             ScriptGenerator::Complex(f) => f(stack, options),
+            ScriptGenerator::Closure(f) => f(stack),
         }
     }
 }
@@ -0,0 +1,786 @@
+use crate::bar::{dummy_script, AllocBar, AllocationMode, Bar};
+use crate::bitcoin_system::{BitcoinSystem, BitcoinSystemRef, Element, TraceEntry};
+use crate::compiler::{Compiler, CompilerConfig};
+use crate::ldm::{LDMKey, LDM};
+use crate::options::OptionsEntry;
+use anyhow::{Error, Result};
+use std::collections::HashMap;
+use std::ops::Range;
+use stwo_prover::core::vcs::sha256_hash::Sha256Hash;
+
+/// Estimated script size of carrying one element across a boundary: hashing it into the LDM
+/// when it is written, and requesting and hashing it again when it is read.
+const BYTES_PER_CARRIED_ELEMENT: usize = 12;
+
+/// Estimated script size of the LDM bookkeeping that every chunk has, no matter how many
+/// elements it carries.
+const BYTES_PER_CHUNK: usize = 64;
+
+/// Estimated number of stack elements used by the LDM bookkeeping of a chunk.
+const STACK_ITEMS_PER_CHUNK: usize = 4;
+
+/// The resources that a single chunk is allowed to use.
+#[derive(Clone, Copy, Debug)]
+pub struct SplitBudget {
+    /// The maximal size of the script of a chunk, in bytes.
+    pub max_script_size: usize,
+    /// The maximal number of elements on the stack during the execution of a chunk.
+    pub max_stack_items: usize,
+}
+
+impl Default for SplitBudget {
+    fn default() -> Self {
+        // leave some room in the transaction for the witness
+        Self {
+            max_script_size: 350_000,
+            max_stack_items: 1000,
+        }
+    }
+}
+
+/// A chunk of a program that has been split.
+pub struct SplitChunk {
+    /// The system of the chunk.
+    pub cs: BitcoinSystemRef,
+    /// The LDM hash that the chunk leaves on the stack. The last chunk leaves the outputs of the
+    /// original program below it.
+    pub ldm_hash: Sha256Hash,
+    /// The LDM after the chunk.
+    pub ldm: LDM,
+}
+
+/// The LDM entries through which a split part is chained with the parts around it.
+struct PartBoundary<'a> {
+    /// Reads the program inputs in the first chunk.
+    read_inputs: &'a dyn Fn(&mut LDM) -> Result<Vec<usize>>,
+    /// Writes the program outputs in the last chunk.
+    write_outputs: &'a dyn Fn(&mut LDM, &BitcoinSystemRef, Vec<usize>) -> Result<()>,
+}
+
+/// Splits a program into chunks that each fit in the budget.
+///
+/// The chunks are chained through `ldm`: the elements that are still needed after a boundary
+/// are written to the LDM under `{name}_{k}` at the end of one chunk and read back at the start
+/// of chunk `k`. Constants are declared again instead, in their original order, so that blocks
+/// of constants referenced through the stack (such as the table) stay consecutive.
+///
+/// The first chunk takes the program inputs of the original program, and the last chunk
+/// produces its program outputs. The split is chosen from estimates and then checked against
+/// the compiled script size and stack usage of each chunk, shrinking a chunk when the estimate
+/// was too low.
+pub fn split_program(
+    cs: &BitcoinSystemRef,
+    ldm: &mut LDM,
+    name: &str,
+    budget: SplitBudget,
+) -> Result<Vec<SplitChunk>> {
+    split(cs, ldm, name, budget, None, 0)
+}
+
+/// Splits a program that stands for a part chained with the other parts through `ldm`, in the
+/// same way as `split_program`. Instead of taking program inputs, the first chunk reads them
+/// from the entry `input`, and instead of producing the program output `output`, the last chunk
+/// writes it to the entry `output_key`.
+///
+/// The chunks before `first_chunk` are only built on copies of `ldm` and are not returned, so
+/// that the part can be resumed from the LDM after one of its chunks.
+pub fn split_part<I: Bar + AllocBar, O: Bar + AllocBar>(
+    cs: &BitcoinSystemRef,
+    output: &O,
+    ldm: &mut LDM,
+    keys: (&LDMKey<I>, &LDMKey<O>),
+    name: &str,
+    budget: SplitBudget,
+    first_chunk: usize,
+) -> Result<Vec<SplitChunk>> {
+    let (input_key, output_key) = keys;
+    let value = output.value()?;
+
+    let read_inputs =
+        |ldm: &mut LDM| -> Result<Vec<usize>> { Ok(ldm.read_key(input_key)?.variables()) };
+    let write_outputs =
+        |ldm: &mut LDM, cs: &BitcoinSystemRef, variables: Vec<usize>| -> Result<()> {
+            if variables.len() != output.variables().len() {
+                return Err(Error::msg(format!(
+                    "The part has {} program outputs, but its output has {} elements",
+                    variables.len(),
+                    output.variables().len()
+                )));
+            }
+            // take the outputs over as a variable of the type of the entry
+            cs.insert_script(dummy_script, variables)?;
+            let output = O::new_function_output(cs, value.clone())?;
+            ldm.write_key(output_key, &output)
+        };
+    let boundary = PartBoundary {
+        read_inputs: &read_inputs,
+        write_outputs: &write_outputs,
+    };
+
+    split(cs, ldm, name, budget, Some(&boundary), first_chunk)
+}
+
+fn split(
+    cs: &BitcoinSystemRef,
+    ldm: &mut LDM,
+    name: &str,
+    budget: SplitBudget,
+    boundary: Option<&PartBoundary>,
+    first_chunk: usize,
+) -> Result<Vec<SplitChunk>> {
+    let sys = cs.0.borrow();
+    if sys.trace.iter().any(|trace_entry| {
+        matches!(
+            trace_entry,
+            TraceEntry::BeginIf(_) | TraceEntry::Else(_) | TraceEntry::EndIf(_)
+        )
+    }) {
+        return Err(Error::msg(
+            "Programs with conditional branches cannot be split",
+        ));
+    }
+    let program = Compiler::compile_system(&sys, &CompilerConfig::default())?;
+    let liveness = Liveness::new(&sys);
+
+    let entry_bytes = (0..sys.trace.len())
+        .map(|t| program.trace_entry_bytes(t).len())
+        .collect::<Vec<_>>();
+
+    let mut chunks = vec![];
+    let mut chunk_idx = 0;
+    let mut start = 0;
+    while start < sys.trace.len() {
+        let mut estimate_budget = budget;
+        loop {
+            let end = liveness.find_end(&sys.trace, &entry_bytes, start, estimate_budget)?;
+
+            let mut trial_ldm = ldm.clone();
+            let chunk = build_chunk(
+                &sys,
+                &liveness,
+                &mut trial_ldm,
+                name,
+                boundary,
+                chunk_idx,
+                start..end,
+            )?;
+
+            let program = Compiler::compile(chunk.cs.clone())?;
+            let script_excess = program.script.len().saturating_sub(budget.max_script_size);
+            let stack_excess = program
+                .max_stack_items
+                .saturating_sub(budget.max_stack_items);
+            if script_excess == 0 && stack_excess == 0 {
+                if chunk_idx >= first_chunk {
+                    *ldm = trial_ldm;
+                    chunks.push(chunk);
+                }
+                chunk_idx += 1;
+                start = end;
+                break;
+            }
+
+            let error = || {
+                Error::msg(format!(
+                    "The trace entry {} does not fit in the budget",
+                    start
+                ))
+            };
+            estimate_budget.max_script_size = estimate_budget
+                .max_script_size
+                .checked_sub(script_excess)
+                .ok_or_else(error)?;
+            estimate_budget.max_stack_items = estimate_budget
+                .max_stack_items
+                .checked_sub(stack_excess)
+                .ok_or_else(error)?;
+        }
+    }
+
+    Ok(chunks)
+}
+
+struct Liveness {
+    /// The first memory entry that is not a program input.
+    num_inputs: usize,
+    /// The trace entry that defines each memory entry, or `None` for program inputs.
+    def: Vec<Option<usize>>,
+    /// The last trace entry that uses each memory entry, the length of the trace for the program
+    /// outputs, or `None` if the memory entry is never used.
+    last_use: Vec<Option<usize>>,
+    is_constant: Vec<bool>,
+    /// The number of non-constant memory entries that need to cross each boundary.
+    live_vars: Vec<usize>,
+    /// The number of constants that need to be declared again at each boundary.
+    live_consts: Vec<usize>,
+    /// The script size of declaring these constants again.
+    live_const_bytes: Vec<usize>,
+    /// The number of memory entries removed from the stack by each trace entry.
+    removed: Vec<usize>,
+}
+
+impl Liveness {
+    fn new(sys: &BitcoinSystem) -> Self {
+        let n = sys.trace.len();
+        let num_entries = sys.memory_last_idx;
+        let num_inputs = sys.num_inputs.unwrap_or(num_entries);
+
+        let mut def = vec![None; num_entries];
+        let mut last_use = vec![None; num_entries];
+        let mut is_constant = vec![false; num_entries];
+        let mut outputs = vec![];
+
+        for (t, trace_entry) in sys.trace.iter().enumerate() {
+            match trace_entry {
+                TraceEntry::InsertScript(_, inputs, options) => {
+                    for i in inputs.iter().copied().chain(options.stack_refs()) {
+                        last_use[i] = Some(t);
+                    }
+                }
+                TraceEntry::DeclareConstant(i) => {
+                    def[*i] = Some(t);
+                    is_constant[*i] = true;
+                }
+                TraceEntry::DeclareOutput(i) | TraceEntry::RequestHint(i) => {
+                    def[*i] = Some(t);
+                }
+                TraceEntry::SystemOutput(i) => {
+                    outputs.push(*i);
+                }
+                TraceEntry::BeginIf(i) => last_use[*i] = Some(t),
+                TraceEntry::Else(results) | TraceEntry::EndIf(results) => {
+                    for &i in results.iter() {
+                        last_use[i] = Some(t);
+                    }
+                }
+            }
+        }
+        for i in outputs {
+            last_use[i] = Some(n);
+        }
+
+        // an entry removes an input from the stack when this is its last use, following the
+        // compiler, which rolls it in that case
+        let mut removed = vec![0; n];
+        for (t, trace_entry) in sys.trace.iter().enumerate() {
+            if let TraceEntry::InsertScript(_, inputs, options) = trace_entry {
+                let stack_refs = options.stack_refs();
+                let mut seen = vec![];
+                for &i in inputs.iter() {
+                    if last_use[i] == Some(t) && !stack_refs.contains(&i) && !seen.contains(&i) {
+                        removed[t] += 1;
+                        seen.push(i);
+                    }
+                }
+            }
+        }
+
+        // a memory entry crosses the boundary `e` (before the trace entry `e`) if it is defined
+        // before `e` and used at or after `e`
+        let mut live_vars_diff = vec![0isize; n + 2];
+        let mut live_consts_diff = vec![0isize; n + 2];
+        let mut live_const_bytes_diff = vec![0isize; n + 2];
+        for (i, &last_use) in last_use.iter().enumerate() {
+            let Some(u) = last_use else {
+                continue;
+            };
+            let first = def[i].map_or(0, |d| d + 1);
+            if first > u {
+                continue;
+            }
+            if is_constant[i] {
+                let bytes = constant_bytes(sys.memory.get(&i).unwrap()) as isize;
+                live_consts_diff[first] += 1;
+                live_consts_diff[u + 1] -= 1;
+                live_const_bytes_diff[first] += bytes;
+                live_const_bytes_diff[u + 1] -= bytes;
+            } else {
+                live_vars_diff[first] += 1;
+                live_vars_diff[u + 1] -= 1;
+            }
+        }
+
+        Self {
+            num_inputs,
+            def,
+            last_use,
+            is_constant,
+            live_vars: prefix_sum(&live_vars_diff, n + 1),
+            live_consts: prefix_sum(&live_consts_diff, n + 1),
+            live_const_bytes: prefix_sum(&live_const_bytes_diff, n + 1),
+            removed,
+        }
+    }
+
+    /// Returns the memory entries that cross the boundary `e`, split into the non-constant ones
+    /// and the constants, each in increasing order.
+    fn crossing(&self, e: usize) -> (Vec<usize>, Vec<usize>) {
+        let mut vars = vec![];
+        let mut consts = vec![];
+        for (i, def) in self.def.iter().enumerate() {
+            let defined_before = def.is_none_or(|d| d < e);
+            let used_after = self.last_use[i].is_some_and(|u| u >= e);
+            if defined_before && used_after {
+                if self.is_constant[i] {
+                    consts.push(i);
+                } else {
+                    vars.push(i);
+                }
+            }
+        }
+        (vars, consts)
+    }
+
+    /// Returns the largest end of a chunk starting at `start` that is estimated to fit in the
+    /// budget.
+    fn find_end(
+        &self,
+        trace: &[TraceEntry],
+        entry_bytes: &[usize],
+        start: usize,
+        budget: SplitBudget,
+    ) -> Result<usize> {
+        let n = trace.len();
+
+        let mut bytes = BYTES_PER_CHUNK
+            + BYTES_PER_CARRIED_ELEMENT * self.live_vars[start]
+            + self.live_const_bytes[start];
+        let mut present =
+            (self.live_vars[start] + self.live_consts[start] + STACK_ITEMS_PER_CHUNK) as isize;
+        let mut num_hints = 0isize;
+
+        // the hints of the chunk all sit on the stack from the beginning, so the number of stack
+        // elements when running the entry `t` is `present + inputs + (total hints - hints so far)`,
+        // and we track the maximum of `present + inputs - hints so far`.
+        let mut max_items = isize::MIN;
+
+        let mut best = None;
+        for t in start..n {
+            bytes += entry_bytes[t];
+
+            let num_inputs = match &trace[t] {
+                TraceEntry::InsertScript(_, inputs, _) => inputs.len() as isize,
+                _ => 0,
+            };
+            max_items = max_items.max(present + num_inputs - num_hints);
+
+            match &trace[t] {
+                TraceEntry::DeclareConstant(_) | TraceEntry::DeclareOutput(_) => present += 1,
+                TraceEntry::RequestHint(_) => {
+                    present += 1;
+                    num_hints += 1;
+                }
+                _ => {}
+            }
+            present -= self.removed[t] as isize;
+            max_items = max_items.max(present - num_hints);
+
+            if bytes > budget.max_script_size
+                || max_items + num_hints > budget.max_stack_items as isize
+            {
+                break;
+            }
+
+            let e = t + 1;
+            if e < n && matches!(trace[e], TraceEntry::DeclareOutput(_)) {
+                // a function output cannot be separated from the script that produces it
+                continue;
+            }
+
+            let carried_out = if e < n { self.live_vars[e] } else { 0 };
+            if bytes + BYTES_PER_CARRIED_ELEMENT * carried_out <= budget.max_script_size {
+                best = Some(e);
+            }
+        }
+
+        best.ok_or(Error::msg(format!(
+            "The trace entry {} does not fit in the budget",
+            start
+        )))
+    }
+}
+
+fn build_chunk(
+    sys: &BitcoinSystem,
+    liveness: &Liveness,
+    ldm: &mut LDM,
+    name: &str,
+    boundary: Option<&PartBoundary>,
+    chunk_idx: usize,
+    range: Range<usize>,
+) -> Result<SplitChunk> {
+    let (start, end) = (range.start, range.end);
+    let cs = BitcoinSystemRef::new_ref();
+    let mut map = HashMap::<usize, usize>::new();
+
+    let lookup = |map: &HashMap<usize, usize>, idx: usize| -> Result<usize> {
+        map.get(&idx).copied().ok_or(Error::msg(format!(
+            "The memory entry {} is not available in the chunk",
+            idx
+        )))
+    };
+
+    if start == 0 && boundary.is_none() {
+        for i in 0..liveness.num_inputs {
+            let element = sys.memory.get(&i).unwrap().clone();
+            map.insert(i, cs.alloc(element, AllocationMode::ProgramInput)?);
+        }
+    }
+
+    ldm.init(&cs)?;
+
+    if let Some(boundary) = boundary.filter(|_| start == 0) {
+        let inputs = (boundary.read_inputs)(ldm)?;
+        if inputs.len() != liveness.num_inputs {
+            return Err(Error::msg(format!(
+                "The part reads {} elements for its {} program inputs",
+                inputs.len(),
+                liveness.num_inputs
+            )));
+        }
+        for (i, variable) in inputs.into_iter().enumerate() {
+            map.insert(i, variable);
+        }
+    }
+
+    let (carried_in, constants) = liveness.crossing(start);
+    if start != 0 && !carried_in.is_empty() {
+        let bar = ldm.read_key(&ElementsBar::key(name, chunk_idx))?;
+        for (&i, &variable) in carried_in.iter().zip(bar.variables.iter()) {
+            map.insert(i, variable);
+        }
+    }
+
+    for i in constants {
+        let element = sys.memory.get(&i).unwrap().clone();
+        map.insert(i, cs.alloc(element, AllocationMode::Constant)?);
+    }
+
+    for (t, trace_entry) in sys.trace.iter().enumerate().take(end).skip(start) {
+        match trace_entry {
+            TraceEntry::InsertScript(script_generator, inputs, options) => {
+                let inputs = inputs
+                    .iter()
+                    .map(|&i| lookup(&map, i))
+                    .collect::<Result<Vec<_>>>()?;
+                for entry in options.map.values() {
+                    if let OptionsEntry::StackRef(idx, len) = entry {
+                        let first = lookup(&map, *idx)?;
+                        for k in 1..*len {
+                            if lookup(&map, idx + k)? != first + k {
+                                return Err(Error::msg(
+                                    "A block referenced through the stack is no longer consecutive",
+                                ));
+                            }
+                        }
+                    }
+                }
+                let options = options.map_stack_refs(|i| lookup(&map, i))?;
+                cs.0.borrow_mut().insert_script_generator(
+                    script_generator.clone(),
+                    inputs,
+                    &options,
+                )?;
+            }
+            TraceEntry::DeclareConstant(i) => {
+                let element = sys.memory.get(i).unwrap().clone();
+                map.insert(*i, cs.alloc(element, AllocationMode::Constant)?);
+            }
+            TraceEntry::DeclareOutput(i) => {
+                let element = sys.memory.get(i).unwrap().clone();
+                map.insert(*i, cs.alloc(element, AllocationMode::FunctionOutput)?);
+            }
+            TraceEntry::RequestHint(i) => {
+                let element = sys.memory.get(i).unwrap().clone();
+                map.insert(*i, cs.alloc(element, AllocationMode::Hint)?);
+            }
+            TraceEntry::SystemOutput(_) => continue,
+            TraceEntry::BeginIf(_) | TraceEntry::Else(_) | TraceEntry::EndIf(_) => {
+                unreachable!("programs with conditional branches are not split")
+            }
+        }
+
+        // keep the origin of the replayed entry rather than pointing at the splitter
+        if let Some(meta) = sys.trace_meta.get(t) {
+            if let Some(last) = cs.0.borrow_mut().trace_meta.last_mut() {
+                *last = meta.clone();
+            }
+        }
+    }
+
+    if end < sys.trace.len() {
+        let (carried_out, _) = liveness.crossing(end);
+        if !carried_out.is_empty() {
+            let bar = ElementsBar {
+                variables: carried_out
+                    .iter()
+                    .map(|&i| lookup(&map, i))
+                    .collect::<Result<Vec<_>>>()?,
+                cs: cs.clone(),
+            };
+            ldm.write_key(&ElementsBar::key(name, chunk_idx + 1), &bar)?;
+        }
+    } else {
+        let mut outputs = vec![];
+        for trace_entry in sys.trace.iter() {
+            if let TraceEntry::SystemOutput(i) = trace_entry {
+                outputs.push(lookup(&map, *i)?);
+            }
+        }
+        if let Some(boundary) = boundary {
+            (boundary.write_outputs)(ldm, &cs, outputs)?;
+        } else if !outputs.is_empty() {
+            cs.set_program_output(&ElementsBar {
+                variables: outputs,
+                cs: cs.clone(),
+            })?;
+        }
+    }
+
+    ldm.save()?;
+
+    for (&old, &new) in map.iter() {
+        if let Some(label) = sys.labels.get(&old) {
+            cs.0.borrow_mut().labels.insert(new, label.clone());
+        }
+    }
+
+    Ok(SplitChunk {
+        cs,
+        ldm_hash: ldm.hash_var.as_ref().unwrap().value.clone(),
+        ldm: ldm.clone(),
+    })
+}
+
+/// A list of arbitrary elements, used to carry the live elements through the LDM.
+#[derive(Clone)]
+struct ElementsBar {
+    variables: Vec<usize>,
+    cs: BitcoinSystemRef,
+}
+
+impl ElementsBar {
+    /// The entry that carries the live elements into the chunk with this index.
+    fn key(name: &str, chunk_idx: usize) -> LDMKey<ElementsBar> {
+        LDMKey::named(name, "elements").indexed(chunk_idx)
+    }
+}
+
+impl Bar for ElementsBar {
+    fn cs(&self) -> BitcoinSystemRef {
+        self.cs.clone()
+    }
+
+    fn variables(&self) -> Vec<usize> {
+        self.variables.clone()
+    }
+}
+
+impl AllocBar for ElementsBar {
+    type Value = Vec<Element>;
+
+    fn value(&self) -> Result<Self::Value> {
+        self.variables
+            .iter()
+            .map(|&v| self.cs.get_element(v))
+            .collect()
+    }
+
+    fn new_variable(
+        cs: &BitcoinSystemRef,
+        data: Self::Value,
+        mode: AllocationMode,
+    ) -> Result<Self> {
+        let mut variables = vec![];
+        for element in data {
+            variables.push(cs.alloc(element, mode)?);
+        }
+        Ok(Self {
+            variables,
+            cs: cs.clone(),
+        })
+    }
+}
+
+fn constant_bytes(element: &Element) -> usize {
+    match element {
+        Element::Num(_) => 5,
+        Element::Str(v) => v.len() + 3,
+    }
+}
+
+fn prefix_sum(diff: &[isize], len: usize) -> Vec<usize> {
+    let mut res = Vec::with_capacity(len);
+    let mut cur = 0isize;
+    for &d in diff.iter().take(len) {
+        cur += d;
+        res.push(cur as usize);
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bar::AllocBar;
+    use crate::basic::i32::I32Bar;
+    use crate::bitcoin_system::BitcoinSystemRef;
+    use crate::compiler::Compiler;
+    use crate::ldm::{LDMKey, LDM};
+    use crate::splitter::{split_part, split_program, SplitBudget, SplitChunk};
+    use crate::test_program;
+    use crate::treepp::*;
+
+    #[test]
+    fn test_split_program() {
+        let cs = BitcoinSystemRef::new_ref();
+
+        let mut expected = 1;
+        let mut acc = I32Bar::new_program_input(&cs, expected).unwrap();
+        for i in 0..64 {
+            let a = I32Bar::new_hint(&cs, i).unwrap();
+            let b = I32Bar::new_constant(&cs, 2 * i + 1).unwrap();
+            acc = &(&acc + &a) - &b;
+            expected = expected + i - (2 * i + 1);
+        }
+        cs.set_program_output(&acc).unwrap();
+
+        let mut ldm = LDM::new();
+        let chunks = split_program(
+            &cs,
+            &mut ldm,
+            "split",
+            SplitBudget {
+                max_script_size: 200,
+                max_stack_items: 1000,
+            },
+        )
+        .unwrap();
+        assert!(chunks.len() > 1);
+
+        let num_chunks = chunks.len();
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let program = Compiler::compile(chunk.cs.clone()).unwrap();
+            assert!(program.script.len() <= 200);
+            assert!(program.max_stack_items <= 1000);
+
+            if i == num_chunks - 1 {
+                test_program(
+                    chunk.cs,
+                    script! {
+                        { expected }
+                        { chunk.ldm_hash }
+                    },
+                )
+                .unwrap();
+            } else {
+                test_program(
+                    chunk.cs,
+                    script! {
+                        { chunk.ldm_hash }
+                    },
+                )
+                .unwrap();
+            }
+        }
+
+        let cs = BitcoinSystemRef::new_ref();
+        ldm.init(&cs).unwrap();
+        ldm.check().unwrap();
+        ldm.save().unwrap();
+
+        test_program(
+            cs,
+            script! {
+                { ldm.hash_var.as_ref().unwrap().value.clone() }
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_split_part() {
+        const INPUT: LDMKey<I32Bar> = LDMKey::new("acc_in", "i32");
+        const OUTPUT: LDMKey<I32Bar> = LDMKey::new("acc_out", "i32");
+
+        // the part before writes the input
+        let mut ldm = LDM::new();
+        let cs = BitcoinSystemRef::new_ref();
+        ldm.init(&cs).unwrap();
+        ldm.write_key(&INPUT, &I32Bar::new_constant(&cs, 1).unwrap())
+            .unwrap();
+        ldm.save().unwrap();
+        test_program(
+            cs,
+            script! {
+                { ldm.hash_var.as_ref().unwrap().value.clone() }
+            },
+        )
+        .unwrap();
+
+        let mut expected = 1;
+        for i in 0..64 {
+            expected = expected + i - (2 * i + 1);
+        }
+
+        let split = |ldm: &mut LDM, first_chunk: usize| -> Vec<SplitChunk> {
+            let cs = BitcoinSystemRef::new_ref();
+            let input = ldm.debug_read(&INPUT).unwrap();
+            let mut acc = I32Bar::new_program_input(&cs, input).unwrap();
+            for i in 0..64 {
+                let a = I32Bar::new_hint(&cs, i).unwrap();
+                let b = I32Bar::new_constant(&cs, 2 * i + 1).unwrap();
+                acc = &(&acc + &a) - &b;
+            }
+            cs.set_program_output(&acc).unwrap();
+
+            let budget = SplitBudget {
+                max_script_size: 200,
+                max_stack_items: 1000,
+            };
+            split_part(
+                &cs,
+                &acc,
+                ldm,
+                (&INPUT, &OUTPUT),
+                "split",
+                budget,
+                first_chunk,
+            )
+            .unwrap()
+        };
+
+        let chunks = split(&mut ldm, 0);
+        assert!(chunks.len() > 1);
+        for chunk in chunks.iter() {
+            test_program(
+                chunk.cs.clone(),
+                script! {
+                    { chunk.ldm_hash.clone() }
+                },
+            )
+            .unwrap();
+        }
+
+        // resuming after the first chunk gives the same chunks
+        let rest = split(&mut chunks[0].ldm.clone(), 1);
+        assert_eq!(rest.len(), chunks.len() - 1);
+        for (chunk, expected) in rest.iter().zip(chunks[1..].iter()) {
+            assert_eq!(chunk.ldm_hash, expected.ldm_hash);
+        }
+
+        // the part after reads the output
+        let cs = BitcoinSystemRef::new_ref();
+        ldm.init(&cs).unwrap();
+        let acc = ldm.read_key(&OUTPUT).unwrap();
+        assert_eq!(acc.value, expected);
+        ldm.check().unwrap();
+        ldm.save().unwrap();
+        test_program(
+            cs,
+            script! {
+                { ldm.hash_var.as_ref().unwrap().value.clone() }
+            },
+        )
+        .unwrap();
+    }
+}
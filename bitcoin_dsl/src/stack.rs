@@ -1,4 +1,5 @@
-use anyhow::{Error, Result};
+use crate::error::DslError;
+use anyhow::Result;
 use fenwick_tree::FenwickTree;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -6,32 +7,52 @@ pub enum StackElementStatus {
     ABSENT,
     PRESENT,
     PULLED,
+    /// The element has been moved to the altstack and can be brought back.
+    PARKED,
 }
 
+/// A model of the stack and the altstack, which tracks where the memory entries are.
+///
+/// The elements of the main stack are kept in the order in which they have been pushed, which is
+/// the order of their indices unless some of them have been parked on the altstack and brought
+/// back.
 pub struct Stack {
     pub bitmap: Vec<StackElementStatus>,
+    /// The number of elements present at each slot of the main stack.
     pub fenwick_tree: FenwickTree<isize>,
+    /// The number of memory entries.
     pub size: usize,
+    /// The slot of each memory entry present on the main stack.
+    pub slot: Vec<usize>,
+    pub next_slot: usize,
+    pub capacity: usize,
+    /// The memory entries parked on the altstack, from the bottom to the top.
+    pub altstack: Vec<usize>,
 }
 
 impl Stack {
     pub fn new(size: usize) -> Self {
+        let capacity = size.max(1) * 2;
         Self {
             bitmap: vec![StackElementStatus::ABSENT; size],
-            fenwick_tree: FenwickTree::with_len(size),
+            fenwick_tree: FenwickTree::with_len(capacity),
             size,
+            slot: vec![0; size],
+            next_slot: 0,
+            capacity,
+            altstack: vec![],
         }
     }
 
     pub fn push_to_stack(&mut self, idx: usize) -> Result<()> {
         if self.bitmap[idx] != StackElementStatus::ABSENT {
-            return Err(Error::msg(
+            return Err(DslError::StackCorrupted(
                 "The stack seems to already have these elements.",
-            ));
+            )
+            .into());
         }
         self.bitmap[idx] = StackElementStatus::PRESENT;
-        self.fenwick_tree.add(idx, 1)?;
-        Ok(())
+        self.place_on_top(idx)
     }
 
     pub fn is_present(&self, idx: usize) -> Result<bool> {
@@ -42,25 +63,90 @@ impl Stack {
         match self.bitmap[idx] {
             StackElementStatus::PRESENT => {
                 self.bitmap[idx] = StackElementStatus::PULLED;
-                self.fenwick_tree.add(idx, -1)?;
+                self.fenwick_tree.add(self.slot[idx], -1)?;
 
                 Ok(())
             }
-            _ => Err(Error::msg(
+            _ => Err(DslError::StackCorrupted(
                 "Only elements present in the stack can be pulled aside.",
-            )),
+            )
+            .into()),
         }
     }
 
+    /// Moves an element of the main stack to the top of the altstack, which the script does by
+    /// rolling it to the top and then `OP_TOALTSTACK`.
+    pub fn park(&mut self, idx: usize) -> Result<()> {
+        match self.bitmap[idx] {
+            StackElementStatus::PRESENT => {
+                self.bitmap[idx] = StackElementStatus::PARKED;
+                self.fenwick_tree.add(self.slot[idx], -1)?;
+                self.altstack.push(idx);
+
+                Ok(())
+            }
+            _ => Err(DslError::StackCorrupted(
+                "Only elements present in the stack can be parked on the altstack.",
+            )
+            .into()),
+        }
+    }
+
+    /// Moves the element on the top of the altstack back to the top of the main stack, which the
+    /// script does by `OP_FROMALTSTACK`, and returns it.
+    pub fn unpark(&mut self) -> Result<usize> {
+        let idx = self
+            .altstack
+            .pop()
+            .ok_or(DslError::StackCorrupted("The altstack is empty."))?;
+        self.bitmap[idx] = StackElementStatus::PRESENT;
+        self.place_on_top(idx)?;
+        Ok(idx)
+    }
+
     pub fn get_relative_position(&mut self, idx: usize) -> Result<usize> {
         if !matches!(self.bitmap[idx], StackElementStatus::PRESENT) {
-            return Err(Error::msg("Only elements in the stack can have the relative position to the top of the stack."));
+            return Err(DslError::StackCorrupted(
+                "Only elements in the stack can have the relative position to the top of the stack.",
+            )
+            .into());
         }
-        let sum = self.fenwick_tree.sum(idx..self.size)?;
+        let sum = self.fenwick_tree.sum(self.slot[idx]..self.capacity)?;
         Ok((sum - 1) as usize)
     }
 
     pub fn get_num_elements_in_stack(&self) -> Result<usize> {
-        Ok(self.fenwick_tree.sum(0..self.size)? as usize)
+        Ok(self.fenwick_tree.sum(0..self.capacity)? as usize)
+    }
+
+    pub fn get_num_elements_in_altstack(&self) -> usize {
+        self.altstack.len()
+    }
+
+    /// Returns the memory entries on the main stack, from the bottom to the top.
+    pub fn main_stack(&self) -> Vec<usize> {
+        let mut res = (0..self.size)
+            .filter(|&idx| self.bitmap[idx] == StackElementStatus::PRESENT)
+            .collect::<Vec<_>>();
+        res.sort_unstable_by_key(|&idx| self.slot[idx]);
+        res
+    }
+
+    fn place_on_top(&mut self, idx: usize) -> Result<()> {
+        if self.next_slot == self.capacity {
+            // elements brought back from the altstack take new slots, so the tree may need to grow
+            self.capacity *= 2;
+            let mut fenwick_tree = FenwickTree::with_len(self.capacity);
+            for (other, status) in self.bitmap.iter().enumerate() {
+                if *status == StackElementStatus::PRESENT && other != idx {
+                    fenwick_tree.add(self.slot[other], 1)?;
+                }
+            }
+            self.fenwick_tree = fenwick_tree;
+        }
+        self.slot[idx] = self.next_slot;
+        self.next_slot += 1;
+        self.fenwick_tree.add(self.slot[idx], 1)?;
+        Ok(())
     }
 }
@@ -0,0 +1,181 @@
+use crate::bitcoin_system::{BitcoinSystem, Element};
+use crate::debugger::new_exec;
+use crate::error::DslError;
+use crate::treepp::*;
+use anyhow::{Error, Result};
+use bitcoin::opcodes::all::OP_NOP;
+use bitcoin_scriptexec::Options;
+
+/// Checks that the script of a gadget consumes exactly its inputs and leaves exactly its declared
+/// outputs, by running it on the values of the memory entries.
+///
+/// The executed script pushes the memory entries of `below`, which are the ones on the stack
+/// under the inputs, then the inputs, and then runs the gadget. Gadgets that locate memory
+/// entries through the stack need `below` to be the whole stack, while the other ones can be
+/// checked with an empty `below`.
+pub fn check_stack_effect(
+    cs: &BitcoinSystem,
+    trace_idx: usize,
+    below: &[usize],
+    inputs: &[usize],
+    outputs: &[usize],
+    gadget: &Script,
+) -> Result<()> {
+    let values = |idxs: &[usize], tail: &[usize]| {
+        idxs.iter()
+            .chain(tail.iter())
+            .map(|idx| {
+                cs.memory
+                    .get(idx)
+                    .ok_or_else(|| Error::from(DslError::UnknownVariable(*idx)))
+            })
+            .collect::<Result<Vec<&Element>>>()
+    };
+
+    let pushed_values = values(below, inputs)?;
+    let mut script = script! {
+        for elem in pushed_values.iter().copied() {
+            { elem }
+        }
+    }
+    .to_bytes();
+    script.extend_from_slice(gadget.as_bytes());
+    // a marker that the execution stops at, so that the stack can be inspected
+    script.push(OP_NOP.to_u8());
+
+    let mismatch =
+        |reason: String| -> Error { DslError::StackEffectMismatch { trace_idx, reason }.into() };
+
+    let mut exec = new_exec(Script::from_bytes(script), Options::default())?;
+    while exec.remaining_script().len() > 1 {
+        if exec.exec_next().is_err() {
+            break;
+        }
+    }
+    if let Some(res) = exec.result() {
+        return Err(mismatch(format!(
+            "the gadget fails on the values of its inputs: {:?}",
+            res.error
+        )));
+    }
+
+    let stack = exec.stack();
+    let expected_len = below.len() + outputs.len();
+    if stack.len() != expected_len {
+        return Err(mismatch(format!(
+            "the gadget leaves {} elements above the ones below its {} inputs, but declares {} outputs",
+            stack.len() as isize - below.len() as isize,
+            inputs.len(),
+            outputs.len()
+        )));
+    }
+
+    let expected_values = values(below, outputs)?;
+    let expected = convert_to_witness(script! {
+        for elem in expected_values.iter().copied() {
+            { elem }
+        }
+    })
+    .map_err(|x| DslError::InvalidWitness(format!("{:?}", x)))?;
+    for (i, expected) in expected.iter().enumerate() {
+        if stack.get(i) != *expected {
+            let reason = if i < below.len() {
+                format!("the gadget modifies {}", cs.describe(below[i]))
+            } else {
+                format!(
+                    "the output {} does not have the declared value {:?}",
+                    cs.describe(outputs[i - below.len()]),
+                    expected_values[i]
+                )
+            };
+            return Err(mismatch(reason));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bar::AllocBar;
+    use crate::basic::bool::BoolBar;
+    use crate::basic::i32::I32Bar;
+    use crate::bitcoin_system::BitcoinSystemRef;
+    use crate::compiler::{Compiler, CompilerConfig};
+    use crate::error::DslError;
+    use crate::treepp::*;
+
+    fn add() -> Script {
+        script! {
+            OP_ADD
+        }
+    }
+
+    fn add_and_keep_inputs() -> Script {
+        script! {
+            OP_2DUP OP_ADD
+        }
+    }
+
+    fn add_wrongly() -> Script {
+        script! {
+            OP_ADD OP_1ADD
+        }
+    }
+
+    fn compile_with(
+        script_generator: fn() -> Script,
+        config: &CompilerConfig,
+    ) -> anyhow::Result<()> {
+        let cs = BitcoinSystemRef::new_ref();
+        let a = I32Bar::new_hint(&cs, 3).unwrap();
+        let b = I32Bar::new_hint(&cs, 4).unwrap();
+        cs.insert_script(script_generator, [a.variable, b.variable])
+            .unwrap();
+        let c = I32Bar::new_function_output(&cs, 7).unwrap();
+        cs.set_program_output(&c).unwrap();
+
+        Compiler::compile_with_config(cs, config).map(|_| ())
+    }
+
+    #[test]
+    fn test_stack_effect() {
+        let config = CompilerConfig::new().with_stack_effect_check();
+        compile_with(add, &config).unwrap();
+
+        let err = compile_with(add_and_keep_inputs, &config).unwrap_err();
+        let Some(DslError::StackEffectMismatch { reason, .. }) = err.downcast_ref::<DslError>()
+        else {
+            panic!("unexpected error: {}", err);
+        };
+        assert!(reason.contains("leaves 3 elements"));
+
+        let err = compile_with(add_wrongly, &config).unwrap_err();
+        let Some(DslError::StackEffectMismatch { reason, .. }) = err.downcast_ref::<DslError>()
+        else {
+            panic!("unexpected error: {}", err);
+        };
+        assert!(reason.contains("declared value"));
+
+        // the check is off by default, and then the compiler trusts the declared outputs
+        compile_with(add_wrongly, &CompilerConfig::new()).unwrap();
+
+        // the gadgets inside branches are not checked
+        let cs = BitcoinSystemRef::new_ref();
+        let cond = BoolBar::new_hint(&cs, true).unwrap();
+        let a = I32Bar::new_hint(&cs, 3).unwrap();
+        let b = I32Bar::new_hint(&cs, 4).unwrap();
+        let c = cs
+            .if_else(
+                &cond,
+                || {
+                    cs.insert_script(add_wrongly, [a.variable, b.variable])?;
+                    I32Bar::new_function_output(&cs, 7)
+                },
+                || Ok(a.clone()),
+            )
+            .unwrap();
+        cs.set_program_output(&c).unwrap();
+        Compiler::compile_with_config(cs, &config).unwrap();
+    }
+}
@@ -0,0 +1,228 @@
+use crate::bitcoin_system::{describe_memory_entry, BitcoinSystemRef, Element, TraceEntry};
+use crate::compiler::Compiler;
+use crate::debugger::Debugger;
+use crate::error::DslError;
+use crate::treepp::*;
+use anyhow::Result;
+use std::fmt::{Display, Formatter};
+
+/// The modulus of the M31 field.
+const M31_MODULUS: i64 = (1 << 31) - 1;
+
+/// A change to a hint element that a sound program must reject.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HintMutation {
+    /// Flips the bit with this index, counting from the least significant bit of a number or of
+    /// the first byte of a string.
+    FlipBit(usize),
+    /// Adds one to a number.
+    AddOne,
+    /// Subtracts one from a number.
+    SubOne,
+    /// Replaces an M31 element with another one, which differs in many bits.
+    ReplaceM31,
+    /// Removes the last byte of a string.
+    Truncate,
+}
+
+impl HintMutation {
+    /// Returns the mutations that apply to the element.
+    pub fn candidates(elem: &Element) -> Vec<HintMutation> {
+        match elem {
+            Element::Num(_) => vec![
+                HintMutation::FlipBit(0),
+                HintMutation::FlipBit(30),
+                HintMutation::AddOne,
+                HintMutation::SubOne,
+                HintMutation::ReplaceM31,
+            ],
+            Element::Str(v) if v.is_empty() => vec![],
+            Element::Str(v) => vec![
+                HintMutation::FlipBit(0),
+                HintMutation::FlipBit(v.len() * 8 - 1),
+                HintMutation::Truncate,
+            ],
+        }
+    }
+
+    /// Applies the mutation to the element, or returns `None` if the mutation does not apply or
+    /// leaves the element unchanged.
+    pub fn apply(&self, elem: &Element) -> Option<Element> {
+        let res = match (self, elem) {
+            (HintMutation::FlipBit(bit), Element::Num(v)) if *bit < 32 => {
+                Element::Num(v ^ (1 << bit))
+            }
+            (HintMutation::FlipBit(bit), Element::Str(v)) if *bit < v.len() * 8 => {
+                let mut v = v.clone();
+                v[bit / 8] ^= 1 << (bit % 8);
+                Element::Str(v)
+            }
+            (HintMutation::AddOne, Element::Num(v)) => Element::Num(v.checked_add(1)?),
+            (HintMutation::SubOne, Element::Num(v)) => Element::Num(v.checked_sub(1)?),
+            (HintMutation::ReplaceM31, Element::Num(v))
+                if (0..M31_MODULUS).contains(&(*v as i64)) =>
+            {
+                Element::Num(((*v as i64 + 0x2aaa_aaaa) % M31_MODULUS) as i32)
+            }
+            (HintMutation::Truncate, Element::Str(v)) if !v.is_empty() => {
+                Element::Str(v[..v.len() - 1].to_vec())
+            }
+            _ => return None,
+        };
+        if &res == elem {
+            None
+        } else {
+            Some(res)
+        }
+    }
+}
+
+impl Display for HintMutation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HintMutation::FlipBit(bit) => write!(f, "flipping bit {}", bit),
+            HintMutation::AddOne => write!(f, "adding one"),
+            HintMutation::SubOne => write!(f, "subtracting one"),
+            HintMutation::ReplaceM31 => write!(f, "replacing with another M31 element"),
+            HintMutation::Truncate => write!(f, "truncating"),
+        }
+    }
+}
+
+/// A mutated hint with which the program still succeeds.
+#[derive(Clone, Debug)]
+pub struct SurvivingMutation {
+    /// The position of the hint in the order in which the hints are requested.
+    pub hint_idx: usize,
+    /// The memory entry of the hint, together with its label if it has one.
+    pub variable: String,
+    pub mutation: HintMutation,
+    pub original: Element,
+    pub mutated: Element,
+}
+
+impl Display for SurvivingMutation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "hint {} ({}) is accepted after {}: {:?} -> {:?}",
+            self.hint_idx, self.variable, self.mutation, self.original, self.mutated
+        )
+    }
+}
+
+/// The outcome of tampering with the hints of a program.
+#[derive(Clone, Debug, Default)]
+pub struct TamperReport {
+    pub num_hints: usize,
+    /// The number of mutated programs that have been executed.
+    pub num_mutations: usize,
+    /// The mutations that the program does not reject, which point to hints that are not fully
+    /// constrained.
+    pub survivors: Vec<SurvivingMutation>,
+}
+
+impl TamperReport {
+    pub fn is_sound(&self) -> bool {
+        self.survivors.is_empty()
+    }
+}
+
+/// Compiles the program, checks that it succeeds with the expected stack, and then re-executes
+/// it with each hint element mutated in turn, recording every mutation that still succeeds.
+pub fn tamper_hints(cs: BitcoinSystemRef, expected_stack: Script) -> Result<TamperReport> {
+    let mut program = Compiler::compile(cs)?;
+
+    if !Debugger::new(&program, expected_stack.clone(), true)?.run() {
+        return Err(DslError::ScriptFailed(
+            "the program fails before any hint is tampered with".to_string(),
+        )
+        .into());
+    }
+
+    let hint_vars = program
+        .trace
+        .iter()
+        .filter_map(|trace_entry| match trace_entry {
+            TraceEntry::RequestHint(idx) => Some(*idx),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut report = TamperReport {
+        num_hints: program.hint.len(),
+        ..Default::default()
+    };
+
+    for hint_idx in 0..program.hint.len() {
+        let original = program.hint[hint_idx].clone();
+        for mutation in HintMutation::candidates(&original) {
+            let Some(mutated) = mutation.apply(&original) else {
+                continue;
+            };
+
+            program.hint[hint_idx] = mutated.clone();
+            let success = Debugger::new(&program, expected_stack.clone(), true)?.run();
+            program.hint[hint_idx] = original.clone();
+
+            report.num_mutations += 1;
+            if success {
+                let variable = hint_vars
+                    .get(hint_idx)
+                    .map(|&idx| describe_memory_entry(&program.labels, idx))
+                    .unwrap_or_default();
+                report.survivors.push(SurvivingMutation {
+                    hint_idx,
+                    variable,
+                    mutation,
+                    original: original.clone(),
+                    mutated,
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bar::AllocBar;
+    use crate::basic::i32::I32Bar;
+    use crate::bitcoin_system::{BitcoinSystemRef, Element};
+    use crate::tamper::{tamper_hints, HintMutation};
+    use crate::treepp::*;
+
+    #[test]
+    fn test_tamper_hints() {
+        // the sum is checked against a constant, so both hints are constrained
+        let cs = BitcoinSystemRef::new_ref();
+        let a = I32Bar::new_hint(&cs, 3).unwrap();
+        let b = I32Bar::new_hint(&cs, 4).unwrap();
+        let c = I32Bar::new_constant(&cs, 7).unwrap();
+        (&a + &b).equalverify(&c).unwrap();
+
+        let report = tamper_hints(cs, script! {}).unwrap();
+        assert_eq!(report.num_hints, 2);
+        assert_eq!(report.num_mutations, 10);
+        assert!(report.is_sound());
+
+        // the second hint is never used
+        let cs = BitcoinSystemRef::new_ref();
+        let a = I32Bar::new_hint(&cs, 3).unwrap();
+        let _unused = I32Bar::new_hint_named(&cs, 4, "unused").unwrap();
+        let c = I32Bar::new_constant(&cs, 3).unwrap();
+        a.equalverify(&c).unwrap();
+
+        let report = tamper_hints(cs, script! {}).unwrap();
+        assert!(!report.is_sound());
+        assert!(report.survivors.iter().all(|s| s.hint_idx == 1));
+        assert!(report.survivors[0].to_string().contains("`unused`"));
+
+        assert_eq!(
+            HintMutation::Truncate.apply(&Element::Str(vec![1, 2])),
+            Some(Element::Str(vec![1]))
+        );
+        assert_eq!(HintMutation::AddOne.apply(&Element::Num(i32::MAX)), None);
+    }
+}
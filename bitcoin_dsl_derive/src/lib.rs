@@ -0,0 +1,280 @@
+//! Derive macros for the `Bar`, `FixedLengthBar` and `AllocBar` traits of
+//! `recursive-stwo-bitcoin-dsl`.
+//!
+//! The macros apply to structs with named fields, where each field is a variable that
+//! implements the traits itself. A field marked with `#[bar(skip)]` is plain data that is not on
+//! the stack: it is copied to and from the value, but does not contribute to `cs()`,
+//! `variables()`, or `length()`. `#[derive(FixedLengthBar)]` therefore fails on a struct with a
+//! vector field, whose length is only known once it is allocated. An empty vector or map field
+//! has no constraint system, and is skipped by `cs()` as long as another field is not empty.
+//!
+//! `#[derive(AllocBar)]` also declares the value type of the struct, with the same fields, named
+//! after the struct with the `Bar` suffix replaced by `Value`. An existing type with the same
+//! fields can be used instead with `#[bar(value = Type)]`.
+//!
+//! The generated code only names `recursive-stwo-bitcoin-dsl`, which re-exports `serde` and
+//! `anyhow` for it, so that the crates using the macros do not need to depend on them.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Field, Fields, Ident, Path, Result};
+
+#[proc_macro_derive(Bar, attributes(bar))]
+pub fn derive_bar(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_bar(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(FixedLengthBar, attributes(bar))]
+pub fn derive_fixed_length_bar(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_fixed_length_bar(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(AllocBar, attributes(bar))]
+pub fn derive_alloc_bar(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_alloc_bar(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct BarField<'a> {
+    field: &'a Field,
+    ident: &'a Ident,
+    skip: bool,
+}
+
+fn parse_fields(input: &DeriveInput) -> Result<Vec<BarField<'_>>> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "The Bar traits can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "The Bar traits can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut res = vec![];
+    for field in fields.iter() {
+        let mut skip = false;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("bar"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown field attribute, expected `skip`"))
+                }
+            })?;
+        }
+        res.push(BarField {
+            field,
+            ident: field.ident.as_ref().unwrap(),
+            skip,
+        });
+    }
+
+    if res.iter().all(|field| field.skip) {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "at least one field must be a variable",
+        ));
+    }
+    Ok(res)
+}
+
+/// Returns the path given by `#[bar(value = Type)]` on the struct, if any.
+fn parse_value_type(input: &DeriveInput) -> Result<Option<Path>> {
+    let mut value = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("bar"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("value") {
+                value = Some(meta.value()?.parse::<Path>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown struct attribute, expected `value`"))
+            }
+        })?;
+    }
+    Ok(value)
+}
+
+fn expand_bar(input: &DeriveInput) -> Result<TokenStream2> {
+    let fields = parse_fields(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let vars = fields
+        .iter()
+        .filter(|field| !field.skip)
+        .collect::<Vec<_>>();
+    let idents = vars.iter().map(|field| field.ident).collect::<Vec<_>>();
+
+    Ok(quote! {
+        impl #impl_generics ::recursive_stwo_bitcoin_dsl::bar::Bar for #name #ty_generics #where_clause {
+            fn cs(&self) -> ::recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef {
+                ::recursive_stwo_bitcoin_dsl::bar::Bar::try_cs(self).unwrap()
+            }
+
+            fn try_cs(
+                &self,
+            ) -> ::recursive_stwo_bitcoin_dsl::__private::anyhow::Result<
+                ::recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef,
+            > {
+                ::recursive_stwo_bitcoin_dsl::bar::try_cs_of_fields([
+                    #(::recursive_stwo_bitcoin_dsl::bar::Bar::try_cs(&self.#idents),)*
+                ])
+            }
+
+            fn variables(&self) -> Vec<usize> {
+                let mut v = Vec::new();
+                #(v.extend(::recursive_stwo_bitcoin_dsl::bar::Bar::variables(&self.#idents));)*
+                v
+            }
+        }
+    })
+}
+
+fn expand_fixed_length_bar(input: &DeriveInput) -> Result<TokenStream2> {
+    let fields = parse_fields(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let types = fields
+        .iter()
+        .filter(|field| !field.skip)
+        .map(|field| &field.field.ty);
+
+    Ok(quote! {
+        impl #impl_generics ::recursive_stwo_bitcoin_dsl::bar::FixedLengthBar for #name #ty_generics #where_clause {
+            fn length() -> usize {
+                0 #(+ <#types as ::recursive_stwo_bitcoin_dsl::bar::FixedLengthBar>::length())*
+            }
+        }
+    })
+}
+
+fn expand_alloc_bar(input: &DeriveInput) -> Result<TokenStream2> {
+    let fields = parse_fields(input)?;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let value_type = parse_value_type(input)?;
+    let value_decl = if value_type.is_none() {
+        if !input.generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                &input.generics,
+                "the value type of a generic struct must be given with `#[bar(value = Type)]`",
+            ));
+        }
+
+        let value_fields = fields.iter().map(|field| {
+            let field_vis = &field.field.vis;
+            let ident = field.ident;
+            let ty = &field.field.ty;
+            if field.skip {
+                quote! { #field_vis #ident: #ty }
+            } else {
+                quote! { #field_vis #ident: <#ty as ::recursive_stwo_bitcoin_dsl::bar::AllocBar>::Value }
+            }
+        });
+        let value_name = value_name(name);
+        let doc = format!("The value of [`{}`].", name);
+        quote! {
+            #[doc = #doc]
+            #[derive(
+                Clone,
+                ::recursive_stwo_bitcoin_dsl::__private::serde::Serialize,
+                ::recursive_stwo_bitcoin_dsl::__private::serde::Deserialize
+            )]
+            #[serde(crate = "::recursive_stwo_bitcoin_dsl::__private::serde")]
+            #vis struct #value_name {
+                #(#value_fields,)*
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let value_type = match value_type {
+        Some(path) => quote! { #path },
+        None => {
+            let value_name = value_name(name);
+            quote! { #value_name }
+        }
+    };
+
+    let values = fields.iter().map(|field| {
+        let ident = field.ident;
+        if field.skip {
+            quote! { #ident: ::std::clone::Clone::clone(&self.#ident) }
+        } else {
+            quote! { #ident: ::recursive_stwo_bitcoin_dsl::bar::AllocBar::value(&self.#ident)? }
+        }
+    });
+    let allocs = fields.iter().map(|field| {
+        let ident = field.ident;
+        let ty = &field.field.ty;
+        if field.skip {
+            quote! { let #ident = data.#ident; }
+        } else {
+            quote! {
+                let #ident = <#ty as ::recursive_stwo_bitcoin_dsl::bar::AllocBar>::new_variable(cs, data.#ident, mode)?;
+            }
+        }
+    });
+    let idents = fields.iter().map(|field| field.ident);
+
+    Ok(quote! {
+        #value_decl
+
+        impl #impl_generics ::recursive_stwo_bitcoin_dsl::bar::AllocBar for #name #ty_generics #where_clause {
+            type Value = #value_type;
+
+            fn value(&self) -> ::recursive_stwo_bitcoin_dsl::__private::anyhow::Result<Self::Value> {
+                Ok(#value_type {
+                    #(#values,)*
+                })
+            }
+
+            fn new_variable(
+                cs: &::recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef,
+                data: Self::Value,
+                mode: ::recursive_stwo_bitcoin_dsl::bar::AllocationMode,
+            ) -> ::recursive_stwo_bitcoin_dsl::__private::anyhow::Result<Self> {
+                #(#allocs)*
+                Ok(Self {
+                    #(#idents,)*
+                })
+            }
+        }
+    })
+}
+
+fn value_name(name: &Ident) -> Ident {
+    let name = name.to_string();
+    format_ident!("{}Value", name.strip_suffix("Bar").unwrap_or(&name))
+}
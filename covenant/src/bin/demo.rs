@@ -7,8 +7,8 @@ use colored::Colorize;
 use covenants_gadgets::test::SimulationInstruction;
 use covenants_gadgets::{get_script_pub_key, get_tx, CovenantInput, CovenantProgram, DUST_AMOUNT};
 use recursive_stwo_covenant::{
-    compute_all_information, RecursiveStwoVerifierProgram, RecursiveStwoVerifierState,
-    RECURSIVE_STWO_ALL_INFORMATION,
+    load_or_compute_all_information, recursive_stwo_all_information, RecursiveStwoVerifierProgram,
+    RecursiveStwoVerifierState,
 };
 use std::io::Write;
 use std::path::PathBuf;
@@ -28,6 +28,10 @@ struct Args {
     randomizer: u32,
     #[arg(long, default_value = "0")]
     funding_tx_vout: u32,
+
+    /// Artifact with the prebuilt scripts, hints, and outputs, created if it does not exist
+    #[arg(long)]
+    artifact: Option<PathBuf>,
 }
 
 fn get_output_dir() -> PathBuf {
@@ -79,6 +83,10 @@ fn print_transaction_info(tx: &bitcoin::Transaction, _step: usize) {
 fn main() {
     let args = Args::parse();
 
+    if let Some(path) = &args.artifact {
+        load_or_compute_all_information(path).unwrap();
+    }
+
     let amount = (1843436 + 10000) * FEE_RATE + 330 * 175 + 400 * FEE_RATE;
     let amount_display = (((amount as f64) / 1000.0 / 1000.0 / 100.0) * 10000.0).ceil() / 10000.0;
     let actual_amount = (amount_display * 100.0 * 1000.0 * 1000.0) as u64;
@@ -167,7 +175,7 @@ fn main() {
 
         let mut txs = Vec::new();
 
-        let all_information = RECURSIVE_STWO_ALL_INFORMATION.get_or_init(compute_all_information);
+        let all_information = recursive_stwo_all_information().unwrap();
         let get_instruction = |old_state: &RecursiveStwoVerifierState| {
             Some(SimulationInstruction::<RecursiveStwoVerifierProgram> {
                 program_index: old_state.pc,
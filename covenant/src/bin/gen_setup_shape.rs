@@ -0,0 +1,20 @@
+use clap::Parser;
+use recursive_stwo_bitcoin_dsl::artifact::Artifact;
+use recursive_stwo_covenant::{RecursiveStwoProofs, RecursiveStwoShape, REFERENCE_SHAPE_PATH};
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[arg(short, long, default_value = REFERENCE_SHAPE_PATH)]
+    output: String,
+}
+
+// Writes the shape of the reference proofs, from which the setup is computed.
+fn main() {
+    let args = Args::parse();
+
+    let shape = RecursiveStwoShape::of(&RecursiveStwoProofs::reference()).unwrap();
+    shape.save(&args.output).unwrap();
+
+    println!("{:#?}", shape);
+}
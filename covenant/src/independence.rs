@@ -0,0 +1,151 @@
+use crate::{compile_all_parts, pipeline_compiler_config, RecursiveStwoProofs, Witness};
+use anyhow::Result;
+use recursive_stwo_bitcoin_dsl::bitcoin_system::TraceEntry;
+use recursive_stwo_bitcoin_dsl::compiler::CompiledProgram;
+
+/// A byte-level difference between two scripts that are expected to be identical.
+#[derive(Clone, Debug)]
+pub struct ScriptDifference {
+    /// The index of the script in the pipeline.
+    pub script_idx: usize,
+    /// The offset in the first script where the difference starts.
+    pub offset: usize,
+    /// The index of the trace entry that generates the differing bytes, or `None` if they belong
+    /// to the final cleanup of the stack or to a script that only one of the proofs leads to.
+    pub trace_idx: Option<usize>,
+    /// The trace entry (in the first program) that generates the differing bytes.
+    pub trace_entry: Option<TraceEntry>,
+    /// The differing bytes in the first script.
+    pub lhs: Vec<u8>,
+    /// The differing bytes in the second script.
+    pub rhs: Vec<u8>,
+}
+
+/// Compiles the whole pipeline for two different proofs of the same shape and reports all the
+/// places where the scripts differ. An empty result means that the scripts do not depend on the
+/// specific proof, and therefore that the covenant address is the same for both.
+///
+/// If one proof leads to more scripts than the other, each extra script is reported as a
+/// difference with the missing script taken as empty.
+pub fn check_proof_independence(
+    lhs: &RecursiveStwoProofs,
+    rhs: &RecursiveStwoProofs,
+) -> Result<Vec<ScriptDifference>> {
+    let config = pipeline_compiler_config();
+    let lhs_programs = compile_all_parts(lhs, &config)?;
+    let rhs_programs = compile_all_parts(rhs, &config)?;
+
+    let mut differences = vec![];
+    for (script_idx, (lhs_program, rhs_program)) in
+        lhs_programs.iter().zip(rhs_programs.iter()).enumerate()
+    {
+        differences.extend(compare_programs(script_idx, &lhs_program.0, &rhs_program.0));
+    }
+
+    let common_len = lhs_programs.len().min(rhs_programs.len());
+    for script_idx in common_len..lhs_programs.len().max(rhs_programs.len()) {
+        let script_bytes = |programs: &[(CompiledProgram, Witness)]| {
+            programs
+                .get(script_idx)
+                .map(|(program, _)| program.script.to_bytes())
+                .unwrap_or_default()
+        };
+        differences.push(ScriptDifference {
+            script_idx,
+            offset: 0,
+            trace_idx: None,
+            trace_entry: None,
+            lhs: script_bytes(&lhs_programs),
+            rhs: script_bytes(&rhs_programs),
+        });
+    }
+
+    Ok(differences)
+}
+
+/// Compares the scripts of two programs compiled from the same part.
+///
+/// When the two traces have the same length, the scripts are compared trace entry by trace
+/// entry, so that a difference in one entry does not hide the differences in the later ones.
+/// Otherwise, only the first differing byte is reported.
+pub fn compare_programs(
+    script_idx: usize,
+    lhs: &CompiledProgram,
+    rhs: &CompiledProgram,
+) -> Vec<ScriptDifference> {
+    let mut differences = vec![];
+    if lhs.script == rhs.script {
+        return differences;
+    }
+
+    if lhs.trace_offsets.len() == rhs.trace_offsets.len() {
+        for trace_idx in 0..lhs.trace_offsets.len() {
+            let lhs_bytes = lhs.trace_entry_bytes(trace_idx);
+            let rhs_bytes = rhs.trace_entry_bytes(trace_idx);
+            if lhs_bytes != rhs_bytes {
+                differences.push(ScriptDifference {
+                    script_idx,
+                    offset: lhs.trace_offsets[trace_idx],
+                    trace_idx: (trace_idx < lhs.trace.len()).then_some(trace_idx),
+                    trace_entry: lhs.trace.get(trace_idx).cloned(),
+                    lhs: lhs_bytes.to_vec(),
+                    rhs: rhs_bytes.to_vec(),
+                });
+            }
+        }
+    } else {
+        let lhs_bytes = lhs.script.as_bytes();
+        let rhs_bytes = rhs.script.as_bytes();
+
+        let offset = lhs_bytes
+            .iter()
+            .zip(rhs_bytes.iter())
+            .position(|(a, b)| a != b)
+            .unwrap_or(lhs_bytes.len().min(rhs_bytes.len()));
+        let trace_idx = lhs.trace_idx_at(offset);
+
+        differences.push(ScriptDifference {
+            script_idx,
+            offset,
+            trace_idx,
+            trace_entry: trace_idx.map(|idx| lhs.trace[idx].clone()),
+            lhs: lhs_bytes[offset..].to_vec(),
+            rhs: rhs_bytes[offset.min(rhs_bytes.len())..].to_vec(),
+        });
+    }
+
+    differences
+}
+
+#[cfg(test)]
+mod test {
+    use crate::independence::compare_programs;
+    use recursive_stwo_bitcoin_dsl::bar::AllocBar;
+    use recursive_stwo_bitcoin_dsl::basic::i32::I32Bar;
+    use recursive_stwo_bitcoin_dsl::bitcoin_system::{BitcoinSystemRef, TraceEntry};
+    use recursive_stwo_bitcoin_dsl::compiler::Compiler;
+
+    fn build(hint: i32, constant: i32) -> BitcoinSystemRef {
+        let cs = BitcoinSystemRef::new_ref();
+        let a = I32Bar::new_hint(&cs, hint).unwrap();
+        let b = I32Bar::new_constant(&cs, constant).unwrap();
+        let c = &a + &b;
+        cs.set_program_output(&c).unwrap();
+        cs
+    }
+
+    #[test]
+    fn test_compare_programs() {
+        let lhs = Compiler::compile(build(1, 1000)).unwrap();
+        let rhs = Compiler::compile(build(2, 1000)).unwrap();
+        assert!(compare_programs(0, &lhs, &rhs).is_empty());
+
+        let rhs = Compiler::compile(build(2, 2000)).unwrap();
+        let differences = compare_programs(0, &lhs, &rhs);
+        assert_eq!(differences.len(), 1);
+        assert!(matches!(
+            differences[0].trace_entry,
+            Some(TraceEntry::DeclareConstant(_))
+        ));
+    }
+}
@@ -1,16 +1,19 @@
-use anyhow::Result;
+use anyhow::{Context, Error, Result};
 use bitcoin::script::write_scriptint;
 use bitcoin_scriptexec::utils::scriptint_vec;
-use circle_plonk_dsl_hints::{AnswerHints, FiatShamirHints};
 use covenants_gadgets::utils::pseudo::OP_HINT;
 use covenants_gadgets::utils::stack_hash::StackHash;
 use covenants_gadgets::CovenantProgram;
-use num_traits::One;
+use rayon::prelude::*;
+use recursive_stwo_bitcoin_dsl::artifact::Artifact;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::{BitcoinSystemRef, Element};
-use recursive_stwo_bitcoin_dsl::compiler::Compiler;
-use recursive_stwo_bitcoin_dsl::ldm::LDM;
+use recursive_stwo_bitcoin_dsl::compiler::{
+    CompiledProgram, Compiler, CompilerBudget, CompilerConfig,
+};
+use recursive_stwo_bitcoin_dsl::debugger::execute_with_witness;
+use recursive_stwo_bitcoin_dsl::ldm::{LDMSnapshot, LDM};
 use recursive_stwo_bitcoin_dsl::treepp::*;
-use recursive_stwo_delegation::folding::{DelegatedFirstLayerHints, DelegatedInnerLayersHints};
+use recursive_stwo_delegation::hints::{DelegatedProofHints, DelegatedProofShape};
 use recursive_stwo_delegation::script::{
     compute_delegation_inputs, compute_input_labels, part1, part2, part3, part4, part5,
 };
@@ -23,38 +26,84 @@ use recursive_stwo_last::script::global::{
     part1_fiat_shamir, part2_input_sum, part3_fiat_shamir, part4_composition, part5_composition,
     part6_composition, part7_coset_vanishing, part8_coset_vanishing, part9_coset_vanishing,
 };
-use recursive_stwo_last::script::hints::answer::LastAnswerHints;
-use recursive_stwo_last::script::hints::decommit::LastDecommitHints;
-use recursive_stwo_last::script::hints::fiat_shamir::LastFiatShamirHints;
-use recursive_stwo_last::script::hints::folding::{LastFirstLayerHints, LastInnerLayersHints};
+use recursive_stwo_last::script::hints::proof::{LastProofHints, LastProofShape};
 use recursive_stwo_last::script::part_last;
 use recursive_stwo_last::script::per_query::{
     part10_folding, part11_folding, part12_folding, part13_clear, part1_domain_point,
     part2_numerator, part3_numerator, part4_numerator, part5_numerator, part6_numerator,
     part7_numerator, part8_fri_decommitment, part9_folding,
 };
+use serde::{Deserialize, Serialize};
 use sha2::digest::Update;
 use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
+use std::path::Path;
 use std::sync::OnceLock;
-use stwo_prover::core::fields::qm31::QM31;
 use stwo_prover::core::fri::FriConfig;
 use stwo_prover::core::pcs::PcsConfig;
-use stwo_prover::core::vcs::sha256_merkle::{Sha256MerkleChannel, Sha256MerkleHasher};
-use stwo_prover::core::vcs::sha256_poseidon31_merkle::{
-    Sha256Poseidon31MerkleChannel, Sha256Poseidon31MerkleHasher,
-};
-use stwo_prover::examples::plonk_with_poseidon::air::{
-    verify_plonk_with_poseidon, PlonkWithPoseidonProof,
-};
-use stwo_prover::examples::plonk_without_poseidon::air::{
-    verify_plonk_without_poseidon, PlonkWithoutPoseidonProof,
-};
+use stwo_prover::core::vcs::sha256_merkle::Sha256MerkleHasher;
+use stwo_prover::core::vcs::sha256_poseidon31_merkle::Sha256Poseidon31MerkleHasher;
+use stwo_prover::examples::plonk_with_poseidon::air::PlonkWithPoseidonProof;
+use stwo_prover::examples::plonk_without_poseidon::air::PlonkWithoutPoseidonProof;
+
+pub mod independence;
 
 pub static RECURSIVE_STWO_ALL_INFORMATION: OnceLock<RecursiveStwoAllInformation> = OnceLock::new();
 
+pub static RECURSIVE_STWO_SETUP: OnceLock<RecursiveStwoSetup> = OnceLock::new();
+
 pub type Witness = Vec<Vec<u8>>;
+
+/// The proof-independent part of the pipeline, from which the program address is derived.
+#[derive(Clone, Debug)]
+pub struct RecursiveStwoSetup {
+    pub scripts: Vec<Script>,
+    /// The number of stack elements that each part leaves as its output.
+    pub output_lens: Vec<usize>,
+}
+
+impl RecursiveStwoSetup {
+    /// Checks that another run of the pipeline leads to the same scripts and output lengths, and
+    /// reports the first part that differs otherwise.
+    pub fn check(&self, other: &RecursiveStwoSetup) -> Result<()> {
+        if self.scripts.len() != other.scripts.len() {
+            return Err(Error::msg(format!(
+                "The setup has {} parts, but the proofs lead to {} parts.",
+                self.scripts.len(),
+                other.scripts.len()
+            )));
+        }
+
+        for (part_idx, (expected, actual)) in
+            self.scripts.iter().zip(other.scripts.iter()).enumerate()
+        {
+            if expected != actual {
+                let offset = expected
+                    .as_bytes()
+                    .iter()
+                    .zip(actual.as_bytes().iter())
+                    .position(|(a, b)| a != b)
+                    .unwrap_or(expected.len().min(actual.len()));
+                return Err(Error::msg(format!(
+                    "The script of part {} differs from the setup at offset {}, so the scripts depend on the proof and not only on its shape.",
+                    part_idx, offset
+                )));
+            }
+            if self.output_lens[part_idx] != other.output_lens[part_idx] {
+                return Err(Error::msg(format!(
+                    "Part {} leaves {} elements, but the setup expects {}.",
+                    part_idx, other.output_lens[part_idx], self.output_lens[part_idx]
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct RecursiveStwoAllInformation {
+    #[serde(with = "recursive_stwo_bitcoin_dsl::artifact::script_vec_serde")]
     pub scripts: Vec<Script>,
     pub witnesses: Vec<Witness>,
     pub outputs: Vec<Witness>,
@@ -99,7 +148,51 @@ impl From<RecursiveStwoVerifierInput> for Script {
     }
 }
 
+impl Artifact for RecursiveStwoAllInformation {
+    const KIND: &'static str = "recursive stwo all information";
+}
+
 impl RecursiveStwoAllInformation {
+    /// Checks that each script, run on its hints and on the output of the previous script, leaves
+    /// exactly its own output. This is how the scripts of a loaded artifact are checked before
+    /// they are used.
+    pub fn verify(&self) -> Result<()> {
+        if self.witnesses.len() != self.scripts.len() || self.outputs.len() != self.scripts.len() {
+            return Err(Error::msg(format!(
+                "There are {} scripts, but {} witnesses and {} outputs.",
+                self.scripts.len(),
+                self.witnesses.len(),
+                self.outputs.len()
+            )));
+        }
+
+        (0..self.scripts.len())
+            .into_par_iter()
+            .try_for_each(|part_idx| -> Result<()> {
+                let input = self.get_input(part_idx);
+                let mut witness = input.hints;
+                witness.extend(input.stack);
+
+                let final_stack = execute_with_witness(&self.scripts[part_idx], witness)
+                    .with_context(|| format!("The script of part {} fails.", part_idx))?;
+                if final_stack != self.outputs[part_idx] {
+                    return Err(Error::msg(format!(
+                        "The script of part {} does not leave its recorded output.",
+                        part_idx
+                    )));
+                }
+                Ok(())
+            })
+    }
+
+    /// Returns the setup made of the scripts and the lengths of the outputs.
+    pub fn setup(&self) -> RecursiveStwoSetup {
+        RecursiveStwoSetup {
+            scripts: self.scripts.clone(),
+            output_lens: self.outputs.iter().map(|output| output.len()).collect(),
+        }
+    }
+
     pub fn get_input(&self, idx: usize) -> RecursiveStwoVerifierInput {
         RecursiveStwoVerifierInput {
             stack: if idx == 0 {
@@ -112,341 +205,586 @@ impl RecursiveStwoAllInformation {
     }
 }
 
-pub fn push_delegated_information(
-    scripts: &mut Vec<Script>,
-    witnesses: &mut Vec<Vec<Vec<u8>>>,
-    outputs: &mut Vec<Vec<Vec<u8>>>,
-    proof: &PlonkWithPoseidonProof<Sha256Poseidon31MerkleHasher>,
-    config: PcsConfig,
-) -> LDM {
-    verify_plonk_with_poseidon::<Sha256Poseidon31MerkleChannel>(
-        proof.clone(),
-        config,
-        &[
-            (1, QM31::one()),
-            (2, QM31::from_u32_unchecked(0, 1, 0, 0)),
-            (3, QM31::from_u32_unchecked(0, 0, 1, 0)),
-        ],
-    )
-    .unwrap();
-
-    let fiat_shamir_hints = FiatShamirHints::<Sha256Poseidon31MerkleChannel>::new(
-        &proof,
-        config,
-        &[
-            (1, QM31::one()),
-            (2, QM31::from_u32_unchecked(0, 1, 0, 0)),
-            (3, QM31::from_u32_unchecked(0, 0, 1, 0)),
-        ],
-    );
-    let fri_answer_hints = AnswerHints::compute(&fiat_shamir_hints, &proof);
-    let first_layer_hints =
-        DelegatedFirstLayerHints::compute(&fiat_shamir_hints, &fri_answer_hints, &proof);
-    let inner_layers_hints = DelegatedInnerLayersHints::compute(
-        &first_layer_hints.folded_evals_by_column,
-        &fiat_shamir_hints,
-        &proof,
-    );
-
-    let mut ldm_delegated = LDM::new();
-
-    let mut add_cs = |cs: BitcoinSystemRef, ldm: &LDM| {
-        let program = Compiler::compile(cs).unwrap();
-
-        scripts.push(program.script);
-
-        let mut witness = vec![];
-        for entry in program.hint.iter() {
-            match &entry {
-                Element::Num(v) => {
-                    witness.push(num_to_str(*v));
-                }
-                Element::Str(v) => {
-                    witness.push(v.clone());
-                }
-            }
-        }
-
-        witnesses.push(witness);
-        outputs.push(
-            convert_to_witness(script! {
-                { ldm.hash_var.as_ref().unwrap().value.clone() }
-            })
-            .unwrap(),
-        );
-    };
-
-    let cs = part1::generate_cs(&fiat_shamir_hints, &proof, config, &mut ldm_delegated).unwrap();
-    add_cs(cs, &ldm_delegated);
-
-    let cs = part2::generate_cs(
-        &fiat_shamir_hints,
-        &proof,
-        &first_layer_hints,
-        &mut ldm_delegated,
-    )
-    .unwrap();
-    add_cs(cs, &ldm_delegated);
-
-    let cs =
-        part3::generate_cs(&fiat_shamir_hints, &inner_layers_hints, &mut ldm_delegated).unwrap();
-    add_cs(cs, &ldm_delegated);
-
-    let cs =
-        part4::generate_cs(&fiat_shamir_hints, &inner_layers_hints, &mut ldm_delegated).unwrap();
-    add_cs(cs, &ldm_delegated);
-
-    let cs =
-        part5::generate_cs(&fiat_shamir_hints, &inner_layers_hints, &mut ldm_delegated).unwrap();
-    add_cs(cs, &ldm_delegated);
+/// The state of the pipeline between two parts, from which the later parts can be generated
+/// without generating the earlier ones again.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RecursiveStwoCheckpoint {
+    /// The index of the next part.
+    pub next_part: usize,
+    /// The memory shared by all the parts.
+    pub ldm: LDMSnapshot,
+    /// The memory of the current query, or `None` if the next part does not continue a query.
+    pub ldm_per_query: Option<LDMSnapshot>,
+}
 
-    ldm_delegated
+impl Artifact for RecursiveStwoCheckpoint {
+    const KIND: &'static str = "recursive stwo checkpoint";
 }
 
-pub fn push_last_information(
-    scripts: &mut Vec<Script>,
-    witnesses: &mut Vec<Vec<Vec<u8>>>,
-    outputs: &mut Vec<Vec<Vec<u8>>>,
-    proof_last: &PlonkWithoutPoseidonProof<Sha256MerkleHasher>,
-    config_last: PcsConfig,
-    inputs: &[(usize, QM31)],
-    mut ldm: LDM,
-) {
-    verify_plonk_without_poseidon::<Sha256MerkleChannel>(proof_last.clone(), config_last, &inputs)
-        .unwrap();
+type AddPart<'a> = dyn FnMut(BitcoinSystemRef, Witness, Option<RecursiveStwoCheckpoint>) + 'a;
+
+/// The indices of the parts at which the sections of the pipeline start.
+#[derive(Clone, Debug, Default)]
+pub struct PipelineLayout {
+    /// The first part of the last proof.
+    pub last_start: usize,
+    /// The first part of each query of the last proof.
+    pub query_starts: Vec<usize>,
+    /// The number of parts.
+    pub num_parts: usize,
+}
 
-    let last_fiat_shamir_hints =
-        LastFiatShamirHints::<Sha256MerkleChannel>::new(&proof_last, config_last, &inputs);
-    let last_decommit_preprocessed_hints =
-        LastDecommitHints::compute(&last_fiat_shamir_hints, &proof_last, 0);
-    let last_decommit_trace_hints =
-        LastDecommitHints::compute(&last_fiat_shamir_hints, &proof_last, 1);
-    let last_decommit_interaction_hints =
-        LastDecommitHints::compute(&last_fiat_shamir_hints, &proof_last, 2);
-    let last_decommit_composition_hints =
-        LastDecommitHints::compute(&last_fiat_shamir_hints, &proof_last, 3);
-    let last_answer_hints = LastAnswerHints::compute(&last_fiat_shamir_hints, &proof_last);
-    let last_first_layer_hints =
-        LastFirstLayerHints::compute(&last_fiat_shamir_hints, &last_answer_hints, &proof_last);
-    let last_inner_layers_hints = LastInnerLayersHints::compute(
-        &last_first_layer_hints.folded_evals_by_column,
-        &last_fiat_shamir_hints,
-        &proof_last,
-    );
-
-    let mut add_cs = |cs: BitcoinSystemRef, ldm: &LDM, ldm_per_query: Option<&LDM>| {
-        let program = Compiler::compile(cs).unwrap();
+/// Calls the generators of the parts in order and passes the generated parts to `add_part`. The
+/// parts before the checkpoint and the parts from `end` on are skipped without being generated.
+struct PartDriver<'a, 'b> {
+    next_part: usize,
+    end: usize,
+    layout: PipelineLayout,
+    checkpoint: Option<RecursiveStwoCheckpoint>,
+    /// Whether `add_part` receives the checkpoint after each part.
+    with_checkpoints: bool,
+    add_part: &'a mut AddPart<'b>,
+}
 
-        scripts.push(program.script);
+impl PartDriver<'_, '_> {
+    /// Returns whether the next part is generated. If it is the part from which the checkpoint
+    /// resumes, the memories are restored from the checkpoint first.
+    fn enter(&mut self, ldm: &mut LDM, ldm_per_query: Option<&mut LDM>) -> bool {
+        let start = self.checkpoint.as_ref().map_or(0, |c| c.next_part);
+        if self.next_part < start || self.next_part >= self.end {
+            self.next_part += 1;
+            return false;
+        }
 
-        let mut witness = vec![];
-        for entry in program.hint.iter() {
-            match &entry {
-                Element::Num(v) => {
-                    witness.push(num_to_str(*v));
-                }
-                Element::Str(v) => {
-                    witness.push(v.clone());
-                }
+        if let Some(checkpoint) = self.checkpoint.take() {
+            *ldm = LDM::resume(checkpoint.ldm);
+            if let Some(ldm_per_query) = ldm_per_query {
+                *ldm_per_query = checkpoint
+                    .ldm_per_query
+                    .map(LDM::resume)
+                    .unwrap_or_default();
             }
         }
+        true
+    }
 
-        witnesses.push(witness);
-        if let Some(ldm_per_query) = ldm_per_query {
-            outputs.push(
-                convert_to_witness(script! {
-                    { ldm.hash_var.as_ref().unwrap().value.clone() }
-                    { ldm_per_query.hash_var.as_ref().unwrap().value.clone() }
-                })
-                .unwrap(),
-            );
-        } else {
-            outputs.push(
-                convert_to_witness(script! {
-                    { ldm.hash_var.as_ref().unwrap().value.clone() }
-                })
-                .unwrap(),
-            );
+    /// Passes the generated part on, with the hashes of the memories as its expected output. The
+    /// memory of the query is given if the next part continues the query.
+    fn leave(
+        &mut self,
+        cs: BitcoinSystemRef,
+        ldm: &LDM,
+        ldm_per_query: Option<&LDM>,
+    ) -> Result<()> {
+        let output = match ldm_per_query {
+            Some(ldm_per_query) => convert_to_witness(script! {
+                { ldm.hash_var.as_ref().unwrap().value.clone() }
+                { ldm_per_query.hash_var.as_ref().unwrap().value.clone() }
+            }),
+            None => convert_to_witness(script! {
+                { ldm.hash_var.as_ref().unwrap().value.clone() }
+            }),
         }
-    };
-
-    let cs = part1_fiat_shamir::generate_cs(&proof_last, &mut ldm).unwrap();
-    add_cs(cs, &ldm, None);
-
-    let input_labels = compute_input_labels();
-    for counter in 0..39 {
-        let cs = part2_input_sum::generate_cs(&mut ldm, counter, &input_labels).unwrap();
-        add_cs(cs, &ldm, None);
+        .map_err(|x| Error::msg(format!("output parsing error: {:?}", x)))?;
+
+        self.next_part += 1;
+        let checkpoint = self.with_checkpoints.then(|| RecursiveStwoCheckpoint {
+            next_part: self.next_part,
+            ldm: ldm.snapshot(),
+            ldm_per_query: ldm_per_query.map(LDM::snapshot),
+        });
+        (self.add_part)(cs, output, checkpoint);
+        Ok(())
     }
 
-    let cs =
-        part3_fiat_shamir::generate_cs(&last_fiat_shamir_hints, &proof_last, config_last, &mut ldm)
-            .unwrap();
-    add_cs(cs, &ldm, None);
-
-    let cs = part4_composition::generate_cs(&mut ldm).unwrap();
-    add_cs(cs, &ldm, None);
-
-    let cs = part5_composition::generate_cs(&mut ldm).unwrap();
-    add_cs(cs, &ldm, None);
-
-    let cs = part6_composition::generate_cs(&mut ldm).unwrap();
-    add_cs(cs, &ldm, None);
+    /// Generates a part that only uses the shared memory.
+    fn part(
+        &mut self,
+        ldm: &mut LDM,
+        generate: impl FnOnce(&mut LDM) -> Result<BitcoinSystemRef>,
+    ) -> Result<()> {
+        if self.enter(ldm, None) {
+            let cs = generate(&mut *ldm)?;
+            self.leave(cs, ldm, None)?;
+        }
+        Ok(())
+    }
 
-    let cs = part7_coset_vanishing::generate_cs(&proof_last, &mut ldm).unwrap();
-    add_cs(cs, &ldm, None);
+    /// Generates a part of a query, which the next part continues unless `ends_query` is set.
+    fn query_part(
+        &mut self,
+        ldm: &mut LDM,
+        ldm_per_query: &mut LDM,
+        ends_query: bool,
+        generate: impl FnOnce(&mut LDM, &mut LDM) -> Result<BitcoinSystemRef>,
+    ) -> Result<()> {
+        if self.enter(ldm, Some(&mut *ldm_per_query)) {
+            let cs = generate(&mut *ldm, &mut *ldm_per_query)?;
+            self.leave(cs, ldm, (!ends_query).then_some(&*ldm_per_query))?;
+        }
+        Ok(())
+    }
+}
 
-    let cs = part8_coset_vanishing::generate_cs(&mut ldm).unwrap();
-    add_cs(cs, &ldm, None);
+fn push_delegated_information(
+    driver: &mut PartDriver,
+    hints: &DelegatedProofHints,
+    config: PcsConfig,
+    ldm: &mut LDM,
+) -> Result<()> {
+    driver.part(ldm, |ldm| part1::generate_cs(hints, config, ldm))?;
+    driver.part(ldm, |ldm| part2::generate_cs(hints, ldm))?;
+    driver.part(ldm, |ldm| part3::generate_cs(hints, ldm))?;
+    driver.part(ldm, |ldm| part4::generate_cs(hints, ldm))?;
+    driver.part(ldm, |ldm| part5::generate_cs(hints, ldm))?;
+
+    Ok(())
+}
 
-    let cs = part9_coset_vanishing::generate_cs(&mut ldm).unwrap();
-    add_cs(cs, &ldm, None);
+fn push_last_information(
+    driver: &mut PartDriver,
+    hints: &LastProofHints,
+    config_last: PcsConfig,
+    ldm: &mut LDM,
+) -> Result<()> {
+    driver.layout.last_start = driver.next_part;
+    driver.part(ldm, |ldm| part1_fiat_shamir::generate_cs(hints, ldm))?;
 
-    let cs = part10_logup::generate_cs(&mut ldm).unwrap();
-    add_cs(cs, &ldm, None);
+    let input_labels = compute_input_labels();
+    for counter in 0..39 {
+        driver.part(ldm, |ldm| {
+            part2_input_sum::generate_cs(ldm, counter, &input_labels)
+        })?;
+    }
 
-    let cs = part11_point_shift::generate_cs(&proof_last, &mut ldm).unwrap();
-    add_cs(cs, &ldm, None);
+    driver.part(ldm, |ldm| {
+        part3_fiat_shamir::generate_cs(hints, config_last, ldm)
+    })?;
+    driver.part(ldm, part4_composition::generate_cs)?;
+    driver.part(ldm, part5_composition::generate_cs)?;
+    driver.part(ldm, part6_composition::generate_cs)?;
+    driver.part(ldm, |ldm| part7_coset_vanishing::generate_cs(hints, ldm))?;
+    driver.part(ldm, part8_coset_vanishing::generate_cs)?;
+    driver.part(ldm, part9_coset_vanishing::generate_cs)?;
+    driver.part(ldm, part10_logup::generate_cs)?;
+    driver.part(ldm, |ldm| part11_point_shift::generate_cs(hints, ldm))?;
 
     let oods_shifted_logsize_26_labels = generate_oods_shifted_logsize_26_labels();
     for counter in 0..2 {
-        let cs =
-            part12_line_coeffs::generate_cs(&mut ldm, counter, &oods_shifted_logsize_26_labels)
-                .unwrap();
-        add_cs(cs, &ldm, None);
+        driver.part(ldm, |ldm| {
+            part12_line_coeffs::generate_cs(ldm, counter, &oods_shifted_logsize_26_labels)
+        })?;
     }
 
     let oods_original_logsize_26_labels = generate_oods_original_logsize_26_labels();
     for counter in 0..12 {
-        let cs =
-            part13_line_coeffs::generate_cs(&mut ldm, counter, &oods_original_logsize_26_labels)
-                .unwrap();
-        add_cs(cs, &ldm, None);
+        driver.part(ldm, |ldm| {
+            part13_line_coeffs::generate_cs(ldm, counter, &oods_original_logsize_26_labels)
+        })?;
     }
 
     let oods_original_logsize_28_labels = generate_oods_original_logsize_28_labels();
     for counter in 0..2 {
-        let cs =
-            part14_line_coeffs::generate_cs(&mut ldm, counter, &oods_original_logsize_28_labels)
-                .unwrap();
-        add_cs(cs, &ldm, None);
+        driver.part(ldm, |ldm| {
+            part14_line_coeffs::generate_cs(ldm, counter, &oods_original_logsize_28_labels)
+        })?;
     }
 
     for query_idx in 0..8 {
+        driver.layout.query_starts.push(driver.next_part);
         let mut ldm_per_query = LDM::new();
-        let cs = part1_domain_point::generate_cs(
-            query_idx,
-            &last_decommit_composition_hints,
-            &mut ldm,
-            &mut ldm_per_query,
-        )
-        .unwrap();
-        add_cs(cs, &ldm, Some(&ldm_per_query));
+        let ldm_per_query = &mut ldm_per_query;
+
+        driver.query_part(ldm, ldm_per_query, false, |ldm, ldm_per_query| {
+            part1_domain_point::generate_cs(query_idx, &hints.decommit[3], ldm, ldm_per_query)
+        })?;
+        driver.query_part(ldm, ldm_per_query, false, |ldm, ldm_per_query| {
+            part2_numerator::generate_cs(query_idx, &hints.decommit[0], ldm, ldm_per_query)
+        })?;
+        driver.query_part(ldm, ldm_per_query, false, |ldm, ldm_per_query| {
+            part3_numerator::generate_cs(query_idx, &hints.decommit[1], ldm, ldm_per_query)
+        })?;
+        driver.query_part(ldm, ldm_per_query, false, part4_numerator::generate_cs)?;
+        driver.query_part(ldm, ldm_per_query, false, part5_numerator::generate_cs)?;
+        driver.query_part(ldm, ldm_per_query, false, |ldm, ldm_per_query| {
+            part6_numerator::generate_cs(query_idx, &hints.decommit[2], ldm, ldm_per_query)
+        })?;
+        driver.query_part(ldm, ldm_per_query, false, part7_numerator::generate_cs)?;
+        driver.query_part(ldm, ldm_per_query, false, |ldm, ldm_per_query| {
+            part8_fri_decommitment::generate_cs(
+                query_idx,
+                &hints.first_layer,
+                &hints.inner_layers,
+                ldm,
+                ldm_per_query,
+            )
+        })?;
+        driver.query_part(ldm, ldm_per_query, false, part9_folding::generate_cs)?;
+        driver.query_part(ldm, ldm_per_query, false, part10_folding::generate_cs)?;
+        driver.query_part(ldm, ldm_per_query, false, part11_folding::generate_cs)?;
+        driver.query_part(ldm, ldm_per_query, false, part12_folding::generate_cs)?;
+        driver.query_part(ldm, ldm_per_query, true, part13_clear::generate_cs)?;
+    }
 
-        let cs = part2_numerator::generate_cs(
-            query_idx,
-            &last_decommit_preprocessed_hints,
-            &mut ldm,
-            &mut ldm_per_query,
-        )
-        .unwrap();
-        add_cs(cs, &ldm, Some(&ldm_per_query));
+    driver.part(ldm, part_last::generate_cs)?;
 
-        let cs = part3_numerator::generate_cs(
-            query_idx,
-            &last_decommit_trace_hints,
-            &mut ldm,
-            &mut ldm_per_query,
-        )
-        .unwrap();
-        add_cs(cs, &ldm, Some(&ldm_per_query));
+    Ok(())
+}
 
-        let cs = part4_numerator::generate_cs(&mut ldm, &mut ldm_per_query).unwrap();
-        add_cs(cs, &ldm, Some(&ldm_per_query));
+/// The proofs, together with their configurations, that drive the verifier pipeline.
+#[derive(Clone)]
+pub struct RecursiveStwoProofs {
+    pub proof: PlonkWithPoseidonProof<Sha256Poseidon31MerkleHasher>,
+    pub config: PcsConfig,
+    pub proof_last: PlonkWithoutPoseidonProof<Sha256MerkleHasher>,
+    pub config_last: PcsConfig,
+}
 
-        let cs = part5_numerator::generate_cs(&mut ldm, &mut ldm_per_query).unwrap();
-        add_cs(cs, &ldm, Some(&ldm_per_query));
+impl RecursiveStwoProofs {
+    /// Returns the reference proofs shipped with the repository.
+    pub fn reference() -> Self {
+        let (config, config_last) = Self::reference_configs();
+        Self {
+            proof: bincode::deserialize(include_bytes!("../../data/hybrid_hash.bin")).unwrap(),
+            config,
+            proof_last: bincode::deserialize(include_bytes!("../../data/bitcoin_proof.bin"))
+                .unwrap(),
+            config_last,
+        }
+    }
 
-        let cs = part6_numerator::generate_cs(
-            query_idx,
-            &last_decommit_interaction_hints,
-            &mut ldm,
-            &mut ldm_per_query,
+    /// Returns the configurations of the reference proofs, for the delegated and the last proof.
+    pub fn reference_configs() -> (PcsConfig, PcsConfig) {
+        (
+            PcsConfig {
+                pow_bits: 28,
+                fri_config: FriConfig::new(7, 9, 8),
+            },
+            PcsConfig {
+                pow_bits: 28,
+                fri_config: FriConfig::new(0, 9, 8),
+            },
         )
-        .unwrap();
-        add_cs(cs, &ldm, Some(&ldm_per_query));
+    }
+}
 
-        let cs = part7_numerator::generate_cs(&mut ldm, &mut ldm_per_query).unwrap();
-        add_cs(cs, &ldm, Some(&ldm_per_query));
+/// The path of the shape of the reference proofs, which `gen_setup_shape` writes.
+pub const REFERENCE_SHAPE_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../data/recursive_stwo_shape.bin"
+);
+
+/// The shape of the proofs, which is, together with the configurations, all that the scripts of
+/// the pipeline depend on. Every pair of proofs of the same statements has the same shape.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecursiveStwoShape {
+    pub delegated: DelegatedProofShape,
+    pub last: LastProofShape,
+}
 
-        let cs = part8_fri_decommitment::generate_cs(
-            query_idx,
-            &last_first_layer_hints,
-            &last_inner_layers_hints,
-            &mut ldm,
-            &mut ldm_per_query,
-        )
-        .unwrap();
-        add_cs(cs, &ldm, Some(&ldm_per_query));
+impl Artifact for RecursiveStwoShape {
+    const KIND: &'static str = "recursive stwo shape";
+}
 
-        let cs = part9_folding::generate_cs(&mut ldm, &mut ldm_per_query).unwrap();
-        add_cs(cs, &ldm, Some(&ldm_per_query));
+impl RecursiveStwoShape {
+    /// Verifies the proofs and returns their shape.
+    pub fn of(proofs: &RecursiveStwoProofs) -> Result<Self> {
+        Ok(RecursiveStwoHints::compute(proofs)?.shape())
+    }
 
-        let cs = part10_folding::generate_cs(&mut ldm, &mut ldm_per_query).unwrap();
-        add_cs(cs, &ldm, Some(&ldm_per_query));
+    /// Loads the shape of the reference proofs from `REFERENCE_SHAPE_PATH`.
+    pub fn reference() -> Result<Self> {
+        Self::load(REFERENCE_SHAPE_PATH).context(
+            "The shape of the reference proofs is not available. Generate it with `cargo run --release --bin gen_setup_shape`.",
+        )
+    }
+}
 
-        let cs = part11_folding::generate_cs(&mut ldm, &mut ldm_per_query).unwrap();
-        add_cs(cs, &ldm, Some(&ldm_per_query));
+/// The hints of both proofs, together with their configurations, from which the parts are
+/// generated. They are either computed from the proofs, or placeholders of their shape.
+struct RecursiveStwoHints {
+    config: PcsConfig,
+    delegated: DelegatedProofHints,
+    config_last: PcsConfig,
+    last: LastProofHints,
+}
 
-        let cs = part12_folding::generate_cs(&mut ldm, &mut ldm_per_query).unwrap();
-        add_cs(cs, &ldm, Some(&ldm_per_query));
+impl RecursiveStwoHints {
+    fn compute(proofs: &RecursiveStwoProofs) -> Result<Self> {
+        let delegated = DelegatedProofHints::compute(&proofs.proof, proofs.config)?;
+        let inputs = compute_delegation_inputs(&proofs.proof, proofs.config);
+        let last = LastProofHints::compute(&proofs.proof_last, proofs.config_last, &inputs)?;
+        Ok(Self {
+            config: proofs.config,
+            delegated,
+            config_last: proofs.config_last,
+            last,
+        })
+    }
 
-        let cs = part13_clear::generate_cs(&mut ldm, &mut ldm_per_query).unwrap();
-        add_cs(cs, &ldm, None);
+    fn placeholder(config: PcsConfig, config_last: PcsConfig, shape: &RecursiveStwoShape) -> Self {
+        Self {
+            config,
+            delegated: DelegatedProofHints::placeholder(config, &shape.delegated),
+            config_last,
+            last: LastProofHints::placeholder(config_last, &shape.last),
+        }
     }
 
-    let cs = part_last::generate_cs(&mut ldm).unwrap();
-    add_cs(cs, &ldm, None);
+    fn shape(&self) -> RecursiveStwoShape {
+        RecursiveStwoShape {
+            delegated: self.delegated.shape(),
+            last: self.last.shape(),
+        }
+    }
 }
 
-pub fn compute_all_information() -> RecursiveStwoAllInformation {
-    let proof: PlonkWithPoseidonProof<Sha256Poseidon31MerkleHasher> =
-        bincode::deserialize(include_bytes!("../../data/hybrid_hash.bin")).unwrap();
-    let config = PcsConfig {
-        pow_bits: 28,
-        fri_config: FriConfig::new(7, 9, 8),
-    };
-    let proof_last: PlonkWithoutPoseidonProof<Sha256MerkleHasher> =
-        bincode::deserialize(include_bytes!("../../data/bitcoin_proof.bin")).unwrap();
-    let config_last = PcsConfig {
-        pow_bits: 28,
-        fri_config: FriConfig::new(0, 9, 8),
+/// Generates the parts from the checkpoint on, or from the first one if there is none, up to
+/// `end`, and passes each of them to `add_part` in the order of execution. Returns the layout of
+/// the whole pipeline, including the skipped parts.
+fn generate_parts(
+    hints: &RecursiveStwoHints,
+    checkpoint: Option<RecursiveStwoCheckpoint>,
+    end: usize,
+    with_checkpoints: bool,
+    add_part: &mut AddPart,
+) -> Result<PipelineLayout> {
+    let mut driver = PartDriver {
+        next_part: 0,
+        end,
+        layout: PipelineLayout::default(),
+        checkpoint,
+        with_checkpoints,
+        add_part,
     };
 
+    let mut ldm = LDM::new();
+    push_delegated_information(&mut driver, &hints.delegated, hints.config, &mut ldm)?;
+    push_last_information(&mut driver, &hints.last, hints.config_last, &mut ldm)?;
+
+    driver.layout.num_parts = driver.next_part;
+    Ok(driver.layout)
+}
+
+/// Returns the layout of the pipeline for the given proofs, without generating any part.
+pub fn pipeline_layout(proofs: &RecursiveStwoProofs) -> Result<PipelineLayout> {
+    let hints = RecursiveStwoHints::compute(proofs)?;
+    generate_parts(&hints, None, 0, false, &mut |_, _, _| {})
+}
+
+/// Generates every part of the pipeline and passes each of them, together with its expected
+/// output, to `add_part` in the order of execution.
+pub fn generate_all_parts(
+    proofs: &RecursiveStwoProofs,
+    add_part: &mut impl FnMut(BitcoinSystemRef, Witness),
+) -> Result<()> {
+    let hints = RecursiveStwoHints::compute(proofs)?;
+    generate_parts(&hints, None, usize::MAX, false, &mut |cs, output, _| {
+        add_part(cs, output)
+    })?;
+    Ok(())
+}
+
+/// Generates every part of the pipeline and passes each of them to `add_part`, together with its
+/// expected output and the checkpoint after it. A saved checkpoint lets
+/// `compute_part_from_checkpoint` regenerate the next part on its own.
+pub fn generate_all_parts_with_checkpoints(
+    proofs: &RecursiveStwoProofs,
+    add_part: &mut impl FnMut(BitcoinSystemRef, Witness, RecursiveStwoCheckpoint),
+) -> Result<()> {
+    let hints = RecursiveStwoHints::compute(proofs)?;
+    generate_parts(
+        &hints,
+        None,
+        usize::MAX,
+        true,
+        &mut |cs, output, checkpoint| add_part(cs, output, checkpoint.unwrap()),
+    )?;
+    Ok(())
+}
+
+/// Generates and compiles the part that follows the checkpoint without generating the earlier
+/// ones, which regenerates the witness of a single transaction. Returns the compiled part, its
+/// expected output, and the checkpoint after it.
+pub fn compute_part_from_checkpoint(
+    proofs: &RecursiveStwoProofs,
+    checkpoint: RecursiveStwoCheckpoint,
+) -> Result<(CompiledProgram, Witness, RecursiveStwoCheckpoint)> {
+    let part_idx = checkpoint.next_part;
+
+    let hints = RecursiveStwoHints::compute(proofs)?;
+    let mut part = None;
+    generate_parts(
+        &hints,
+        Some(checkpoint),
+        part_idx + 1,
+        true,
+        &mut |cs, output, checkpoint| part = Some((cs, output, checkpoint.unwrap())),
+    )?;
+    let (cs, output, checkpoint) =
+        part.ok_or_else(|| Error::msg(format!("The pipeline has no part {}.", part_idx)))?;
+
+    let program = Compiler::compile_with_config(cs, &pipeline_compiler_config())
+        .with_context(|| format!("Cannot compile part {}.", part_idx))?;
+    Ok((program, output, checkpoint))
+}
+
+/// Generates all the parts and compiles them with the given configuration, in the order of the
+/// parts. The parts are generated one after the other, as each one continues from the LDM of the
+/// previous ones, and each generated part is detached, so that they are then all compiled on the
+/// rayon pool.
+pub fn compile_all_parts(
+    proofs: &RecursiveStwoProofs,
+    config: &CompilerConfig,
+) -> Result<Vec<(CompiledProgram, Witness)>> {
+    compile_parts(&RecursiveStwoHints::compute(proofs)?, config)
+}
+
+fn compile_parts(
+    hints: &RecursiveStwoHints,
+    config: &CompilerConfig,
+) -> Result<Vec<(CompiledProgram, Witness)>> {
+    let mut parts = vec![];
+    generate_parts(hints, None, usize::MAX, false, &mut |cs, output, _| {
+        parts.push((cs.detach(), output));
+    })?;
+
+    parts
+        .into_par_iter()
+        .enumerate()
+        .map(|(part_idx, (cs, output))| {
+            let program = Compiler::compile_system(&cs, config)
+                .with_context(|| format!("Cannot compile part {}.", part_idx))?;
+            Ok((program, output))
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
+/// The configuration with which every part of the pipeline is compiled, in both the setup and
+/// the prove modes, so that the two produce the same scripts. A part that does not fit in a
+/// transaction fails with `DslError::BudgetExceeded`, and a gadget whose script does not match
+/// its declared outputs fails with `DslError::StackEffectMismatch`.
+pub fn pipeline_compiler_config() -> CompilerConfig {
+    CompilerConfig::new()
+        .with_budget(CompilerBudget::default())
+        .with_stack_effect_check()
+}
+
+/// Setup mode: computes the locking scripts of all the parts from the configurations and the
+/// proof shape alone, with placeholder hints. Fails if a part does not fit in a transaction.
+pub fn compute_setup(
+    config: PcsConfig,
+    config_last: PcsConfig,
+    shape: &RecursiveStwoShape,
+) -> Result<RecursiveStwoSetup> {
+    let hints = RecursiveStwoHints::placeholder(config, config_last, shape);
+
+    let mut scripts = vec![];
+    let mut output_lens = vec![];
+
+    for (program, output) in compile_parts(&hints, &pipeline_compiler_config())? {
+        scripts.push(program.script);
+        output_lens.push(output.len());
+    }
+
+    Ok(RecursiveStwoSetup {
+        scripts,
+        output_lens,
+    })
+}
+
+/// Prove mode: computes the scripts, hints, and outputs for the given proofs, and checks that
+/// the scripts are identical to those of the setup returned by `recursive_stwo_setup`, which is
+/// computed from the shape alone. Fails if no setup is available.
+pub fn compute_all_information_for(
+    proofs: &RecursiveStwoProofs,
+) -> Result<RecursiveStwoAllInformation> {
     let mut scripts = vec![];
     let mut witnesses = vec![];
     let mut outputs = vec![];
 
-    let ldm =
-        push_delegated_information(&mut scripts, &mut witnesses, &mut outputs, &proof, config);
-    let inputs = compute_delegation_inputs(&proof, config);
-    push_last_information(
-        &mut scripts,
-        &mut witnesses,
-        &mut outputs,
-        &proof_last,
-        config_last,
-        &inputs,
-        ldm,
-    );
-
-    assert_eq!(scripts.len(), witnesses.len());
-    assert_eq!(scripts.len(), outputs.len());
-
-    RecursiveStwoAllInformation {
+    for (program, output) in compile_all_parts(proofs, &pipeline_compiler_config())? {
+        scripts.push(program.script);
+        witnesses.push(program.hint.iter().map(element_to_witness).collect());
+        outputs.push(output);
+    }
+
+    let all_information = RecursiveStwoAllInformation {
         scripts,
         witnesses,
         outputs,
+    };
+
+    recursive_stwo_setup()?.check(&all_information.setup())?;
+
+    Ok(all_information)
+}
+
+/// Computes the setup for the reference configurations and the shape of the reference proofs,
+/// without the proofs themselves.
+pub fn compute_default_setup() -> Result<RecursiveStwoSetup> {
+    let (config, config_last) = RecursiveStwoProofs::reference_configs();
+    compute_setup(config, config_last, &RecursiveStwoShape::reference()?)
+}
+
+pub fn compute_all_information() -> Result<RecursiveStwoAllInformation> {
+    compute_all_information_for(&RecursiveStwoProofs::reference())
+}
+
+/// Returns the scripts, hints, and outputs used by the covenant, which are computed for the
+/// reference proofs on first use.
+pub fn recursive_stwo_all_information() -> Result<&'static RecursiveStwoAllInformation> {
+    if let Some(all_information) = RECURSIVE_STWO_ALL_INFORMATION.get() {
+        return Ok(all_information);
+    }
+    let all_information = compute_all_information()?;
+    Ok(RECURSIVE_STWO_ALL_INFORMATION.get_or_init(|| all_information))
+}
+
+/// Returns the setup used by the covenant, which is computed by `compute_default_setup` on first
+/// use, independently of any proof.
+pub fn recursive_stwo_setup() -> Result<&'static RecursiveStwoSetup> {
+    if let Some(setup) = RECURSIVE_STWO_SETUP.get() {
+        return Ok(setup);
+    }
+    let setup = compute_default_setup()?;
+    Ok(RECURSIVE_STWO_SETUP.get_or_init(|| setup))
+}
+
+/// Loads the scripts, hints, and outputs from the artifact at `path` if it exists, and otherwise
+/// computes them for the reference proofs and saves them there. They then become the ones used
+/// by the covenant.
+///
+/// The scripts of a loaded artifact are first checked with `RecursiveStwoAllInformation::verify`,
+/// and against the setup returned by `recursive_stwo_setup`.
+pub fn load_or_compute_all_information(
+    path: impl AsRef<Path>,
+) -> Result<&'static RecursiveStwoAllInformation> {
+    let path = path.as_ref();
+    let all_information = if path.exists() {
+        let all_information = RecursiveStwoAllInformation::load(path)?;
+        all_information
+            .verify()
+            .with_context(|| format!("The artifact {} is not consistent.", path.display()))?;
+        all_information
+    } else {
+        let all_information = compute_all_information()?;
+        all_information.save(path)?;
+        all_information
+    };
+
+    recursive_stwo_setup()?.check(&all_information.setup())?;
+
+    RECURSIVE_STWO_ALL_INFORMATION
+        .set(all_information)
+        .map_err(|_| Error::msg("The covenant already uses other scripts, hints, and outputs."))?;
+    recursive_stwo_all_information()
+}
+
+fn element_to_witness(element: &Element) -> Vec<u8> {
+    match element {
+        Element::Num(v) => num_to_str(*v),
+        Element::Str(v) => v.clone(),
     }
 }
 
@@ -482,10 +820,10 @@ impl CovenantProgram for RecursiveStwoVerifierProgram {
     }
 
     fn get_all_scripts() -> BTreeMap<usize, Script> {
-        let all_information = RECURSIVE_STWO_ALL_INFORMATION.get_or_init(compute_all_information);
+        let setup = recursive_stwo_setup().expect("cannot compute the setup");
 
         let mut map = BTreeMap::new();
-        let num_scripts = all_information.scripts.len();
+        let num_scripts = setup.scripts.len();
         println!("num_scripts: {}", num_scripts);
 
         for script_idx in 0..num_scripts {
@@ -513,17 +851,17 @@ impl CovenantProgram for RecursiveStwoVerifierProgram {
                         // - new stack hash
                         OP_TOALTSTACK OP_TOALTSTACK
 
-                        { StackHash::hash_from_hint(all_information.outputs[script_idx - 1].len()) }
+                        { StackHash::hash_from_hint(setup.output_lens[script_idx - 1]) }
                         OP_FROMALTSTACK OP_EQUALVERIFY
                     }
 
-                    { all_information.scripts[script_idx].clone() }
+                    { setup.scripts[script_idx].clone() }
 
                     OP_DEPTH
-                    { all_information.outputs[script_idx].len() }
+                    { setup.output_lens[script_idx] }
                     OP_EQUALVERIFY
 
-                    { StackHash::hash_drop(all_information.outputs[script_idx].len()) }
+                    { StackHash::hash_drop(setup.output_lens[script_idx]) }
                     OP_FROMALTSTACK OP_EQUALVERIFY
                     OP_TRUE
                 },
@@ -565,7 +903,7 @@ impl CovenantProgram for RecursiveStwoVerifierProgram {
     }
 
     fn run(id: usize, _: &Self::State, _: &Self::Input) -> Result<Self::State> {
-        let all_information = RECURSIVE_STWO_ALL_INFORMATION.get_or_init(compute_all_information);
+        let all_information = recursive_stwo_all_information()?;
 
         let final_stack = all_information.outputs[id].clone();
         let stack_hash = StackHash::compute(&final_stack);
@@ -580,15 +918,22 @@ impl CovenantProgram for RecursiveStwoVerifierProgram {
 #[cfg(test)]
 mod test {
     use crate::{
-        compute_all_information, RecursiveStwoVerifierProgram, RecursiveStwoVerifierState,
-        RECURSIVE_STWO_ALL_INFORMATION,
+        compile_all_parts, compute_part_from_checkpoint, compute_setup, generate_all_parts,
+        generate_all_parts_with_checkpoints, pipeline_compiler_config, pipeline_layout,
+        recursive_stwo_all_information, RecursiveStwoCheckpoint, RecursiveStwoProofs,
+        RecursiveStwoSetup, RecursiveStwoShape, RecursiveStwoVerifierProgram,
+        RecursiveStwoVerifierState,
     };
     use bitcoin_simulator::policy::Policy;
     use covenants_gadgets::test::{simulation_test_with_policy, SimulationInstruction};
+    use recursive_stwo_bitcoin_dsl::artifact::Artifact;
+    use recursive_stwo_bitcoin_dsl::compiler::Compiler;
+    use recursive_stwo_bitcoin_dsl::tamper::tamper_hints;
+    use recursive_stwo_bitcoin_dsl::treepp::*;
 
     #[test]
     fn test_covenant() {
-        let all_information = RECURSIVE_STWO_ALL_INFORMATION.get_or_init(compute_all_information);
+        let all_information = recursive_stwo_all_information().unwrap();
         let mut test_generator = |old_state: &RecursiveStwoVerifierState| {
             Some(SimulationInstruction {
                 program_index: old_state.pc,
@@ -603,4 +948,91 @@ mod test {
         );
         println!("total_fee: {:?} sats", total_fee);
     }
+
+    #[test]
+    fn test_setup_from_shape() {
+        let proofs = RecursiveStwoProofs::reference();
+        let shape = RecursiveStwoShape::of(&proofs).unwrap();
+        assert_eq!(RecursiveStwoShape::reference().unwrap(), shape);
+
+        let setup = compute_setup(proofs.config, proofs.config_last, &shape).unwrap();
+
+        let mut scripts = vec![];
+        let mut output_lens = vec![];
+        for (program, output) in compile_all_parts(&proofs, &pipeline_compiler_config()).unwrap() {
+            scripts.push(program.script);
+            output_lens.push(output.len());
+        }
+        setup
+            .check(&RecursiveStwoSetup {
+                scripts,
+                output_lens,
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_part_from_checkpoint() {
+        let proofs = RecursiveStwoProofs::reference();
+        let layout = pipeline_layout(&proofs).unwrap();
+
+        // the first part of the last proof, and a part in the middle of the first query
+        let targets = [layout.last_start, layout.query_starts[0] + 2];
+
+        let mut expected = vec![];
+        let mut checkpoints = vec![];
+        generate_all_parts_with_checkpoints(&proofs, &mut |cs, output, checkpoint| {
+            if targets.contains(&(checkpoint.next_part - 1)) {
+                let program =
+                    Compiler::compile_with_config(cs, &pipeline_compiler_config()).unwrap();
+                expected.push((program, output));
+            }
+            if targets.contains(&checkpoint.next_part) {
+                let bytes = checkpoint.to_artifact_bytes().unwrap();
+                checkpoints.push(RecursiveStwoCheckpoint::from_artifact_bytes(&bytes).unwrap());
+            }
+        })
+        .unwrap();
+        assert_eq!(checkpoints.len(), targets.len());
+
+        for (checkpoint, (expected_program, expected_output)) in
+            checkpoints.into_iter().zip(expected)
+        {
+            let part_idx = checkpoint.next_part;
+            let (program, output, next) =
+                compute_part_from_checkpoint(&proofs, checkpoint).unwrap();
+            assert_eq!(program.input, expected_program.input);
+            assert_eq!(program.hint, expected_program.hint);
+            assert_eq!(program.script, expected_program.script);
+            assert_eq!(output, expected_output);
+            assert_eq!(next.next_part, part_idx + 1);
+        }
+    }
+
+    // re-executes every part once per mutated hint element, which takes hours, so it is only run
+    // on demand with `cargo test --release -- --ignored test_tamper_all_parts`
+    #[test]
+    #[ignore]
+    fn test_tamper_all_parts() {
+        let proofs = RecursiveStwoProofs::reference();
+
+        let mut part_idx = 0;
+        let mut survivors = vec![];
+        generate_all_parts(&proofs, &mut |cs, output| {
+            let expected_stack = script! {
+                for elem in output {
+                    { elem }
+                }
+            };
+            let report = tamper_hints(cs, expected_stack)
+                .unwrap_or_else(|err| panic!("part {}: {}", part_idx, err));
+            for survivor in report.survivors {
+                survivors.push(format!("part {}: {}", part_idx, survivor));
+            }
+            part_idx += 1;
+        })
+        .unwrap();
+
+        assert!(survivors.is_empty(), "{}", survivors.join("\n"));
+    }
 }
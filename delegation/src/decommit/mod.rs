@@ -143,6 +143,19 @@ impl DelegatedSingleLeafMerkleProof {
         res
     }
 
+    /// Returns a proof of the given depth and number of columns filled with zeros, which leads to
+    /// the same scripts as any proof of that shape but does not verify.
+    pub fn placeholder(depth: usize, n_columns: usize) -> Self {
+        let zero_hash = Sha256Hash::from([0u8; 32].as_slice());
+        Self {
+            query: 0,
+            sibling_hashes: vec![zero_hash; depth],
+            columns: vec![M31::zero(); n_columns],
+            root: zero_hash,
+            depth,
+        }
+    }
+
     pub fn verify(&self) {
         let mut cur_hash = Sha256Poseidon31MerkleHasher::hash_node(None, &self.columns);
 
@@ -288,6 +301,14 @@ impl DelegatedDecommitHints {
 
         DelegatedDecommitHints { proofs }
     }
+
+    /// Returns the placeholder hints for a tree of the given depth and number of columns, with
+    /// one proof per query.
+    pub fn placeholder(n_queries: usize, depth: usize, n_columns: usize) -> Self {
+        DelegatedDecommitHints {
+            proofs: vec![DelegatedSingleLeafMerkleProof::placeholder(depth, n_columns); n_queries],
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -4,9 +4,8 @@ use crate::folding::merkle_proofs::{
 use anyhow::Result;
 use circle_plonk_dsl_hints::{AnswerHints, FiatShamirHints};
 use itertools::{zip_eq, Itertools};
-use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode};
+use recursive_stwo_bitcoin_dsl::bar::{AllocBar, Bar};
 use recursive_stwo_bitcoin_dsl::basic::sha256_hash::Sha256HashBar;
-use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_primitives::fields::m31::M31Bar;
 use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use serde::{Deserialize, Serialize};
@@ -148,6 +147,18 @@ impl DelegatedFirstLayerHints {
         }
     }
 
+    /// Returns the placeholder hints for a first layer with columns at the given log sizes, with
+    /// one proof per query. The folded evaluations are left empty, as no script depends on them.
+    pub fn placeholder(n_queries: usize, log_sizes_with_data: &BTreeSet<u32>) -> Self {
+        DelegatedFirstLayerHints {
+            merkle_proofs: vec![
+                DelegatedSinglePairMerkleProof::placeholder(log_sizes_with_data);
+                n_queries
+            ],
+            folded_evals_by_column: BTreeMap::new(),
+        }
+    }
+
     pub fn compute_decommitment_positions_and_rebuild_evals(
         queries: &[usize],
         domain_log_size: u32,
@@ -188,37 +199,12 @@ impl DelegatedFirstLayerHints {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Bar, AllocBar)]
+#[bar(value = DelegatedFirstLayerHints)]
 pub struct DelegatedFirstLayerBar {
-    pub value: DelegatedFirstLayerHints,
     pub merkle_proofs: Vec<DelegatedSinglePairMerkleProofBar>,
-}
-
-impl AllocBar for DelegatedFirstLayerBar {
-    type Value = DelegatedFirstLayerHints;
-
-    fn value(&self) -> Result<Self::Value> {
-        Ok(self.value.clone())
-    }
-
-    fn new_variable(
-        cs: &BitcoinSystemRef,
-        data: Self::Value,
-        mode: AllocationMode,
-    ) -> Result<Self> {
-        let mut merkle_proofs = vec![];
-        for proof in data.merkle_proofs.iter() {
-            merkle_proofs.push(DelegatedSinglePairMerkleProofBar::new_variable(
-                cs,
-                proof.clone(),
-                mode,
-            )?);
-        }
-        Ok(Self {
-            value: data,
-            merkle_proofs,
-        })
-    }
+    #[bar(skip)]
+    pub folded_evals_by_column: BTreeMap<u32, Vec<SecureField>>,
 }
 
 impl DelegatedFirstLayerBar {
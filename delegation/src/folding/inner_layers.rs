@@ -173,6 +173,32 @@ impl DelegatedInnerLayersHints {
             folded_intermediate_results: all_folded_intermediate_results,
         }
     }
+
+    /// Returns the placeholder hints for the inner layers that follow a first layer of the given
+    /// log size, with one proof per query. The intermediate results are left empty, as no script
+    /// depends on them.
+    pub fn placeholder(
+        n_queries: usize,
+        max_first_layer_column_log_size: u32,
+        n_inner_layers: usize,
+    ) -> Self {
+        let mut merkle_proofs = BTreeMap::new();
+        for i in 0..n_inner_layers {
+            let log_size = max_first_layer_column_log_size - 1 - i as u32;
+            merkle_proofs.insert(
+                log_size,
+                vec![
+                    DelegatedSinglePairMerkleProof::placeholder(&BTreeSet::from([log_size]));
+                    n_queries
+                ],
+            );
+        }
+
+        Self {
+            merkle_proofs,
+            folded_intermediate_results: BTreeMap::new(),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -184,7 +210,7 @@ impl AllocBar for DelegatedInnerLayersPerLayerBar {
     type Value = Vec<DelegatedSinglePairMerkleProof>;
 
     fn value(&self) -> Result<Self::Value> {
-        Ok(self.merkle_proofs.iter().map(|v| v.value.clone()).collect())
+        self.merkle_proofs.value()
     }
 
     fn new_variable(
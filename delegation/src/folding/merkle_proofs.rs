@@ -1,8 +1,8 @@
 use bitcoin::ScriptBuf as Script;
-use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar};
+use num_traits::Zero;
+use recursive_stwo_bitcoin_dsl::bar::{AllocBar, Bar};
 use recursive_stwo_bitcoin_dsl::basic::bool::BoolBar;
 use recursive_stwo_bitcoin_dsl::basic::sha256_hash::Sha256HashBar;
-use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::treepp::*;
 use recursive_stwo_primitives::bits::split_be_bits;
 use recursive_stwo_primitives::fields::m31::M31Bar;
@@ -102,6 +102,29 @@ impl DelegatedSinglePairMerkleProof {
         assert_eq!(self_hash, self.root);
     }
 
+    /// Returns a proof with columns at the given log sizes filled with zeros, which leads to the
+    /// same scripts as any proof of that shape but does not verify.
+    pub fn placeholder(log_sizes_with_data: &BTreeSet<u32>) -> Self {
+        let zero_hash = Sha256Hash::from([0u8; 32].as_slice());
+        let depth = *log_sizes_with_data.last().unwrap() as usize;
+
+        let mut self_columns = BTreeMap::new();
+        let mut siblings_columns = BTreeMap::new();
+        for &log_size in log_sizes_with_data.iter() {
+            self_columns.insert(log_size as usize, QM31::zero());
+            siblings_columns.insert(log_size as usize, QM31::zero());
+        }
+
+        Self {
+            query: 0,
+            sibling_hashes: vec![zero_hash; depth - 1],
+            self_columns,
+            siblings_columns,
+            root: zero_hash,
+            depth,
+        }
+    }
+
     pub fn from_stwo_proof(
         log_sizes_with_data: &BTreeSet<u32>,
         root: Sha256Hash,
@@ -304,52 +327,21 @@ impl DelegatedSinglePairMerkleProof {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Bar, AllocBar)]
+#[bar(value = DelegatedSinglePairMerkleProof)]
 pub struct DelegatedSinglePairMerkleProofBar {
-    pub cs: BitcoinSystemRef,
-    pub value: DelegatedSinglePairMerkleProof,
+    #[bar(skip)]
+    pub query: usize,
 
     pub sibling_hashes: Vec<Sha256HashBar>,
 
     pub self_columns: BTreeMap<usize, QM31Bar>,
     pub siblings_columns: BTreeMap<usize, QM31Bar>,
-}
-
-impl AllocBar for DelegatedSinglePairMerkleProofBar {
-    type Value = DelegatedSinglePairMerkleProof;
-
-    fn value(&self) -> anyhow::Result<Self::Value> {
-        Ok(self.value.clone())
-    }
-
-    fn new_variable(
-        cs: &BitcoinSystemRef,
-        data: Self::Value,
-        mode: AllocationMode,
-    ) -> anyhow::Result<Self> {
-        let mut sibling_hashes = vec![];
-        for sibling_hash in data.sibling_hashes.iter() {
-            sibling_hashes.push(Sha256HashBar::new_variable(cs, sibling_hash.clone(), mode)?);
-        }
 
-        let mut self_columns = BTreeMap::new();
-        for (k, column) in data.self_columns.iter() {
-            self_columns.insert(*k, QM31Bar::new_variable(cs, column.clone(), mode)?);
-        }
-
-        let mut siblings_columns = BTreeMap::new();
-        for (k, column) in data.siblings_columns.iter() {
-            siblings_columns.insert(*k, QM31Bar::new_variable(cs, column.clone(), mode)?);
-        }
-
-        Ok(Self {
-            cs: cs.clone(),
-            value: data.clone(),
-            sibling_hashes,
-            self_columns,
-            siblings_columns,
-        })
-    }
+    #[bar(skip)]
+    pub root: Sha256Hash,
+    #[bar(skip)]
+    pub depth: usize,
 }
 
 impl DelegatedSinglePairMerkleProofBar {
@@ -373,23 +365,23 @@ impl DelegatedSinglePairMerkleProofBar {
             &cs,
             &self
                 .self_columns
-                .get(&self.value.depth)
+                .get(&self.depth)
                 .map_or(vec![], |v| v.to_m31_array().to_vec()),
         )?;
         let mut sibling_hash = hash_many_m31(
             &cs,
             &self
                 .siblings_columns
-                .get(&self.value.depth)
+                .get(&self.depth)
                 .map_or(vec![], |v| v.to_m31_array().to_vec()),
         )?;
 
-        for i in 0..self.value.depth {
-            let h = self.value.depth - i - 1;
+        for i in 0..self.depth {
+            let h = self.depth - i - 1;
 
             if !self.self_columns.contains_key(&h) {
                 self_hash = hash_node(&self_hash, &sibling_hash, &bits_vars[i], None)?;
-                if i != self.value.depth - 1 {
+                if i != self.depth - 1 {
                     sibling_hash = self.sibling_hashes[i].clone();
                 }
             } else {
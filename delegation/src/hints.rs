@@ -0,0 +1,201 @@
+use crate::decommit::DelegatedDecommitHints;
+use crate::folding::{DelegatedFirstLayerHints, DelegatedInnerLayersHints};
+use anyhow::{Error, Result};
+use circle_plonk_dsl_hints::{AnswerHints, FiatShamirHints};
+use itertools::Itertools;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use stwo_prover::core::fields::qm31::QM31;
+use stwo_prover::core::pcs::PcsConfig;
+use stwo_prover::core::vcs::poseidon31_merkle::Poseidon31MerkleHasher;
+use stwo_prover::core::vcs::sha256_hash::Sha256Hash;
+use stwo_prover::core::vcs::sha256_poseidon31_merkle::{
+    Sha256Poseidon31MerkleChannel, Sha256Poseidon31MerkleHasher,
+};
+use stwo_prover::examples::plonk_with_poseidon::air::{
+    verify_plonk_with_poseidon, PlonkWithPoseidonProof,
+};
+
+/// The shape of a delegated proof, which is, together with the configuration, all that the
+/// scripts of the delegation parts depend on. Every proof of the same statement has the same
+/// shape.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelegatedProofShape {
+    pub log_size_plonk: u32,
+    pub log_size_poseidon: u32,
+    /// The depth and the number of columns of each of the four committed trees.
+    pub trees: [(usize, usize); 4],
+    /// The log sizes of the columns folded into the first FRI layer.
+    pub first_layer_log_sizes: BTreeSet<u32>,
+    pub n_inner_layers: usize,
+}
+
+/// The values that the delegation parts take as hints.
+///
+/// In the prove mode, they are computed from a proof. In the setup mode, they are placeholders of
+/// the shape of the proof, which lead to the same scripts as any proof of that shape.
+#[derive(Clone, Debug)]
+pub struct DelegatedProofHints {
+    pub log_size_plonk: u32,
+    pub log_size_poseidon: u32,
+    pub commitments: [Sha256Hash; 4],
+    pub plonk_total_sum: QM31,
+    pub poseidon_total_sum: QM31,
+    pub sampled_values_hash: [QM31; 2],
+    pub first_layer_commitment: Sha256Hash,
+    pub inner_layer_commitments: Vec<Sha256Hash>,
+    pub last_layer_coeffs_hash: [QM31; 2],
+    pub proof_of_work: u64,
+    pub max_first_layer_column_log_size: u32,
+    pub decommit: [DelegatedDecommitHints; 4],
+    pub first_layer: DelegatedFirstLayerHints,
+    pub inner_layers: DelegatedInnerLayersHints,
+}
+
+impl DelegatedProofHints {
+    /// Verifies the proof and computes the hints from it.
+    pub fn compute(
+        proof: &PlonkWithPoseidonProof<Sha256Poseidon31MerkleHasher>,
+        config: PcsConfig,
+    ) -> Result<Self> {
+        let inputs = [
+            (1, QM31::one()),
+            (2, QM31::from_u32_unchecked(0, 1, 0, 0)),
+            (3, QM31::from_u32_unchecked(0, 0, 1, 0)),
+        ];
+        verify_plonk_with_poseidon::<Sha256Poseidon31MerkleChannel>(proof.clone(), config, &inputs)
+            .map_err(|err| Error::msg(format!("The delegated proof is invalid: {:?}.", err)))?;
+
+        let fiat_shamir_hints =
+            FiatShamirHints::<Sha256Poseidon31MerkleChannel>::new(proof, config, &inputs);
+        let fri_answer_hints = AnswerHints::compute(&fiat_shamir_hints, proof);
+        let first_layer =
+            DelegatedFirstLayerHints::compute(&fiat_shamir_hints, &fri_answer_hints, proof);
+        let inner_layers = DelegatedInnerLayersHints::compute(
+            &first_layer.folded_evals_by_column,
+            &fiat_shamir_hints,
+            proof,
+        );
+
+        let sampled_values_hash = Poseidon31MerkleHasher::hash_column_get_rate(
+            &proof
+                .stark_proof
+                .sampled_values
+                .clone()
+                .flatten_cols()
+                .iter()
+                .flat_map(|v| v.to_m31_array())
+                .collect_vec(),
+        );
+
+        let coeffs = &proof.stark_proof.fri_proof.last_layer_poly.coeffs;
+        assert!(coeffs.len() > 2);
+        let last_layer_coeffs_hash = Poseidon31MerkleHasher::hash_column_get_rate(
+            &coeffs.iter().flat_map(|v| v.to_m31_array()).collect_vec(),
+        );
+
+        Ok(Self {
+            log_size_plonk: proof.stmt0.log_size_plonk,
+            log_size_poseidon: proof.stmt0.log_size_poseidon,
+            commitments: std::array::from_fn(|i| proof.stark_proof.commitments[i]),
+            plonk_total_sum: fiat_shamir_hints.plonk_total_sum,
+            poseidon_total_sum: fiat_shamir_hints.poseidon_total_sum,
+            sampled_values_hash: [
+                QM31::from_m31(
+                    sampled_values_hash.0[0],
+                    sampled_values_hash.0[1],
+                    sampled_values_hash.0[2],
+                    sampled_values_hash.0[3],
+                ),
+                QM31::from_m31(
+                    sampled_values_hash.0[4],
+                    sampled_values_hash.0[5],
+                    sampled_values_hash.0[6],
+                    sampled_values_hash.0[7],
+                ),
+            ],
+            first_layer_commitment: proof.stark_proof.fri_proof.first_layer.commitment,
+            inner_layer_commitments: proof
+                .stark_proof
+                .fri_proof
+                .inner_layers
+                .iter()
+                .map(|inner_layer| inner_layer.commitment)
+                .collect(),
+            last_layer_coeffs_hash: [
+                QM31::from_m31(
+                    last_layer_coeffs_hash.0[0],
+                    last_layer_coeffs_hash.0[1],
+                    last_layer_coeffs_hash.0[2],
+                    last_layer_coeffs_hash.0[3],
+                ),
+                QM31::from_m31(
+                    last_layer_coeffs_hash.0[4],
+                    last_layer_coeffs_hash.0[5],
+                    last_layer_coeffs_hash.0[6],
+                    last_layer_coeffs_hash.0[7],
+                ),
+            ],
+            proof_of_work: proof.stark_proof.proof_of_work,
+            max_first_layer_column_log_size: fiat_shamir_hints.max_first_layer_column_log_size,
+            decommit: std::array::from_fn(|round| {
+                DelegatedDecommitHints::compute(&fiat_shamir_hints, proof, round)
+            }),
+            first_layer,
+            inner_layers,
+        })
+    }
+
+    /// Returns hints of the given shape filled with zeros, from which the scripts of the
+    /// delegation parts can be generated without a proof.
+    pub fn placeholder(config: PcsConfig, shape: &DelegatedProofShape) -> Self {
+        let n_queries = config.fri_config.n_queries;
+        let zero_hash = Sha256Hash::from([0u8; 32].as_slice());
+        let max_first_layer_column_log_size = *shape.first_layer_log_sizes.last().unwrap();
+
+        Self {
+            log_size_plonk: shape.log_size_plonk,
+            log_size_poseidon: shape.log_size_poseidon,
+            commitments: [zero_hash; 4],
+            plonk_total_sum: QM31::zero(),
+            poseidon_total_sum: QM31::zero(),
+            sampled_values_hash: [QM31::zero(); 2],
+            first_layer_commitment: zero_hash,
+            inner_layer_commitments: vec![zero_hash; shape.n_inner_layers],
+            last_layer_coeffs_hash: [QM31::zero(); 2],
+            proof_of_work: 0,
+            max_first_layer_column_log_size,
+            decommit: shape.trees.map(|(depth, n_columns)| {
+                DelegatedDecommitHints::placeholder(n_queries, depth, n_columns)
+            }),
+            first_layer: DelegatedFirstLayerHints::placeholder(
+                n_queries,
+                &shape.first_layer_log_sizes,
+            ),
+            inner_layers: DelegatedInnerLayersHints::placeholder(
+                n_queries,
+                max_first_layer_column_log_size,
+                shape.n_inner_layers,
+            ),
+        }
+    }
+
+    /// Returns the shape of the proof from which the hints are computed.
+    pub fn shape(&self) -> DelegatedProofShape {
+        DelegatedProofShape {
+            log_size_plonk: self.log_size_plonk,
+            log_size_poseidon: self.log_size_poseidon,
+            trees: std::array::from_fn(|round| {
+                let proof = &self.decommit[round].proofs[0];
+                (proof.depth, proof.columns.len())
+            }),
+            first_layer_log_sizes: self.first_layer.merkle_proofs[0]
+                .self_columns
+                .keys()
+                .map(|&log_size| log_size as u32)
+                .collect(),
+            n_inner_layers: self.inner_layers.merkle_proofs.len(),
+        }
+    }
+}
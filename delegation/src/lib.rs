@@ -3,3 +3,5 @@ pub mod script;
 pub mod decommit;
 
 pub mod folding;
+
+pub mod hints;
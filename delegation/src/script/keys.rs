@@ -0,0 +1,54 @@
+//! The entries that the delegation parts store in the LDM, which the last verifier reads as its
+//! inputs. The families of entries are indexed with `LDMKey::indexed`.
+
+use recursive_stwo_bitcoin_dsl::basic::sha256_hash::Sha256HashBar;
+use recursive_stwo_bitcoin_dsl::ldm::LDMKey;
+use recursive_stwo_primitives::fields::qm31::QM31Bar;
+
+pub const DELEGATED_Z: LDMKey<QM31Bar> = LDMKey::new("delegated_z", "qm31");
+pub const DELEGATED_ALPHA: LDMKey<QM31Bar> = LDMKey::new("delegated_alpha", "qm31");
+pub const DELEGATED_PLONK_TOTAL_SUM: LDMKey<QM31Bar> =
+    LDMKey::new("delegated_plonk_total_sum", "qm31");
+pub const DELEGATED_POSEIDON_TOTAL_SUM: LDMKey<QM31Bar> =
+    LDMKey::new("delegated_poseidon_total_sum", "qm31");
+pub const DELEGATED_RANDOM_COEFF: LDMKey<QM31Bar> = LDMKey::new("delegated_random_coeff", "qm31");
+pub const DELEGATED_OODS_T: LDMKey<QM31Bar> = LDMKey::new("delegated_oods_t", "qm31");
+/// Indexed by 0 and 1, the two halves of the hash of the sampled values.
+pub const DELEGATED_SAMPLED_VALUE_HASH: LDMKey<QM31Bar> =
+    LDMKey::new("delegated_sampled_value_hash", "qm31");
+pub const DELEGATED_AFTER_SAMPLED_VALUES_RANDOM_COEFF: LDMKey<QM31Bar> =
+    LDMKey::new("delegated_after_sampled_values_random_coeff", "qm31");
+/// Indexed by 1 and 2, the queries packed four by four.
+pub const DELEGATED_QUERIES_FELT: LDMKey<QM31Bar> = LDMKey::new("delegated_queries_felt", "qm31");
+pub const DELEGATED_FIRST_LAYER_COMMIT: LDMKey<Sha256HashBar> =
+    LDMKey::new("delegated_first_layer_commit", "sha256_hash");
+pub const DELEGATED_FIRST_LAYER_FOLDING_ALPHA: LDMKey<QM31Bar> =
+    LDMKey::new("delegated_first_layer_folding_alpha", "qm31");
+/// Indexed by the inner layer.
+pub const DELEGATED_INNER_LAYERS_COMMIT: LDMKey<Sha256HashBar> =
+    LDMKey::new("delegated_inner_layers_commit", "sha256_hash");
+/// Indexed by the inner layer.
+pub const DELEGATED_INNER_LAYERS_FOLDING_ALPHA: LDMKey<QM31Bar> =
+    LDMKey::new("delegated_inner_layers_folding_alpha", "qm31");
+pub const DELEGATED_INTERACTION_COMMIT: LDMKey<Sha256HashBar> =
+    LDMKey::new("delegated_interaction_commit", "sha256_hash");
+pub const DELEGATED_COMPOSITION_COMMIT: LDMKey<Sha256HashBar> =
+    LDMKey::new("delegated_composition_commit", "sha256_hash");
+/// Indexed by the position in the input elements.
+pub const DELEGATED_DECOMMIT_PREPROCESSED_INPUT: LDMKey<QM31Bar> =
+    LDMKey::new("delegated_decommit_preprocessed_input", "qm31");
+/// Indexed by the position in the input elements.
+pub const DELEGATED_DECOMMIT_TRACE_INPUT: LDMKey<QM31Bar> =
+    LDMKey::new("delegated_decommit_trace_input", "qm31");
+/// Indexed by the position in the input elements.
+pub const DELEGATED_DECOMMIT_INTERACTION_INPUT: LDMKey<QM31Bar> =
+    LDMKey::new("delegated_decommit_interaction_input", "qm31");
+/// Indexed by the position in the input elements.
+pub const DELEGATED_DECOMMIT_COMPOSITION_INPUT: LDMKey<QM31Bar> =
+    LDMKey::new("delegated_decommit_composition_input", "qm31");
+/// Indexed by the position in the input elements.
+pub const DELEGATED_FIRST_LAYER_INPUT: LDMKey<QM31Bar> =
+    LDMKey::new("delegated_first_layer_input", "qm31");
+/// Indexed by the inner layer and then by the position in its input elements.
+pub const DELEGATED_INNER_LAYERS_INPUT: LDMKey<QM31Bar> =
+    LDMKey::new("delegated_inner_layers_input", "qm31");
@@ -1,3 +1,4 @@
+use crate::script::keys::*;
 use circle_plonk_dsl_hints::{AnswerHints, FiatShamirHints};
 use circle_plonk_dsl_last_answer::data_structures::{LastDecommitHints, LastDecommitInput};
 use circle_plonk_dsl_last_fiat_shamir::LastFiatShamirInput;
@@ -5,6 +6,8 @@ use circle_plonk_dsl_last_folding::data_structures::merkle_proofs::{
     LastFirstLayerHints, LastInnerLayersHints,
 };
 use num_traits::One;
+use recursive_stwo_bitcoin_dsl::ldm::LDMKey;
+use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use stwo_prover::core::fields::qm31::QM31;
 use stwo_prover::core::pcs::PcsConfig;
 use stwo_prover::core::vcs::sha256_poseidon31_merkle::{
@@ -22,6 +25,8 @@ pub mod part4;
 
 pub mod part5;
 
+pub mod keys;
+
 pub fn compute_delegation_inputs(
     proof: &PlonkWithPoseidonProof<Sha256Poseidon31MerkleHasher>,
     config: PcsConfig,
@@ -135,42 +140,42 @@ pub fn compute_delegation_inputs(
     inputs
 }
 
-pub fn compute_input_labels() -> Vec<String> {
+pub fn compute_input_labels() -> Vec<LDMKey<QM31Bar>> {
     let mut v = vec![];
-    v.push("delegated_oods_t".to_string());
-    v.push("delegated_sampled_value_hash_0".to_string());
-    v.push("delegated_sampled_value_hash_1".to_string());
-    v.push("delegated_plonk_total_sum".to_string());
-    v.push("delegated_poseidon_total_sum".to_string());
-    v.push("delegated_z".to_string());
-    v.push("delegated_alpha".to_string());
-    v.push("delegated_random_coeff".to_string());
-    v.push("delegated_after_sampled_values_random_coeff".to_string());
-    v.push("delegated_queries_felt_1".to_string());
-    v.push("delegated_queries_felt_2".to_string());
-
-    v.push("delegated_first_layer_folding_alpha".to_string());
+    v.push(DELEGATED_OODS_T.clone());
+    v.push(DELEGATED_SAMPLED_VALUE_HASH.indexed(0));
+    v.push(DELEGATED_SAMPLED_VALUE_HASH.indexed(1));
+    v.push(DELEGATED_PLONK_TOTAL_SUM.clone());
+    v.push(DELEGATED_POSEIDON_TOTAL_SUM.clone());
+    v.push(DELEGATED_Z.clone());
+    v.push(DELEGATED_ALPHA.clone());
+    v.push(DELEGATED_RANDOM_COEFF.clone());
+    v.push(DELEGATED_AFTER_SAMPLED_VALUES_RANDOM_COEFF.clone());
+    v.push(DELEGATED_QUERIES_FELT.indexed(1));
+    v.push(DELEGATED_QUERIES_FELT.indexed(2));
+
+    v.push(DELEGATED_FIRST_LAYER_FOLDING_ALPHA.clone());
     for i in 0..10 {
-        v.push(format!("delegated_inner_layers_folding_alpha_{}", i));
+        v.push(DELEGATED_INNER_LAYERS_FOLDING_ALPHA.indexed(i));
     }
     for i in 0..16 {
-        v.push(format!("delegated_decommit_preprocessed_input_{}", i));
+        v.push(DELEGATED_DECOMMIT_PREPROCESSED_INPUT.indexed(i));
     }
     for i in 0..16 {
-        v.push(format!("delegated_decommit_trace_input_{}", i));
+        v.push(DELEGATED_DECOMMIT_TRACE_INPUT.indexed(i));
     }
     for i in 0..16 {
-        v.push(format!("delegated_decommit_interaction_input_{}", i));
+        v.push(DELEGATED_DECOMMIT_INTERACTION_INPUT.indexed(i));
     }
     for i in 0..8 {
-        v.push(format!("delegated_decommit_composition_input_{}", i));
+        v.push(DELEGATED_DECOMMIT_COMPOSITION_INPUT.indexed(i));
     }
     for i in 0..32 {
-        v.push(format!("delegated_first_layer_input_{}", i));
+        v.push(DELEGATED_FIRST_LAYER_INPUT.indexed(i));
     }
     for i in 0..10 {
         for j in 0..16 {
-            v.push(format!("delegated_inner_layers_input_{}_{}", i, j));
+            v.push(DELEGATED_INNER_LAYERS_INPUT.indexed(i).indexed(j));
         }
     }
     v
@@ -178,26 +183,20 @@ pub fn compute_input_labels() -> Vec<String> {
 
 #[cfg(test)]
 mod test {
-    use crate::folding::{DelegatedFirstLayerHints, DelegatedInnerLayersHints};
+    use crate::hints::DelegatedProofHints;
+    use crate::script::keys::*;
     use crate::script::{compute_delegation_inputs, part1, part2, part3, part4, part5};
-    use circle_plonk_dsl_hints::{AnswerHints, FiatShamirHints};
     use itertools::Itertools;
-    use num_traits::One;
     use recursive_stwo_bitcoin_dsl::bar::AllocBar;
     use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
+    use recursive_stwo_bitcoin_dsl::compiler::Compiler;
     use recursive_stwo_bitcoin_dsl::ldm::LDM;
     use recursive_stwo_bitcoin_dsl::test_program;
     use recursive_stwo_bitcoin_dsl::treepp::*;
-    use recursive_stwo_primitives::fields::qm31::QM31Bar;
-    use stwo_prover::core::fields::qm31::QM31;
     use stwo_prover::core::fri::FriConfig;
     use stwo_prover::core::pcs::PcsConfig;
-    use stwo_prover::core::vcs::sha256_poseidon31_merkle::{
-        Sha256Poseidon31MerkleChannel, Sha256Poseidon31MerkleHasher,
-    };
-    use stwo_prover::examples::plonk_with_poseidon::air::{
-        verify_plonk_with_poseidon, PlonkWithPoseidonProof,
-    };
+    use stwo_prover::core::vcs::sha256_poseidon31_merkle::Sha256Poseidon31MerkleHasher;
+    use stwo_prover::examples::plonk_with_poseidon::air::PlonkWithPoseidonProof;
 
     #[test]
     fn test_delegated() {
@@ -208,39 +207,11 @@ mod test {
             fri_config: FriConfig::new(7, 9, 8),
         };
 
-        verify_plonk_with_poseidon::<Sha256Poseidon31MerkleChannel>(
-            proof.clone(),
-            config,
-            &[
-                (1, QM31::one()),
-                (2, QM31::from_u32_unchecked(0, 1, 0, 0)),
-                (3, QM31::from_u32_unchecked(0, 0, 1, 0)),
-            ],
-        )
-        .unwrap();
-
-        let fiat_shamir_hints = FiatShamirHints::<Sha256Poseidon31MerkleChannel>::new(
-            &proof,
-            config,
-            &[
-                (1, QM31::one()),
-                (2, QM31::from_u32_unchecked(0, 1, 0, 0)),
-                (3, QM31::from_u32_unchecked(0, 0, 1, 0)),
-            ],
-        );
-        let fri_answer_hints = AnswerHints::compute(&fiat_shamir_hints, &proof);
-        let first_layer_hints =
-            DelegatedFirstLayerHints::compute(&fiat_shamir_hints, &fri_answer_hints, &proof);
-        let inner_layers_hints = DelegatedInnerLayersHints::compute(
-            &first_layer_hints.folded_evals_by_column,
-            &fiat_shamir_hints,
-            &proof,
-        );
+        let hints = DelegatedProofHints::compute(&proof, config).unwrap();
 
         let mut ldm_delegated = LDM::new();
 
-        let cs =
-            part1::generate_cs(&fiat_shamir_hints, &proof, config, &mut ldm_delegated).unwrap();
+        let cs = part1::generate_cs(&hints, config, &mut ldm_delegated).unwrap();
         test_program(
             cs,
             script! {
@@ -249,13 +220,7 @@ mod test {
         )
         .unwrap();
 
-        let cs = part2::generate_cs(
-            &fiat_shamir_hints,
-            &proof,
-            &first_layer_hints,
-            &mut ldm_delegated,
-        )
-        .unwrap();
+        let cs = part2::generate_cs(&hints, &mut ldm_delegated).unwrap();
         test_program(
             cs,
             script! {
@@ -264,8 +229,7 @@ mod test {
         )
         .unwrap();
 
-        let cs = part3::generate_cs(&fiat_shamir_hints, &inner_layers_hints, &mut ldm_delegated)
-            .unwrap();
+        let cs = part3::generate_cs(&hints, &mut ldm_delegated).unwrap();
         test_program(
             cs,
             script! {
@@ -274,8 +238,7 @@ mod test {
         )
         .unwrap();
 
-        let cs = part4::generate_cs(&fiat_shamir_hints, &inner_layers_hints, &mut ldm_delegated)
-            .unwrap();
+        let cs = part4::generate_cs(&hints, &mut ldm_delegated).unwrap();
         test_program(
             cs,
             script! {
@@ -284,8 +247,7 @@ mod test {
         )
         .unwrap();
 
-        let cs = part5::generate_cs(&fiat_shamir_hints, &inner_layers_hints, &mut ldm_delegated)
-            .unwrap();
+        let cs = part5::generate_cs(&hints, &mut ldm_delegated).unwrap();
         test_program(
             cs,
             script! {
@@ -296,7 +258,7 @@ mod test {
     }
 
     #[test]
-    fn test_input_elements() {
+    fn test_placeholder_hints() {
         let proof: PlonkWithPoseidonProof<Sha256Poseidon31MerkleHasher> =
             bincode::deserialize(include_bytes!("../../../data/hybrid_hash.bin")).unwrap();
         let config = PcsConfig {
@@ -304,39 +266,38 @@ mod test {
             fri_config: FriConfig::new(7, 9, 8),
         };
 
-        verify_plonk_with_poseidon::<Sha256Poseidon31MerkleChannel>(
-            proof.clone(),
-            config,
-            &[
-                (1, QM31::one()),
-                (2, QM31::from_u32_unchecked(0, 1, 0, 0)),
-                (3, QM31::from_u32_unchecked(0, 0, 1, 0)),
-            ],
-        )
-        .unwrap();
+        let hints = DelegatedProofHints::compute(&proof, config).unwrap();
+        let placeholder = DelegatedProofHints::placeholder(config, &hints.shape());
+        assert_eq!(placeholder.shape(), hints.shape());
 
-        let fiat_shamir_hints = FiatShamirHints::<Sha256Poseidon31MerkleChannel>::new(
-            &proof,
-            config,
-            &[
-                (1, QM31::one()),
-                (2, QM31::from_u32_unchecked(0, 1, 0, 0)),
-                (3, QM31::from_u32_unchecked(0, 0, 1, 0)),
-            ],
-        );
-        let fri_answer_hints = AnswerHints::compute(&fiat_shamir_hints, &proof);
-        let first_layer_hints =
-            DelegatedFirstLayerHints::compute(&fiat_shamir_hints, &fri_answer_hints, &proof);
-        let inner_layers_hints = DelegatedInnerLayersHints::compute(
-            &first_layer_hints.folded_evals_by_column,
-            &fiat_shamir_hints,
-            &proof,
-        );
+        let compile_all = |hints: &DelegatedProofHints| {
+            let mut ldm_delegated = LDM::new();
+            [
+                part1::generate_cs(hints, config, &mut ldm_delegated).unwrap(),
+                part2::generate_cs(hints, &mut ldm_delegated).unwrap(),
+                part3::generate_cs(hints, &mut ldm_delegated).unwrap(),
+                part4::generate_cs(hints, &mut ldm_delegated).unwrap(),
+                part5::generate_cs(hints, &mut ldm_delegated).unwrap(),
+            ]
+            .map(|cs| Compiler::compile(cs).unwrap().script)
+        };
+        assert_eq!(compile_all(&placeholder), compile_all(&hints));
+    }
+
+    #[test]
+    fn test_input_elements() {
+        let proof: PlonkWithPoseidonProof<Sha256Poseidon31MerkleHasher> =
+            bincode::deserialize(include_bytes!("../../../data/hybrid_hash.bin")).unwrap();
+        let config = PcsConfig {
+            pow_bits: 28,
+            fri_config: FriConfig::new(7, 9, 8),
+        };
+
+        let hints = DelegatedProofHints::compute(&proof, config).unwrap();
 
         let mut ldm_delegated = LDM::new();
 
-        let cs =
-            part1::generate_cs(&fiat_shamir_hints, &proof, config, &mut ldm_delegated).unwrap();
+        let cs = part1::generate_cs(&hints, config, &mut ldm_delegated).unwrap();
         test_program(
             cs,
             script! {
@@ -345,97 +306,93 @@ mod test {
         )
         .unwrap();
 
-        let _ = part2::generate_cs(
-            &fiat_shamir_hints,
-            &proof,
-            &first_layer_hints,
-            &mut ldm_delegated,
-        )
-        .unwrap();
-        let _ = part3::generate_cs(&fiat_shamir_hints, &inner_layers_hints, &mut ldm_delegated)
-            .unwrap();
-        let _ = part4::generate_cs(&fiat_shamir_hints, &inner_layers_hints, &mut ldm_delegated)
-            .unwrap();
-        let _ = part5::generate_cs(&fiat_shamir_hints, &inner_layers_hints, &mut ldm_delegated)
-            .unwrap();
+        let _ = part2::generate_cs(&hints, &mut ldm_delegated).unwrap();
+        let _ = part3::generate_cs(&hints, &mut ldm_delegated).unwrap();
+        let _ = part4::generate_cs(&hints, &mut ldm_delegated).unwrap();
+        let _ = part5::generate_cs(&hints, &mut ldm_delegated).unwrap();
 
         let cs = BitcoinSystemRef::new_ref();
         ldm_delegated.init(&cs).unwrap();
 
         let mut input_elements = vec![];
-        let oods_t: QM31Bar = ldm_delegated.read("delegated_oods_t").unwrap();
+        let oods_t = ldm_delegated.read_key(&DELEGATED_OODS_T).unwrap();
         input_elements.push(oods_t.value().unwrap());
-        let sampled_value_hash_0: QM31Bar = ldm_delegated
-            .read("delegated_sampled_value_hash_0")
+        let sampled_value_hash_0 = ldm_delegated
+            .read_key(&DELEGATED_SAMPLED_VALUE_HASH.indexed(0))
             .unwrap();
         input_elements.push(sampled_value_hash_0.value().unwrap());
-        let sampled_value_hash_1: QM31Bar = ldm_delegated
-            .read("delegated_sampled_value_hash_1")
+        let sampled_value_hash_1 = ldm_delegated
+            .read_key(&DELEGATED_SAMPLED_VALUE_HASH.indexed(1))
             .unwrap();
         input_elements.push(sampled_value_hash_1.value().unwrap());
-        let plonk_total_sum: QM31Bar = ldm_delegated.read("delegated_plonk_total_sum").unwrap();
+        let plonk_total_sum = ldm_delegated.read_key(&DELEGATED_PLONK_TOTAL_SUM).unwrap();
         input_elements.push(plonk_total_sum.value().unwrap());
-        let poseidon_total_sum: QM31Bar =
-            ldm_delegated.read("delegated_poseidon_total_sum").unwrap();
+        let poseidon_total_sum = ldm_delegated
+            .read_key(&DELEGATED_POSEIDON_TOTAL_SUM)
+            .unwrap();
         input_elements.push(poseidon_total_sum.value().unwrap());
-        let z: QM31Bar = ldm_delegated.read("delegated_z").unwrap();
+        let z = ldm_delegated.read_key(&DELEGATED_Z).unwrap();
         input_elements.push(z.value().unwrap());
-        let alpha: QM31Bar = ldm_delegated.read("delegated_alpha").unwrap();
+        let alpha = ldm_delegated.read_key(&DELEGATED_ALPHA).unwrap();
         input_elements.push(alpha.value().unwrap());
-        let random_coeff: QM31Bar = ldm_delegated.read("delegated_random_coeff").unwrap();
+        let random_coeff = ldm_delegated.read_key(&DELEGATED_RANDOM_COEFF).unwrap();
         input_elements.push(random_coeff.value().unwrap());
-        let after_sampled_values_random_coeff: QM31Bar = ldm_delegated
-            .read("delegated_after_sampled_values_random_coeff")
+        let after_sampled_values_random_coeff = ldm_delegated
+            .read_key(&DELEGATED_AFTER_SAMPLED_VALUES_RANDOM_COEFF)
             .unwrap();
         input_elements.push(after_sampled_values_random_coeff.value().unwrap());
-        let queries_felt_1: QM31Bar = ldm_delegated.read("delegated_queries_felt_1").unwrap();
+        let queries_felt_1 = ldm_delegated
+            .read_key(&DELEGATED_QUERIES_FELT.indexed(1))
+            .unwrap();
         input_elements.push(queries_felt_1.value().unwrap());
-        let queries_felt_2: QM31Bar = ldm_delegated.read("delegated_queries_felt_2").unwrap();
+        let queries_felt_2 = ldm_delegated
+            .read_key(&DELEGATED_QUERIES_FELT.indexed(2))
+            .unwrap();
         input_elements.push(queries_felt_2.value().unwrap());
-        let first_layer_folding_alpha: QM31Bar = ldm_delegated
-            .read("delegated_first_layer_folding_alpha")
+        let first_layer_folding_alpha = ldm_delegated
+            .read_key(&DELEGATED_FIRST_LAYER_FOLDING_ALPHA)
             .unwrap();
         input_elements.push(first_layer_folding_alpha.value().unwrap());
         for i in 0..proof.stark_proof.fri_proof.inner_layers.len() {
-            let inner_layer_folding_alpha: QM31Bar = ldm_delegated
-                .read(format!("delegated_inner_layers_folding_alpha_{}", i))
+            let inner_layer_folding_alpha = ldm_delegated
+                .read_key(&DELEGATED_INNER_LAYERS_FOLDING_ALPHA.indexed(i))
                 .unwrap();
             input_elements.push(inner_layer_folding_alpha.value().unwrap());
         }
         for i in 0..16 {
-            let decommit_item: QM31Bar = ldm_delegated
-                .read(format!("delegated_decommit_preprocessed_input_{}", i))
+            let decommit_item = ldm_delegated
+                .read_key(&DELEGATED_DECOMMIT_PREPROCESSED_INPUT.indexed(i))
                 .unwrap();
             input_elements.push(decommit_item.value().unwrap());
         }
         for i in 0..16 {
-            let decommit_item: QM31Bar = ldm_delegated
-                .read(format!("delegated_decommit_trace_input_{}", i))
+            let decommit_item = ldm_delegated
+                .read_key(&DELEGATED_DECOMMIT_TRACE_INPUT.indexed(i))
                 .unwrap();
             input_elements.push(decommit_item.value().unwrap());
         }
         for i in 0..16 {
-            let decommit_item: QM31Bar = ldm_delegated
-                .read(format!("delegated_decommit_interaction_input_{}", i))
+            let decommit_item = ldm_delegated
+                .read_key(&DELEGATED_DECOMMIT_INTERACTION_INPUT.indexed(i))
                 .unwrap();
             input_elements.push(decommit_item.value().unwrap());
         }
         for i in 0..8 {
-            let decommit_item: QM31Bar = ldm_delegated
-                .read(format!("delegated_decommit_composition_input_{}", i))
+            let decommit_item = ldm_delegated
+                .read_key(&DELEGATED_DECOMMIT_COMPOSITION_INPUT.indexed(i))
                 .unwrap();
             input_elements.push(decommit_item.value().unwrap());
         }
         for i in 0..32 {
-            let fri_item: QM31Bar = ldm_delegated
-                .read(format!("delegated_first_layer_input_{}", i))
+            let fri_item = ldm_delegated
+                .read_key(&DELEGATED_FIRST_LAYER_INPUT.indexed(i))
                 .unwrap();
             input_elements.push(fri_item.value().unwrap());
         }
         for i in 0..10 {
             for j in 0..16 {
-                let fri_item: QM31Bar = ldm_delegated
-                    .read(format!("delegated_inner_layers_input_{}_{}", i, j))
+                let fri_item = ldm_delegated
+                    .read_key(&DELEGATED_INNER_LAYERS_INPUT.indexed(i).indexed(j))
                     .unwrap();
                 input_elements.push(fri_item.value().unwrap());
             }
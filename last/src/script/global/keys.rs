@@ -0,0 +1,92 @@
+//! The entries that the global parts of the last verifier store in the LDM, which is shared by
+//! all the parts. The families of entries are indexed with `LDMKey::indexed`.
+
+use recursive_stwo_bitcoin_dsl::basic::sha256_hash::Sha256HashBar;
+use recursive_stwo_bitcoin_dsl::ldm::LDMKey;
+use recursive_stwo_primitives::channel::sha256::Sha256ChannelBar;
+use recursive_stwo_primitives::fields::m31::M31Bar;
+use recursive_stwo_primitives::fields::qm31::QM31Bar;
+use recursive_stwo_primitives::quotient::ColumnLineCoeffBar;
+
+pub const PREPROCESSED_COMMITMENT_VAR: LDMKey<Sha256HashBar> =
+    LDMKey::new("preprocessed_commitment_var", "sha256_hash");
+pub const TRACE_COMMITMENT_VAR: LDMKey<Sha256HashBar> =
+    LDMKey::new("trace_commitment_var", "sha256_hash");
+pub const Z: LDMKey<QM31Bar> = LDMKey::new("z", "qm31");
+pub const ALPHA: LDMKey<QM31Bar> = LDMKey::new("alpha", "qm31");
+pub const CHANNEL_VAR_AFTER_Z_AND_ALPHA: LDMKey<Sha256ChannelBar> =
+    LDMKey::new("channel_var_after_z_and_alpha", "sha256_channel");
+
+/// The accumulator of the inputs from the delegation, indexed by the number of parts that have
+/// accumulated into it.
+pub const INPUT_ACC_ALPHA: LDMKey<QM31Bar> = LDMKey::new("input_acc_alpha", "qm31");
+pub const INPUT_ACC_CUR: LDMKey<QM31Bar> = LDMKey::new("input_acc_cur", "qm31");
+pub const INPUT_ACC_SUM: LDMKey<QM31Bar> = LDMKey::new("input_acc_sum", "qm31");
+
+pub const PLONK_TOTAL_SUM: LDMKey<QM31Bar> = LDMKey::new("plonk_total_sum", "qm31");
+pub const INTERACTION_COMMITMENT_VAR: LDMKey<Sha256HashBar> =
+    LDMKey::new("interaction_commitment_var", "sha256_hash");
+pub const RANDOM_COEFF: LDMKey<QM31Bar> = LDMKey::new("random_coeff", "qm31");
+pub const COMPOSITION_COMMITMENT_VAR: LDMKey<Sha256HashBar> =
+    LDMKey::new("composition_commitment_var", "sha256_hash");
+pub const OODS_T: LDMKey<QM31Bar> = LDMKey::new("oods_t", "qm31");
+
+/// The sampled values of the columns, all of which are QM31. A column of the traces is committed
+/// as its four coordinate columns, whose sampled values are indexed, while the key itself holds
+/// the sampled value of the column combined from them.
+pub const PREPROCESSED_A_WIRE: LDMKey<QM31Bar> = LDMKey::new("preprocessed_a_wire", "qm31");
+pub const PREPROCESSED_B_WIRE: LDMKey<QM31Bar> = LDMKey::new("preprocessed_b_wire", "qm31");
+pub const PREPROCESSED_C_WIRE: LDMKey<QM31Bar> = LDMKey::new("preprocessed_c_wire", "qm31");
+pub const PREPROCESSED_OP1: LDMKey<QM31Bar> = LDMKey::new("preprocessed_op1", "qm31");
+pub const PREPROCESSED_OP2: LDMKey<QM31Bar> = LDMKey::new("preprocessed_op2", "qm31");
+pub const PREPROCESSED_OP3: LDMKey<QM31Bar> = LDMKey::new("preprocessed_op3", "qm31");
+pub const PREPROCESSED_OP4: LDMKey<QM31Bar> = LDMKey::new("preprocessed_op4", "qm31");
+pub const PREPROCESSED_MULT_C: LDMKey<QM31Bar> = LDMKey::new("preprocessed_mult_c", "qm31");
+pub const TRACE_A_VAL: LDMKey<QM31Bar> = LDMKey::new("trace_a_val", "qm31");
+pub const TRACE_B_VAL: LDMKey<QM31Bar> = LDMKey::new("trace_b_val", "qm31");
+pub const TRACE_C_VAL: LDMKey<QM31Bar> = LDMKey::new("trace_c_val", "qm31");
+pub const INTERACTION_PREV: LDMKey<QM31Bar> = LDMKey::new("interaction_prev", "qm31");
+pub const INTERACTION: LDMKey<QM31Bar> = LDMKey::new("interaction", "qm31");
+pub const COMPOSITION: LDMKey<QM31Bar> = LDMKey::new("composition", "qm31");
+
+pub const AFTER_SAMPLED_VALUES_RANDOM_COEFF: LDMKey<QM31Bar> =
+    LDMKey::new("after_sampled_values_random_coeff", "qm31");
+pub const FIRST_LAYER_COMMITMENT: LDMKey<Sha256HashBar> =
+    LDMKey::new("first_layer_commitment", "sha256_hash");
+pub const FIRST_LAYER_ALPHA: LDMKey<QM31Bar> = LDMKey::new("first_layer_alpha", "qm31");
+pub const INNER_LAYER_COMMITMENT: LDMKey<Sha256HashBar> =
+    LDMKey::new("inner_layer_commitment", "sha256_hash");
+pub const INNER_LAYER_ALPHA: LDMKey<QM31Bar> = LDMKey::new("inner_layer_alpha", "qm31");
+pub const INNER_LAYER_ALPHA_1_SQUARED: LDMKey<QM31Bar> =
+    LDMKey::new("inner_layer_alpha_1_squared", "qm31");
+pub const LAST_LAYER_POLY: LDMKey<QM31Bar> = LDMKey::new("last_layer_poly", "qm31");
+/// The queries drawn from the channel, at the log size of the first layer.
+pub const QUERIES: LDMKey<M31Bar> = LDMKey::new("query", "m31");
+
+/// The accumulator of the composition, after the part with this number.
+pub const EVAL_ACC_ACCUMULATION_PART3: LDMKey<QM31Bar> =
+    LDMKey::new("eval_acc_accumulation_part3", "qm31");
+pub const EVAL_ACC_ACCUMULATION_PART4: LDMKey<QM31Bar> =
+    LDMKey::new("eval_acc_accumulation_part4", "qm31");
+pub const EVAL_ACC_ACCUMULATION_PART6: LDMKey<QM31Bar> =
+    LDMKey::new("eval_acc_accumulation_part6", "qm31");
+pub const ARITH_SUM_PART_5: LDMKey<QM31Bar> = LDMKey::new("arith_sum_part_5", "qm31");
+
+pub const OODS_X: LDMKey<QM31Bar> = LDMKey::new("oods_x", "qm31");
+pub const OODS_Y: LDMKey<QM31Bar> = LDMKey::new("oods_y", "qm31");
+pub const COSET_VANISHING_X_PART7: LDMKey<QM31Bar> = LDMKey::new("coset_vanishing_x_part7", "qm31");
+pub const COSET_VANISHING_X_PART8: LDMKey<QM31Bar> = LDMKey::new("coset_vanishing_x_part8", "qm31");
+pub const COSET_VANISHING_X_INV: LDMKey<QM31Bar> = LDMKey::new("coset_vanishing_x_inv", "qm31");
+pub const RELATION_NUM: LDMKey<QM31Bar> = LDMKey::new("relation_num", "qm31");
+pub const RELATION_DENOM: LDMKey<QM31Bar> = LDMKey::new("relation_denom", "qm31");
+pub const OODS_SHIFTED_X: LDMKey<QM31Bar> = LDMKey::new("oods_shifted_x", "qm31");
+pub const OODS_SHIFTED_Y: LDMKey<QM31Bar> = LDMKey::new("oods_shifted_y", "qm31");
+
+/// The randomizers of the line coefficients, indexed by the number of parts that have used them.
+pub const LINE_COEFF_RANDOMIZER_26_ALPHA: LDMKey<QM31Bar> =
+    LDMKey::new("line_coeff_randomizer_26_alpha", "qm31");
+pub const LINE_COEFF_RANDOMIZER_28_ALPHA: LDMKey<QM31Bar> =
+    LDMKey::new("line_coeff_randomizer_28_alpha", "qm31");
+/// The line coefficients of the columns, indexed by the name of the sampled value of the column.
+pub const COLUMN_LINE_COEFF: LDMKey<ColumnLineCoeffBar> =
+    LDMKey::new("column_line_coeff", "column_line_coeff");
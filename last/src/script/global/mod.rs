@@ -1,3 +1,4 @@
+pub mod keys;
 pub mod part10_logup;
 pub mod part11_point_shift;
 pub mod part12_line_coeffs;
@@ -1,25 +1,28 @@
+use crate::script::global::keys::{
+    ALPHA, PREPROCESSED_A_WIRE, PREPROCESSED_B_WIRE, PREPROCESSED_C_WIRE, PREPROCESSED_MULT_C,
+    RELATION_DENOM, RELATION_NUM, TRACE_A_VAL, TRACE_B_VAL, TRACE_C_VAL, Z,
+};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::ldm::LDM;
-use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
 
 pub fn generate_cs(ldm: &mut LDM) -> Result<BitcoinSystemRef> {
     let cs = BitcoinSystemRef::new_ref();
     ldm.init(&cs)?;
 
-    let trace_a_val: QM31Bar = ldm.read("trace_a_val")?;
-    let trace_b_val: QM31Bar = ldm.read("trace_b_val")?;
-    let trace_c_val: QM31Bar = ldm.read("trace_c_val")?;
+    let trace_a_val = ldm.read_key(&TRACE_A_VAL)?;
+    let trace_b_val = ldm.read_key(&TRACE_B_VAL)?;
+    let trace_c_val = ldm.read_key(&TRACE_C_VAL)?;
 
-    let preprocessed_a_wire: QM31Bar = ldm.read("preprocessed_a_wire")?;
-    let preprocessed_b_wire: QM31Bar = ldm.read("preprocessed_b_wire")?;
-    let preprocessed_c_wire: QM31Bar = ldm.read("preprocessed_c_wire")?;
-    let preprocessed_mult_c: QM31Bar = ldm.read("preprocessed_mult_c")?;
+    let preprocessed_a_wire = ldm.read_key(&PREPROCESSED_A_WIRE)?;
+    let preprocessed_b_wire = ldm.read_key(&PREPROCESSED_B_WIRE)?;
+    let preprocessed_c_wire = ldm.read_key(&PREPROCESSED_C_WIRE)?;
+    let preprocessed_mult_c = ldm.read_key(&PREPROCESSED_MULT_C)?;
 
-    let z: QM31Bar = ldm.read("z")?;
-    let alpha: QM31Bar = ldm.read("alpha")?;
+    let z = ldm.read_key(&Z)?;
+    let alpha = ldm.read_key(&ALPHA)?;
 
     let table = TableBar::new_constant(&cs, ())?;
 
@@ -37,8 +40,8 @@ pub fn generate_cs(ldm: &mut LDM) -> Result<BitcoinSystemRef> {
     let abc_num = &(&ab_num * (&table, &c_denom)) + &(&c_num * (&table, &ab_denom));
     let abc_denom = &ab_denom * (&table, &c_denom);
 
-    ldm.write("relation_num", &abc_num)?;
-    ldm.write("relation_denom", &abc_denom)?;
+    ldm.write_key(&RELATION_NUM, &abc_num)?;
+    ldm.write_key(&RELATION_DENOM, &abc_denom)?;
 
     ldm.save()?;
     Ok(cs)
@@ -1,3 +1,10 @@
+use crate::script::global::keys::{
+    AFTER_SAMPLED_VALUES_RANDOM_COEFF, COMPOSITION, COSET_VANISHING_X_INV,
+    EVAL_ACC_ACCUMULATION_PART6, INTERACTION, INTERACTION_PREV, LINE_COEFF_RANDOMIZER_26_ALPHA,
+    LINE_COEFF_RANDOMIZER_28_ALPHA, OODS_SHIFTED_X, OODS_SHIFTED_Y, OODS_X, OODS_Y,
+    PLONK_TOTAL_SUM, RANDOM_COEFF, RELATION_DENOM, RELATION_NUM,
+};
+use crate::script::hints::proof::LastProofHints;
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::{AllocBar, Bar};
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
@@ -10,32 +17,27 @@ use recursive_stwo_primitives::fields::table::TableBar;
 use recursive_stwo_primitives::quotient::LineCoeffRandomizerBar;
 use stwo_prover::core::fields::m31::M31;
 use stwo_prover::core::poly::circle::CanonicCoset;
-use stwo_prover::core::vcs::sha256_merkle::Sha256MerkleHasher;
-use stwo_prover::examples::plonk_without_poseidon::air::PlonkWithoutPoseidonProof;
 
-pub fn generate_cs(
-    proof: &PlonkWithoutPoseidonProof<Sha256MerkleHasher>,
-    ldm: &mut LDM,
-) -> Result<BitcoinSystemRef> {
+pub fn generate_cs(hints: &LastProofHints, ldm: &mut LDM) -> Result<BitcoinSystemRef> {
     let cs = BitcoinSystemRef::new_ref();
     ldm.init(&cs)?;
 
-    let interaction_prev: QM31Bar = ldm.read("interaction_prev")?;
-    let interaction: QM31Bar = ldm.read("interaction")?;
+    let interaction_prev = ldm.read_key(&INTERACTION_PREV)?;
+    let interaction = ldm.read_key(&INTERACTION)?;
     let diff = &interaction - &interaction_prev;
 
-    let plonk_total_sum: QM31Bar = ldm.read("plonk_total_sum")?;
-    let shift = M31::from_u32_unchecked(1 << proof.stmt0.log_size_plonk).inverse();
+    let plonk_total_sum = ldm.read_key(&PLONK_TOTAL_SUM)?;
+    let shift = M31::from_u32_unchecked(1 << hints.log_size_plonk).inverse();
 
     let table = TableBar::new_constant(&cs, ())?;
     let cumsum_shift: QM31Bar = &plonk_total_sum * (&table, &M31Bar::new_constant(&cs, shift)?);
     let fixed_diff = &diff + &cumsum_shift;
 
-    let relation_num: QM31Bar = ldm.read("relation_num")?;
-    let relation_denom: QM31Bar = ldm.read("relation_denom")?;
+    let relation_num = ldm.read_key(&RELATION_NUM)?;
+    let relation_denom = ldm.read_key(&RELATION_DENOM)?;
 
-    let random_coeff: QM31Bar = ldm.read("random_coeff")?;
-    let accumulation: QM31Bar = ldm.read("eval_acc_accumulation_part6")?;
+    let random_coeff = ldm.read_key(&RANDOM_COEFF)?;
+    let accumulation = ldm.read_key(&EVAL_ACC_ACCUMULATION_PART6)?;
 
     let mut eval_acc = PointEvaluationAccumulatorBar {
         random_coeff,
@@ -47,40 +49,39 @@ pub fn generate_cs(
         &(&(&fixed_diff * (&table, &relation_denom)) - &relation_num),
     );
 
-    let coset_vanishing_x_inv: QM31Bar = ldm.read("coset_vanishing_x_inv")?;
+    let coset_vanishing_x_inv = ldm.read_key(&COSET_VANISHING_X_INV)?;
     let expected_composition = &eval_acc.accumulation * (&table, &coset_vanishing_x_inv);
 
-    let composition: QM31Bar = ldm.read("composition")?;
+    let composition = ldm.read_key(&COMPOSITION)?;
     composition.equalverify(&expected_composition)?;
-    let after_sampled_values_random_coeff: QM31Bar =
-        ldm.read("after_sampled_values_random_coeff")?;
+    let after_sampled_values_random_coeff = ldm.read_key(&AFTER_SAMPLED_VALUES_RANDOM_COEFF)?;
 
     let line_coeff_randomizer_26 = LineCoeffRandomizerBar::new(&after_sampled_values_random_coeff)?;
-    ldm.write(
-        "line_coeff_randomizer_26_alpha_0",
+    ldm.write_key(
+        &LINE_COEFF_RANDOMIZER_26_ALPHA.indexed(0),
         &line_coeff_randomizer_26.alpha,
     )?;
 
     let line_coeff_randomizer_28 = LineCoeffRandomizerBar::new(&after_sampled_values_random_coeff)?;
-    ldm.write(
-        "line_coeff_randomizer_28_alpha_0",
+    ldm.write_key(
+        &LINE_COEFF_RANDOMIZER_28_ALPHA.indexed(0),
         &line_coeff_randomizer_28.alpha,
     )?;
 
-    let oods_x: QM31Bar = ldm.read("oods_x")?;
-    let oods_y: QM31Bar = ldm.read("oods_y")?;
+    let oods_x = ldm.read_key(&OODS_X)?;
+    let oods_y = ldm.read_key(&OODS_Y)?;
     let oods_point = CirclePointQM31Bar {
         x: oods_x,
         y: oods_y,
     };
 
-    let shift_point = CanonicCoset::new(proof.stmt0.log_size_plonk)
+    let shift_point = CanonicCoset::new(hints.log_size_plonk)
         .step()
         .mul_signed(-1);
     let oods_shifted_point = &oods_point + (&table, &shift_point);
 
-    ldm.write("oods_shifted_x", &oods_shifted_point.x)?;
-    ldm.write("oods_shifted_y", &oods_shifted_point.y)?;
+    ldm.write_key(&OODS_SHIFTED_X, &oods_shifted_point.x)?;
+    ldm.write_key(&OODS_SHIFTED_Y, &oods_shifted_point.y)?;
 
     ldm.save()?;
     Ok(cs)
@@ -1,7 +1,13 @@
+use crate::script::global::keys::{
+    AFTER_SAMPLED_VALUES_RANDOM_COEFF, COLUMN_LINE_COEFF, COMPOSITION, INTERACTION,
+    LINE_COEFF_RANDOMIZER_26_ALPHA, OODS_X, OODS_Y, PREPROCESSED_A_WIRE, PREPROCESSED_B_WIRE,
+    PREPROCESSED_C_WIRE, PREPROCESSED_MULT_C, PREPROCESSED_OP1, PREPROCESSED_OP2, PREPROCESSED_OP3,
+    PREPROCESSED_OP4, TRACE_A_VAL, TRACE_B_VAL, TRACE_C_VAL,
+};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
-use recursive_stwo_bitcoin_dsl::ldm::LDM;
+use recursive_stwo_bitcoin_dsl::ldm::{LDMKey, LDM};
 use recursive_stwo_primitives::circle::CirclePointQM31Bar;
 use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
@@ -13,37 +19,36 @@ use std::cmp::min;
 pub fn generate_cs(
     ldm: &mut LDM,
     counter: usize,
-    oods_original_logsize_26_labels: &[String],
+    oods_original_logsize_26_labels: &[LDMKey<QM31Bar>],
 ) -> Result<BitcoinSystemRef> {
     let cs = BitcoinSystemRef::new_ref();
     ldm.init(&cs)?;
 
-    let after_sampled_values_random_coeff: QM31Bar =
-        ldm.read("after_sampled_values_random_coeff")?;
-    let oods_x: QM31Bar = ldm.read("oods_x")?;
-    let oods_y: QM31Bar = ldm.read("oods_y")?;
+    let after_sampled_values_random_coeff = ldm.read_key(&AFTER_SAMPLED_VALUES_RANDOM_COEFF)?;
+    let oods_x = ldm.read_key(&OODS_X)?;
+    let oods_y = ldm.read_key(&OODS_Y)?;
     let oods_point = CirclePointQM31Bar {
         x: oods_x,
         y: oods_y,
     };
     let mut line_coeff_randomizer = LineCoeffRandomizerBar {
-        alpha: ldm.read(format!("line_coeff_randomizer_26_alpha_{}", counter + 2))?,
+        alpha: ldm.read_key(&LINE_COEFF_RANDOMIZER_26_ALPHA.indexed(counter + 2))?,
     };
     let table = TableBar::new_constant(&cs, ())?;
 
     for i in counter * 2..min(oods_original_logsize_26_labels.len(), counter * 2 + 2) {
-        let value: QM31Bar = ldm.read(oods_original_logsize_26_labels[i].to_string())?;
+        let value = ldm.read_key(&oods_original_logsize_26_labels[i])?;
         let alpha =
             line_coeff_randomizer.get_and_update(&table, &after_sampled_values_random_coeff);
         let coeff = complex_conjugate_line_coeffs_var(&table, &oods_point, &value, &alpha)?;
-        ldm.write(
-            format!("column_line_coeff_{}", oods_original_logsize_26_labels[i]),
+        ldm.write_key(
+            &COLUMN_LINE_COEFF.indexed(&oods_original_logsize_26_labels[i]),
             &coeff,
         )?;
     }
 
-    ldm.write(
-        format!("line_coeff_randomizer_26_alpha_{}", counter + 2 + 1),
+    ldm.write_key(
+        &LINE_COEFF_RANDOMIZER_26_ALPHA.indexed(counter + 2 + 1),
         &line_coeff_randomizer.alpha,
     )?;
 
@@ -51,40 +56,23 @@ pub fn generate_cs(
     Ok(cs)
 }
 
-pub fn generate_oods_original_logsize_26_labels() -> Vec<String> {
-    vec![
-        "preprocessed_a_wire".to_string(),
-        "preprocessed_b_wire".to_string(),
-        "preprocessed_c_wire".to_string(),
-        "preprocessed_op1".to_string(),
-        "preprocessed_op2".to_string(),
-        "preprocessed_op3".to_string(),
-        "preprocessed_op4".to_string(),
-        "preprocessed_mult_c".to_string(),
-        "trace_a_val_0".to_string(),
-        "trace_a_val_1".to_string(),
-        "trace_a_val_2".to_string(),
-        "trace_a_val_3".to_string(),
-        "trace_b_val_0".to_string(),
-        "trace_b_val_1".to_string(),
-        "trace_b_val_2".to_string(),
-        "trace_b_val_3".to_string(),
-        "trace_c_val_0".to_string(),
-        "trace_c_val_1".to_string(),
-        "trace_c_val_2".to_string(),
-        "trace_c_val_3".to_string(),
-        "interaction_0".to_string(),
-        "interaction_1".to_string(),
-        "interaction_2".to_string(),
-        "interaction_3".to_string(),
-    ]
+pub fn generate_oods_original_logsize_26_labels() -> Vec<LDMKey<QM31Bar>> {
+    let mut labels = vec![
+        PREPROCESSED_A_WIRE,
+        PREPROCESSED_B_WIRE,
+        PREPROCESSED_C_WIRE,
+        PREPROCESSED_OP1,
+        PREPROCESSED_OP2,
+        PREPROCESSED_OP3,
+        PREPROCESSED_OP4,
+        PREPROCESSED_MULT_C,
+    ];
+    for column in [TRACE_A_VAL, TRACE_B_VAL, TRACE_C_VAL, INTERACTION] {
+        labels.extend((0..4).map(|i| column.indexed(i)));
+    }
+    labels
 }
 
-pub fn generate_oods_original_logsize_28_labels() -> Vec<String> {
-    vec![
-        "composition_0".to_string(),
-        "composition_1".to_string(),
-        "composition_2".to_string(),
-        "composition_3".to_string(),
-    ]
+pub fn generate_oods_original_logsize_28_labels() -> Vec<LDMKey<QM31Bar>> {
+    (0..4).map(|i| COMPOSITION.indexed(i)).collect()
 }
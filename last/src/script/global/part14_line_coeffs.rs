@@ -1,7 +1,11 @@
+use crate::script::global::keys::{
+    AFTER_SAMPLED_VALUES_RANDOM_COEFF, COLUMN_LINE_COEFF, LINE_COEFF_RANDOMIZER_28_ALPHA, OODS_X,
+    OODS_Y,
+};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
-use recursive_stwo_bitcoin_dsl::ldm::LDM;
+use recursive_stwo_bitcoin_dsl::ldm::{LDMKey, LDM};
 use recursive_stwo_primitives::circle::CirclePointQM31Bar;
 use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
@@ -13,37 +17,36 @@ use std::cmp::min;
 pub fn generate_cs(
     ldm: &mut LDM,
     counter: usize,
-    oods_original_logsize_28_labels: &[String],
+    oods_original_logsize_28_labels: &[LDMKey<QM31Bar>],
 ) -> Result<BitcoinSystemRef> {
     let cs = BitcoinSystemRef::new_ref();
     ldm.init(&cs)?;
 
-    let after_sampled_values_random_coeff: QM31Bar =
-        ldm.read("after_sampled_values_random_coeff")?;
-    let oods_x: QM31Bar = ldm.read("oods_x")?;
-    let oods_y: QM31Bar = ldm.read("oods_y")?;
+    let after_sampled_values_random_coeff = ldm.read_key(&AFTER_SAMPLED_VALUES_RANDOM_COEFF)?;
+    let oods_x = ldm.read_key(&OODS_X)?;
+    let oods_y = ldm.read_key(&OODS_Y)?;
     let oods_point = CirclePointQM31Bar {
         x: oods_x,
         y: oods_y,
     };
     let mut line_coeff_randomizer = LineCoeffRandomizerBar {
-        alpha: ldm.read(format!("line_coeff_randomizer_28_alpha_{}", counter))?,
+        alpha: ldm.read_key(&LINE_COEFF_RANDOMIZER_28_ALPHA.indexed(counter))?,
     };
     let table = TableBar::new_constant(&cs, ())?;
 
     for i in counter * 2..min(oods_original_logsize_28_labels.len(), counter * 2 + 2) {
-        let value: QM31Bar = ldm.read(oods_original_logsize_28_labels[i].to_string())?;
+        let value = ldm.read_key(&oods_original_logsize_28_labels[i])?;
         let alpha =
             line_coeff_randomizer.get_and_update(&table, &after_sampled_values_random_coeff);
         let coeff = complex_conjugate_line_coeffs_var(&table, &oods_point, &value, &alpha)?;
-        ldm.write(
-            format!("column_line_coeff_{}", oods_original_logsize_28_labels[i]),
+        ldm.write_key(
+            &COLUMN_LINE_COEFF.indexed(&oods_original_logsize_28_labels[i]),
             &coeff,
         )?;
     }
 
-    ldm.write(
-        format!("line_coeff_randomizer_28_alpha_{}", counter + 1),
+    ldm.write_key(
+        &LINE_COEFF_RANDOMIZER_28_ALPHA.indexed(counter + 1),
         &line_coeff_randomizer.alpha,
     )?;
 
@@ -1,7 +1,8 @@
+use crate::script::global::keys::{INPUT_ACC_ALPHA, INPUT_ACC_CUR, INPUT_ACC_SUM};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
-use recursive_stwo_bitcoin_dsl::ldm::LDM;
+use recursive_stwo_bitcoin_dsl::ldm::{LDMKey, LDM};
 use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
 use recursive_stwo_primitives::input_sum::InputSumBar;
@@ -10,14 +11,14 @@ use std::cmp::min;
 pub fn generate_cs(
     ldm: &mut LDM,
     counter: usize,
-    input_labels: &[String],
+    input_labels: &[LDMKey<QM31Bar>],
 ) -> Result<BitcoinSystemRef> {
     let cs = BitcoinSystemRef::new_ref();
     ldm.init(&cs)?;
 
-    let alpha: QM31Bar = ldm.read(format!("input_acc_alpha_{}", counter))?;
-    let cur: QM31Bar = ldm.read(format!("input_acc_cur_{}", counter))?;
-    let sum: QM31Bar = ldm.read(format!("input_acc_sum_{}", counter))?;
+    let alpha = ldm.read_key(&INPUT_ACC_ALPHA.indexed(counter))?;
+    let cur = ldm.read_key(&INPUT_ACC_CUR.indexed(counter))?;
+    let sum = ldm.read_key(&INPUT_ACC_SUM.indexed(counter))?;
 
     let mut input_acc = InputSumBar { alpha, cur, sum };
 
@@ -27,9 +28,9 @@ pub fn generate_cs(
         input_acc.accumulate_from_ldm(&table, ldm, &input_labels[i])?;
     }
 
-    ldm.write(format!("input_acc_alpha_{}", counter + 1), &input_acc.alpha)?;
-    ldm.write(format!("input_acc_cur_{}", counter + 1), &input_acc.cur)?;
-    ldm.write(format!("input_acc_sum_{}", counter + 1), &input_acc.sum)?;
+    ldm.write_key(&INPUT_ACC_ALPHA.indexed(counter + 1), &input_acc.alpha)?;
+    ldm.write_key(&INPUT_ACC_CUR.indexed(counter + 1), &input_acc.cur)?;
+    ldm.write_key(&INPUT_ACC_SUM.indexed(counter + 1), &input_acc.sum)?;
 
     ldm.save()?;
     Ok(cs)
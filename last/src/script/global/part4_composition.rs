@@ -1,25 +1,28 @@
+use crate::script::global::keys::{
+    EVAL_ACC_ACCUMULATION_PART3, EVAL_ACC_ACCUMULATION_PART4, PREPROCESSED_OP2, RANDOM_COEFF,
+    TRACE_A_VAL, TRACE_B_VAL,
+};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::ldm::LDM;
 use recursive_stwo_primitives::composition::PointEvaluationAccumulatorBar;
-use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
 
 pub fn generate_cs(ldm: &mut LDM) -> Result<BitcoinSystemRef> {
     let cs = BitcoinSystemRef::new_ref();
     ldm.init(&cs)?;
 
-    let random_coeff: QM31Bar = ldm.read("random_coeff")?;
-    let accumulation: QM31Bar = ldm.read("eval_acc_accumulation_part3")?;
+    let random_coeff = ldm.read_key(&RANDOM_COEFF)?;
+    let accumulation = ldm.read_key(&EVAL_ACC_ACCUMULATION_PART3)?;
 
-    let preprocessed_op2: QM31Bar = ldm.read("preprocessed_op2")?;
+    let preprocessed_op2 = ldm.read_key(&PREPROCESSED_OP2)?;
     let is_pow5 = preprocessed_op2.clone();
 
-    let trace_a_val_2: QM31Bar = ldm.read("trace_a_val_2")?;
-    let trace_a_val_3: QM31Bar = ldm.read("trace_a_val_3")?;
-    let trace_b_val_2: QM31Bar = ldm.read("trace_b_val_2")?;
-    let trace_b_val_3: QM31Bar = ldm.read("trace_b_val_3")?;
+    let trace_a_val_2 = ldm.read_key(&TRACE_A_VAL.indexed(2))?;
+    let trace_a_val_3 = ldm.read_key(&TRACE_A_VAL.indexed(3))?;
+    let trace_b_val_2 = ldm.read_key(&TRACE_B_VAL.indexed(2))?;
+    let trace_b_val_3 = ldm.read_key(&TRACE_B_VAL.indexed(3))?;
 
     let table = TableBar::new_constant(&cs, ())?;
 
@@ -43,7 +46,7 @@ pub fn generate_cs(ldm: &mut LDM) -> Result<BitcoinSystemRef> {
         &(&(&a_val_3_pow4 - &trace_b_val_3) * (&table, &is_pow5)),
     );
 
-    ldm.write("eval_acc_accumulation_part4", &eval_acc.accumulation)?;
+    ldm.write_key(&EVAL_ACC_ACCUMULATION_PART4, &eval_acc.accumulation)?;
 
     ldm.save()?;
     Ok(cs)
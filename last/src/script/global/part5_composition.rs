@@ -1,23 +1,25 @@
+use crate::script::global::keys::{
+    ARITH_SUM_PART_5, PREPROCESSED_OP3, PREPROCESSED_OP4, TRACE_A_VAL, TRACE_B_VAL,
+};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::ldm::LDM;
-use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
 
 pub fn generate_cs(ldm: &mut LDM) -> Result<BitcoinSystemRef> {
     let cs = BitcoinSystemRef::new_ref();
     ldm.init(&cs)?;
 
-    let trace_a_val_0: QM31Bar = ldm.read("trace_a_val_0")?;
-    let trace_a_val_1: QM31Bar = ldm.read("trace_a_val_1")?;
-    let trace_a_val_2: QM31Bar = ldm.read("trace_a_val_2")?;
-    let trace_a_val_3: QM31Bar = ldm.read("trace_a_val_3")?;
+    let trace_a_val_0 = ldm.read_key(&TRACE_A_VAL.indexed(0))?;
+    let trace_a_val_1 = ldm.read_key(&TRACE_A_VAL.indexed(1))?;
+    let trace_a_val_2 = ldm.read_key(&TRACE_A_VAL.indexed(2))?;
+    let trace_a_val_3 = ldm.read_key(&TRACE_A_VAL.indexed(3))?;
 
-    let trace_b_val_0: QM31Bar = ldm.read("trace_b_val_0")?;
-    let trace_b_val_1: QM31Bar = ldm.read("trace_b_val_1")?;
-    let trace_b_val_2: QM31Bar = ldm.read("trace_b_val_2")?;
-    let trace_b_val_3: QM31Bar = ldm.read("trace_b_val_3")?;
+    let trace_b_val_0 = ldm.read_key(&TRACE_B_VAL.indexed(0))?;
+    let trace_b_val_1 = ldm.read_key(&TRACE_B_VAL.indexed(1))?;
+    let trace_b_val_2 = ldm.read_key(&TRACE_B_VAL.indexed(2))?;
+    let trace_b_val_3 = ldm.read_key(&TRACE_B_VAL.indexed(3))?;
 
     let mut grand_sum = &trace_a_val_0 + &trace_a_val_1;
     grand_sum = &grand_sum + &trace_a_val_2;
@@ -53,8 +55,8 @@ pub fn generate_cs(ldm: &mut LDM) -> Result<BitcoinSystemRef> {
     let m4_result_2 = t7;
     let m4_result_3 = t4;
 
-    let preprocessed_op3: QM31Bar = ldm.read("preprocessed_op3")?;
-    let preprocessed_op4: QM31Bar = ldm.read("preprocessed_op4")?;
+    let preprocessed_op3 = ldm.read_key(&PREPROCESSED_OP3)?;
+    let preprocessed_op4 = ldm.read_key(&PREPROCESSED_OP4)?;
 
     let is_grand_sum = &preprocessed_op3 * (&table, &preprocessed_op4);
     let is_m4 = &preprocessed_op3 - &is_grand_sum;
@@ -73,7 +75,7 @@ pub fn generate_cs(ldm: &mut LDM) -> Result<BitcoinSystemRef> {
     let grand_sum_result = &grand_sum * (&table, &is_grand_sum);
     sum = &sum - &grand_sum_result;
 
-    ldm.write("arith_sum_part_5", &sum)?;
+    ldm.write_key(&ARITH_SUM_PART_5, &sum)?;
 
     ldm.save()?;
     Ok(cs)
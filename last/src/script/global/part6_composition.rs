@@ -1,3 +1,8 @@
+use crate::script::global::keys::{
+    ARITH_SUM_PART_5, EVAL_ACC_ACCUMULATION_PART4, EVAL_ACC_ACCUMULATION_PART6, INNER_LAYER_ALPHA,
+    INNER_LAYER_ALPHA_1_SQUARED, PREPROCESSED_OP1, PREPROCESSED_OP3, PREPROCESSED_OP4,
+    RANDOM_COEFF, TRACE_A_VAL, TRACE_B_VAL, TRACE_C_VAL,
+};
 use anyhow::Result;
 use num_traits::One;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
@@ -12,12 +17,12 @@ pub fn generate_cs(ldm: &mut LDM) -> Result<BitcoinSystemRef> {
     let cs = BitcoinSystemRef::new_ref();
     ldm.init(&cs)?;
 
-    let trace_a_val: QM31Bar = ldm.read("trace_a_val")?;
-    let trace_b_val: QM31Bar = ldm.read("trace_b_val")?;
+    let trace_a_val = ldm.read_key(&TRACE_A_VAL)?;
+    let trace_b_val = ldm.read_key(&TRACE_B_VAL)?;
 
-    let preprocessed_op1: QM31Bar = ldm.read("preprocessed_op1")?;
-    let preprocessed_op3: QM31Bar = ldm.read("preprocessed_op3")?;
-    let preprocessed_op4: QM31Bar = ldm.read("preprocessed_op4")?;
+    let preprocessed_op1 = ldm.read_key(&PREPROCESSED_OP1)?;
+    let preprocessed_op3 = ldm.read_key(&PREPROCESSED_OP3)?;
+    let preprocessed_op4 = ldm.read_key(&PREPROCESSED_OP4)?;
     let one = QM31Bar::new_constant(&cs, QM31::one())?;
     let one_minus_op1 = &one - &preprocessed_op1;
     let one_minus_op3 = &one - &preprocessed_op3;
@@ -27,16 +32,16 @@ pub fn generate_cs(ldm: &mut LDM) -> Result<BitcoinSystemRef> {
 
     let is_arith = &one_minus_op3 * (&table, &one_minus_op4);
 
-    let trace_c_val: QM31Bar = ldm.read("trace_c_val")?;
+    let trace_c_val = ldm.read_key(&TRACE_C_VAL)?;
 
-    let mut sum: QM31Bar = ldm.read("arith_sum_part_5")?;
+    let mut sum = ldm.read_key(&ARITH_SUM_PART_5)?;
     sum = &sum + &trace_c_val;
     sum = &sum
         - &(&(&is_arith * (&table, &preprocessed_op1)) * (&table, &(&trace_a_val + &trace_b_val)));
     sum = &sum - &(&(&one_minus_op1 * (&table, &trace_a_val)) * (&table, &trace_b_val));
 
-    let random_coeff: QM31Bar = ldm.read("random_coeff")?;
-    let accumulation: QM31Bar = ldm.read("eval_acc_accumulation_part4")?;
+    let random_coeff = ldm.read_key(&RANDOM_COEFF)?;
+    let accumulation = ldm.read_key(&EVAL_ACC_ACCUMULATION_PART4)?;
 
     let mut eval_acc = PointEvaluationAccumulatorBar {
         random_coeff,
@@ -44,11 +49,11 @@ pub fn generate_cs(ldm: &mut LDM) -> Result<BitcoinSystemRef> {
     };
 
     eval_acc.accumulate(&table, &sum);
-    ldm.write("eval_acc_accumulation_part6", &eval_acc.accumulation)?;
+    ldm.write_key(&EVAL_ACC_ACCUMULATION_PART6, &eval_acc.accumulation)?;
 
-    let inner_layer_alpha_1: QM31Bar = ldm.read("inner_layer_alpha_1")?;
-    ldm.write(
-        "inner_layer_alpha_1_squared",
+    let inner_layer_alpha_1 = ldm.read_key(&INNER_LAYER_ALPHA.indexed(1))?;
+    ldm.write_key(
+        &INNER_LAYER_ALPHA_1_SQUARED,
         &(&inner_layer_alpha_1 * (&table, &inner_layer_alpha_1)),
     )?;
 
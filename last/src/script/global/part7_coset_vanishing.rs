@@ -1,29 +1,25 @@
+use crate::script::global::keys::{COSET_VANISHING_X_PART7, OODS_T, OODS_X, OODS_Y};
+use crate::script::hints::proof::LastProofHints;
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::ldm::LDM;
 use recursive_stwo_primitives::circle::CirclePointQM31Bar;
-use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
 use stwo_prover::core::poly::circle::CanonicCoset;
-use stwo_prover::core::vcs::sha256_merkle::Sha256MerkleHasher;
-use stwo_prover::examples::plonk_without_poseidon::air::PlonkWithoutPoseidonProof;
 
-pub fn generate_cs(
-    proof: &PlonkWithoutPoseidonProof<Sha256MerkleHasher>,
-    ldm: &mut LDM,
-) -> Result<BitcoinSystemRef> {
+pub fn generate_cs(hints: &LastProofHints, ldm: &mut LDM) -> Result<BitcoinSystemRef> {
     let cs = BitcoinSystemRef::new_ref();
     ldm.init(&cs)?;
 
-    let oods_t: QM31Bar = ldm.read("oods_t")?;
+    let oods_t = ldm.read_key(&OODS_T)?;
     let table = TableBar::new_constant(&cs, ())?;
 
     let oods_point = CirclePointQM31Bar::from_t(&table, &oods_t);
-    ldm.write("oods_x", &oods_point.x)?;
-    ldm.write("oods_y", &oods_point.y)?;
+    ldm.write_key(&OODS_X, &oods_point.x)?;
+    ldm.write_key(&OODS_Y, &oods_point.y)?;
 
-    let coset = CanonicCoset::new(proof.stmt0.log_size_plonk).coset;
+    let coset = CanonicCoset::new(hints.log_size_plonk).coset;
     let mut x = (&oods_point
         + (
             &table,
@@ -37,7 +33,7 @@ pub fn generate_cs(
         let sq = &x * (&table, &x);
         x = (&sq + &sq).sub1();
     }
-    ldm.write("coset_vanishing_x_part7", &x)?;
+    ldm.write_key(&COSET_VANISHING_X_PART7, &x)?;
 
     ldm.save()?;
     Ok(cs)
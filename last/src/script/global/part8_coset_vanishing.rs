@@ -1,15 +1,15 @@
+use crate::script::global::keys::{COSET_VANISHING_X_PART7, COSET_VANISHING_X_PART8};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::ldm::LDM;
-use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
 
 pub fn generate_cs(ldm: &mut LDM) -> Result<BitcoinSystemRef> {
     let cs = BitcoinSystemRef::new_ref();
     ldm.init(&cs)?;
 
-    let mut x: QM31Bar = ldm.read("coset_vanishing_x_part7")?;
+    let mut x = ldm.read_key(&COSET_VANISHING_X_PART7)?;
     let table = TableBar::new_constant(&cs, ())?;
 
     // The formula for the x coordinate of the double of a point.
@@ -17,7 +17,7 @@ pub fn generate_cs(ldm: &mut LDM) -> Result<BitcoinSystemRef> {
         let sq = &x * (&table, &x);
         x = (&sq + &sq).sub1();
     }
-    ldm.write("coset_vanishing_x_part8", &x)?;
+    ldm.write_key(&COSET_VANISHING_X_PART8, &x)?;
 
     ldm.save()?;
     Ok(cs)
@@ -1,6 +1,7 @@
 use crate::script::hints::fiat_shamir::LastFiatShamirHints;
 use anyhow::Result;
 use itertools::Itertools;
+use num_traits::Zero;
 use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar};
 use recursive_stwo_bitcoin_dsl::basic::sha256_hash::Sha256HashBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
@@ -137,6 +138,19 @@ impl LastSingleLeafMerkleProof {
         res
     }
 
+    /// Returns a proof of the given depth and number of columns filled with zeros, which leads to
+    /// the same scripts as any proof of that shape but does not verify.
+    pub fn placeholder(depth: usize, n_columns: usize) -> Self {
+        let zero_hash = Sha256Hash::from([0u8; 32].as_slice());
+        Self {
+            query: 0,
+            sibling_hashes: vec![zero_hash; depth],
+            columns: vec![M31::zero(); n_columns],
+            root: zero_hash,
+            depth,
+        }
+    }
+
     pub fn verify(&self) {
         let mut cur_hash = Sha256MerkleHasher::hash_node(None, &self.columns);
         for i in 0..self.depth {
@@ -273,6 +287,14 @@ impl LastDecommitHints {
 
         LastDecommitHints { proofs }
     }
+
+    /// Returns the placeholder hints for a tree of the given depth and number of columns, with
+    /// one proof per query.
+    pub fn placeholder(n_queries: usize, depth: usize, n_columns: usize) -> Self {
+        LastDecommitHints {
+            proofs: vec![LastSingleLeafMerkleProof::placeholder(depth, n_columns); n_queries],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -3,10 +3,9 @@ use crate::script::hints::fiat_shamir::LastFiatShamirHints;
 use anyhow::Result;
 use itertools::{zip_eq, Itertools};
 use num_traits::Zero;
-use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar};
+use recursive_stwo_bitcoin_dsl::bar::{AllocBar, Bar};
 use recursive_stwo_bitcoin_dsl::basic::bool::BoolBar;
 use recursive_stwo_bitcoin_dsl::basic::sha256_hash::Sha256HashBar;
-use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::treepp::*;
 use recursive_stwo_primitives::bits::split_be_bits;
 use recursive_stwo_primitives::fields::m31::M31Bar;
@@ -127,6 +126,29 @@ impl LastSinglePairMerkleProof {
         assert_eq!(self_hash, self.root);
     }
 
+    /// Returns a proof with columns at the given log sizes filled with zeros, which leads to the
+    /// same scripts as any proof of that shape but does not verify.
+    pub fn placeholder(log_sizes_with_data: &BTreeSet<u32>) -> Self {
+        let zero_hash = Sha256Hash::from([0u8; 32].as_slice());
+        let depth = *log_sizes_with_data.last().unwrap() as usize;
+
+        let mut self_columns = BTreeMap::new();
+        let mut siblings_columns = BTreeMap::new();
+        for &log_size in log_sizes_with_data.iter() {
+            self_columns.insert(log_size as usize, QM31::zero());
+            siblings_columns.insert(log_size as usize, QM31::zero());
+        }
+
+        Self {
+            query: 0,
+            sibling_hashes: vec![zero_hash; depth - 1],
+            self_columns,
+            siblings_columns,
+            root: zero_hash,
+            depth,
+        }
+    }
+
     pub fn from_stwo_proof(
         log_sizes_with_data: &BTreeSet<u32>,
         root: Sha256Hash,
@@ -324,52 +346,21 @@ impl LastSinglePairMerkleProof {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Bar, AllocBar)]
+#[bar(value = LastSinglePairMerkleProof)]
 pub struct LastSinglePairMerkleProofBar {
-    pub cs: BitcoinSystemRef,
-    pub value: LastSinglePairMerkleProof,
+    #[bar(skip)]
+    pub query: usize,
 
     pub sibling_hashes: Vec<Sha256HashBar>,
 
     pub self_columns: BTreeMap<usize, QM31Bar>,
     pub siblings_columns: BTreeMap<usize, QM31Bar>,
-}
-
-impl AllocBar for LastSinglePairMerkleProofBar {
-    type Value = LastSinglePairMerkleProof;
-
-    fn value(&self) -> Result<Self::Value> {
-        Ok(self.value.clone())
-    }
-
-    fn new_variable(
-        cs: &BitcoinSystemRef,
-        data: Self::Value,
-        mode: AllocationMode,
-    ) -> Result<Self> {
-        let mut sibling_hashes = vec![];
-        for sibling_hash in data.sibling_hashes.iter() {
-            sibling_hashes.push(Sha256HashBar::new_variable(cs, sibling_hash.clone(), mode)?);
-        }
-
-        let mut self_columns = BTreeMap::new();
-        for (i, v) in data.self_columns.iter() {
-            self_columns.insert(*i, QM31Bar::new_variable(&cs, v.clone(), mode)?);
-        }
-
-        let mut siblings_columns = BTreeMap::new();
-        for (i, v) in data.siblings_columns.iter() {
-            siblings_columns.insert(*i, QM31Bar::new_variable(&cs, v.clone(), mode)?);
-        }
 
-        Ok(Self {
-            cs: cs.clone(),
-            value: data.clone(),
-            sibling_hashes,
-            self_columns,
-            siblings_columns,
-        })
-    }
+    #[bar(skip)]
+    pub root: Sha256Hash,
+    #[bar(skip)]
+    pub depth: usize,
 }
 
 impl LastSinglePairMerkleProofBar {
@@ -386,29 +377,23 @@ impl LastSinglePairMerkleProofBar {
 
         let mut self_hash = hash_many_m31(
             &cs,
-            &self
-                .self_columns
-                .get(&self.value.depth)
-                .map_or(vec![], |v| {
-                    v.to_m31_array().iter().rev().cloned().collect_vec()
-                }),
+            &self.self_columns.get(&self.depth).map_or(vec![], |v| {
+                v.to_m31_array().iter().rev().cloned().collect_vec()
+            }),
         )?;
         let mut sibling_hash = hash_many_m31(
             &cs,
-            &self
-                .siblings_columns
-                .get(&self.value.depth)
-                .map_or(vec![], |v| {
-                    v.to_m31_array().iter().rev().cloned().collect_vec()
-                }),
+            &self.siblings_columns.get(&self.depth).map_or(vec![], |v| {
+                v.to_m31_array().iter().rev().cloned().collect_vec()
+            }),
         )?;
 
-        for i in 0..self.value.depth {
-            let h = self.value.depth - i - 1;
+        for i in 0..self.depth {
+            let h = self.depth - i - 1;
 
             if !self.self_columns.contains_key(&h) {
                 self_hash = hash_node(&self_hash, &sibling_hash, &bits_vars[i], None)?;
-                if i != self.value.depth - 1 {
+                if i != self.depth - 1 {
                     sibling_hash = self.sibling_hashes[i].clone();
                 }
             } else {
@@ -617,6 +602,18 @@ impl LastFirstLayerHints {
         }
     }
 
+    /// Returns the placeholder hints for a first layer with columns at the given log sizes, with
+    /// one proof per query. The folded evaluations are left empty, as no script depends on them.
+    pub fn placeholder(n_queries: usize, log_sizes_with_data: &BTreeSet<u32>) -> Self {
+        LastFirstLayerHints {
+            merkle_proofs: vec![
+                LastSinglePairMerkleProof::placeholder(log_sizes_with_data);
+                n_queries
+            ],
+            folded_evals_by_column: BTreeMap::new(),
+        }
+    }
+
     pub fn compute_decommitment_positions_and_rebuild_evals(
         queries: &[usize],
         domain_log_size: u32,
@@ -806,6 +803,32 @@ impl LastInnerLayersHints {
             folded_intermediate_results: all_folded_intermediate_results,
         }
     }
+
+    /// Returns the placeholder hints for the inner layers that follow a first layer of the given
+    /// log size, with one proof per query. The intermediate results are left empty, as no script
+    /// depends on them.
+    pub fn placeholder(
+        n_queries: usize,
+        max_first_layer_column_log_size: u32,
+        n_inner_layers: usize,
+    ) -> Self {
+        let mut merkle_proofs = BTreeMap::new();
+        for i in 0..n_inner_layers {
+            let log_size = max_first_layer_column_log_size - 1 - i as u32;
+            merkle_proofs.insert(
+                log_size,
+                vec![
+                    LastSinglePairMerkleProof::placeholder(&BTreeSet::from([log_size]));
+                    n_queries
+                ],
+            );
+        }
+
+        Self {
+            merkle_proofs,
+            folded_intermediate_results: BTreeMap::new(),
+        }
+    }
 }
 
 #[cfg(test)]
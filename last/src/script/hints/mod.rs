@@ -5,3 +5,5 @@ pub mod decommit;
 pub mod folding;
 
 pub mod answer;
+
+pub mod proof;
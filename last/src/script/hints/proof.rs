@@ -0,0 +1,152 @@
+use crate::script::hints::answer::LastAnswerHints;
+use crate::script::hints::decommit::LastDecommitHints;
+use crate::script::hints::fiat_shamir::LastFiatShamirHints;
+use crate::script::hints::folding::{LastFirstLayerHints, LastInnerLayersHints};
+use anyhow::{Error, Result};
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use stwo_prover::core::fields::qm31::QM31;
+use stwo_prover::core::pcs::PcsConfig;
+use stwo_prover::core::vcs::sha256_hash::Sha256Hash;
+use stwo_prover::core::vcs::sha256_merkle::{Sha256MerkleChannel, Sha256MerkleHasher};
+use stwo_prover::examples::plonk_without_poseidon::air::{
+    verify_plonk_without_poseidon, PlonkWithoutPoseidonProof,
+};
+
+/// The shape of a last proof, which is, together with the configuration, all that the scripts of
+/// the last parts depend on. Every proof of the same statement has the same shape.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastProofShape {
+    pub log_size_plonk: u32,
+    /// The number of sampled values of each column, tree by tree.
+    pub n_sampled_values: Vec<Vec<usize>>,
+    /// The depth and the number of columns of each of the four committed trees.
+    pub trees: [(usize, usize); 4],
+    /// The log sizes of the columns folded into the first FRI layer.
+    pub first_layer_log_sizes: BTreeSet<u32>,
+    pub n_inner_layers: usize,
+}
+
+/// The values that the last parts take as hints.
+///
+/// In the prove mode, they are computed from a proof. In the setup mode, they are placeholders of
+/// the shape of the proof, which lead to the same scripts as any proof of that shape.
+#[derive(Clone, Debug)]
+pub struct LastProofHints {
+    pub log_size_plonk: u32,
+    pub commitments: [Sha256Hash; 4],
+    /// The total sum of the plonk trace, or `None` for the placeholders, in which case the part
+    /// that checks it against the input sum takes the opposite of the input sum instead.
+    pub plonk_total_sum: Option<QM31>,
+    pub sampled_values: Vec<Vec<Vec<QM31>>>,
+    pub first_layer_commitment: Sha256Hash,
+    pub inner_layer_commitments: Vec<Sha256Hash>,
+    pub last_layer_coeffs: Vec<QM31>,
+    pub proof_of_work: u64,
+    pub decommit: [LastDecommitHints; 4],
+    pub first_layer: LastFirstLayerHints,
+    pub inner_layers: LastInnerLayersHints,
+}
+
+impl LastProofHints {
+    /// Verifies the proof against the inputs of the delegated proof and computes the hints from
+    /// it.
+    pub fn compute(
+        proof: &PlonkWithoutPoseidonProof<Sha256MerkleHasher>,
+        config: PcsConfig,
+        inputs: &[(usize, QM31)],
+    ) -> Result<Self> {
+        verify_plonk_without_poseidon::<Sha256MerkleChannel>(proof.clone(), config, inputs)
+            .map_err(|err| Error::msg(format!("The last proof is invalid: {:?}.", err)))?;
+
+        let fiat_shamir_hints =
+            LastFiatShamirHints::<Sha256MerkleChannel>::new(proof, config, inputs);
+        let answer_hints = LastAnswerHints::compute(&fiat_shamir_hints, proof);
+        let first_layer = LastFirstLayerHints::compute(&fiat_shamir_hints, &answer_hints, proof);
+        let inner_layers = LastInnerLayersHints::compute(
+            &first_layer.folded_evals_by_column,
+            &fiat_shamir_hints,
+            proof,
+        );
+
+        Ok(Self {
+            log_size_plonk: proof.stmt0.log_size_plonk,
+            commitments: std::array::from_fn(|i| proof.stark_proof.commitments[i]),
+            plonk_total_sum: Some(fiat_shamir_hints.plonk_total_sum),
+            sampled_values: proof.stark_proof.sampled_values.0.clone(),
+            first_layer_commitment: proof.stark_proof.fri_proof.first_layer.commitment,
+            inner_layer_commitments: proof
+                .stark_proof
+                .fri_proof
+                .inner_layers
+                .iter()
+                .map(|inner_layer| inner_layer.commitment)
+                .collect(),
+            last_layer_coeffs: proof.stark_proof.fri_proof.last_layer_poly.coeffs.clone(),
+            proof_of_work: proof.stark_proof.proof_of_work,
+            decommit: std::array::from_fn(|round| {
+                LastDecommitHints::compute(&fiat_shamir_hints, proof, round)
+            }),
+            first_layer,
+            inner_layers,
+        })
+    }
+
+    /// Returns hints of the given shape filled with zeros, from which the scripts of the last
+    /// parts can be generated without a proof.
+    pub fn placeholder(config: PcsConfig, shape: &LastProofShape) -> Self {
+        let n_queries = config.fri_config.n_queries;
+        let zero_hash = Sha256Hash::from([0u8; 32].as_slice());
+        let max_first_layer_column_log_size = *shape.first_layer_log_sizes.last().unwrap();
+
+        Self {
+            log_size_plonk: shape.log_size_plonk,
+            commitments: [zero_hash; 4],
+            plonk_total_sum: None,
+            sampled_values: shape
+                .n_sampled_values
+                .iter()
+                .map(|tree| tree.iter().map(|&n| vec![QM31::zero(); n]).collect())
+                .collect(),
+            first_layer_commitment: zero_hash,
+            inner_layer_commitments: vec![zero_hash; shape.n_inner_layers],
+            last_layer_coeffs: vec![
+                QM31::zero();
+                1 << config.fri_config.log_last_layer_degree_bound
+            ],
+            proof_of_work: 0,
+            decommit: shape.trees.map(|(depth, n_columns)| {
+                LastDecommitHints::placeholder(n_queries, depth, n_columns)
+            }),
+            first_layer: LastFirstLayerHints::placeholder(n_queries, &shape.first_layer_log_sizes),
+            inner_layers: LastInnerLayersHints::placeholder(
+                n_queries,
+                max_first_layer_column_log_size,
+                shape.n_inner_layers,
+            ),
+        }
+    }
+
+    /// Returns the shape of the proof from which the hints are computed.
+    pub fn shape(&self) -> LastProofShape {
+        LastProofShape {
+            log_size_plonk: self.log_size_plonk,
+            n_sampled_values: self
+                .sampled_values
+                .iter()
+                .map(|tree| tree.iter().map(|column| column.len()).collect())
+                .collect(),
+            trees: std::array::from_fn(|round| {
+                let proof = &self.decommit[round].proofs[0];
+                (proof.depth, proof.columns.len())
+            }),
+            first_layer_log_sizes: self.first_layer.merkle_proofs[0]
+                .self_columns
+                .keys()
+                .map(|&log_size| log_size as u32)
+                .collect(),
+            n_inner_layers: self.inner_layers.merkle_proofs.len(),
+        }
+    }
+}
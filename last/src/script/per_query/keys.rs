@@ -0,0 +1,61 @@
+//! The entries that the parts of a query store in the LDM of the query, which is cleared at the
+//! end of each query. Some names are also used by the global LDM, but with other types: there,
+//! they hold the sampled values, while here they hold the values of the columns at the query.
+
+use recursive_stwo_bitcoin_dsl::ldm::LDMKey;
+use recursive_stwo_primitives::fields::cm31::CM31Bar;
+use recursive_stwo_primitives::fields::m31::M31Bar;
+use recursive_stwo_primitives::fields::qm31::QM31Bar;
+
+pub const POINT_28_X: LDMKey<M31Bar> = LDMKey::new("point_28_x", "m31");
+pub const POINT_28_Y: LDMKey<M31Bar> = LDMKey::new("point_28_y", "m31");
+pub const POINT_28_Y_INV: LDMKey<M31Bar> = LDMKey::new("point_28_y_inv", "m31");
+pub const POINT_26_X: LDMKey<M31Bar> = LDMKey::new("point_26_x", "m31");
+pub const POINT_26_Y: LDMKey<M31Bar> = LDMKey::new("point_26_y", "m31");
+pub const POINT_26_Y_INV: LDMKey<M31Bar> = LDMKey::new("point_26_y_inv", "m31");
+/// The twiddles of the folding, indexed by the log size of the layer.
+pub const TWIDDLE: LDMKey<M31Bar> = LDMKey::new("twiddle", "m31");
+/// The query, indexed by the log size of the layer that it is folded into.
+pub const QUERY: LDMKey<M31Bar> = LDMKey::new("query", "m31");
+
+/// The denominators of the quotients at the OODS point, indexed by the log size of the domain.
+pub const DENOMINATOR_OODS: LDMKey<CM31Bar> = LDMKey::new("denominator_oods", "cm31");
+pub const DENOMINATOR_OODS_26_SHIFTED: LDMKey<CM31Bar> =
+    LDMKey::new("denominator_oods_26_shifted", "cm31");
+
+/// The values of the columns at the query.
+pub const COMPOSITION_2_VAL: LDMKey<M31Bar> = LDMKey::new("composition_2_val", "m31");
+pub const COMPOSITION_3_VAL: LDMKey<M31Bar> = LDMKey::new("composition_3_val", "m31");
+pub const PREPROCESSED_OP1: LDMKey<M31Bar> = LDMKey::new("preprocessed_op1", "m31");
+pub const PREPROCESSED_OP2: LDMKey<M31Bar> = LDMKey::new("preprocessed_op2", "m31");
+pub const PREPROCESSED_OP3: LDMKey<M31Bar> = LDMKey::new("preprocessed_op3", "m31");
+pub const PREPROCESSED_OP4: LDMKey<M31Bar> = LDMKey::new("preprocessed_op4", "m31");
+pub const PREPROCESSED_MULT_C: LDMKey<M31Bar> = LDMKey::new("preprocessed_mult_c", "m31");
+pub const TRACE_A_VAL: LDMKey<M31Bar> = LDMKey::new("trace_a_val", "m31");
+pub const TRACE_B_VAL: LDMKey<M31Bar> = LDMKey::new("trace_b_val", "m31");
+pub const TRACE_C_VAL: LDMKey<M31Bar> = LDMKey::new("trace_c_val", "m31");
+pub const INTERACTION: LDMKey<M31Bar> = LDMKey::new("interaction", "m31");
+
+/// The partial sums of the numerators of the quotients.
+pub const NUMERATOR_COMPOSITION_01: LDMKey<QM31Bar> =
+    LDMKey::new("numerator_composition_01", "qm31");
+pub const NUMERATOR_PREPROCESSED_A_WIRE_TO_C_WIRE: LDMKey<QM31Bar> =
+    LDMKey::new("numerator_preprocessed_a_wire_to_c_wire", "qm31");
+pub const NUMERATOR_PREPROCESSED: LDMKey<QM31Bar> = LDMKey::new("numerator_preprocessed", "qm31");
+pub const NUMERATOR_TRACE_A_VAL_0_TO_B_VAL_1: LDMKey<QM31Bar> =
+    LDMKey::new("numerator_trace_a_val_0_to_b_val_1", "qm31");
+pub const NUMERATOR_TRACE: LDMKey<QM31Bar> = LDMKey::new("numerator_trace", "qm31");
+
+/// The sums of the quotients, indexed by the log size of the domain.
+pub const ROW: LDMKey<QM31Bar> = LDMKey::new("row", "qm31");
+pub const ROW_PREPROCESSED_TO_INTERACTION: LDMKey<QM31Bar> =
+    LDMKey::new("row_preprocessed_to_interaction", "qm31");
+
+/// The decommitted values of the FRI layers, indexed by the log size of the first layer and by
+/// the index of the inner layers.
+pub const FIRST_LAYER_SELF: LDMKey<QM31Bar> = LDMKey::new("first_layer_self", "qm31");
+pub const FIRST_LAYER_SIBLING: LDMKey<QM31Bar> = LDMKey::new("first_layer_sibling", "qm31");
+pub const INNER_LAYER_SELF: LDMKey<QM31Bar> = LDMKey::new("inner_layer_self", "qm31");
+pub const INNER_LAYER_SIBLING: LDMKey<QM31Bar> = LDMKey::new("inner_layer_sibling", "qm31");
+/// The folded value, indexed by the log size of the layer.
+pub const LAYER: LDMKey<QM31Bar> = LDMKey::new("layer", "qm31");
@@ -1,3 +1,4 @@
+pub mod keys;
 pub mod part10_folding;
 pub mod part11_folding;
 pub mod part12_folding;
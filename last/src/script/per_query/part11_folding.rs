@@ -1,10 +1,12 @@
+use crate::script::global::keys::INNER_LAYER_ALPHA;
+use crate::script::per_query::keys::{
+    INNER_LAYER_SELF, INNER_LAYER_SIBLING, LAYER, QUERY, TWIDDLE,
+};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::{AllocBar, Bar};
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::ldm::LDM;
 use recursive_stwo_primitives::bits::split_hi_lo;
-use recursive_stwo_primitives::fields::m31::M31Bar;
-use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
 
 pub fn generate_cs(ldm: &mut LDM, ldm_per_query: &mut LDM) -> Result<BitcoinSystemRef> {
@@ -12,76 +14,76 @@ pub fn generate_cs(ldm: &mut LDM, ldm_per_query: &mut LDM) -> Result<BitcoinSyst
     ldm.init(&cs)?;
     ldm_per_query.init(&cs)?;
 
-    let mut query: M31Bar = ldm_per_query.read("query_17")?;
-    let layer_18: QM31Bar = ldm_per_query.read("layer_18")?;
+    let mut query = ldm_per_query.read_key(&QUERY.indexed(17))?;
+    let layer_18 = ldm_per_query.read_key(&LAYER.indexed(18))?;
     let table = TableBar::new_constant(&cs, ())?;
 
     let layer_17 = {
-        let left: QM31Bar = ldm_per_query.read("inner_layer_self_10")?;
-        let right: QM31Bar = ldm_per_query.read("inner_layer_sibling_10")?;
+        let left = ldm_per_query.read_key(&INNER_LAYER_SELF.indexed(10))?;
+        let right = ldm_per_query.read_key(&INNER_LAYER_SIBLING.indexed(10))?;
         layer_18.equalverify(&left)?;
 
         let (hi, lo) = split_hi_lo(&query, 1)?;
         query = hi;
 
         let (left, right) = left.conditional_swap(&right, &lo);
-        let point_x_inv: M31Bar = ldm_per_query.read("twiddle_17")?;
-        let inner_layer_alpha: QM31Bar = ldm.read("inner_layer_alpha_10")?;
+        let point_x_inv = ldm_per_query.read_key(&TWIDDLE.indexed(17))?;
+        let inner_layer_alpha = ldm.read_key(&INNER_LAYER_ALPHA.indexed(10))?;
 
         let t0 = &left + &right;
         let t1 = &(&left - &right) * (&table, &point_x_inv);
         &(&t1 * (&table, &inner_layer_alpha)) + &t0
     };
     let layer_16 = {
-        let left: QM31Bar = ldm_per_query.read("inner_layer_self_11")?;
-        let right: QM31Bar = ldm_per_query.read("inner_layer_sibling_11")?;
+        let left = ldm_per_query.read_key(&INNER_LAYER_SELF.indexed(11))?;
+        let right = ldm_per_query.read_key(&INNER_LAYER_SIBLING.indexed(11))?;
         layer_17.equalverify(&left)?;
 
         let (hi, lo) = split_hi_lo(&query, 1)?;
         query = hi;
 
         let (left, right) = left.conditional_swap(&right, &lo);
-        let point_x_inv: M31Bar = ldm_per_query.read("twiddle_16")?;
-        let inner_layer_alpha: QM31Bar = ldm.read("inner_layer_alpha_11")?;
+        let point_x_inv = ldm_per_query.read_key(&TWIDDLE.indexed(16))?;
+        let inner_layer_alpha = ldm.read_key(&INNER_LAYER_ALPHA.indexed(11))?;
 
         let t0 = &left + &right;
         let t1 = &(&left - &right) * (&table, &point_x_inv);
         &(&t1 * (&table, &inner_layer_alpha)) + &t0
     };
     let layer_15 = {
-        let left: QM31Bar = ldm_per_query.read("inner_layer_self_12")?;
-        let right: QM31Bar = ldm_per_query.read("inner_layer_sibling_12")?;
+        let left = ldm_per_query.read_key(&INNER_LAYER_SELF.indexed(12))?;
+        let right = ldm_per_query.read_key(&INNER_LAYER_SIBLING.indexed(12))?;
         layer_16.equalverify(&left)?;
 
         let (hi, lo) = split_hi_lo(&query, 1)?;
         query = hi;
 
         let (left, right) = left.conditional_swap(&right, &lo);
-        let point_x_inv: M31Bar = ldm_per_query.read("twiddle_15")?;
-        let inner_layer_alpha: QM31Bar = ldm.read("inner_layer_alpha_12")?;
+        let point_x_inv = ldm_per_query.read_key(&TWIDDLE.indexed(15))?;
+        let inner_layer_alpha = ldm.read_key(&INNER_LAYER_ALPHA.indexed(12))?;
 
         let t0 = &left + &right;
         let t1 = &(&left - &right) * (&table, &point_x_inv);
         &(&t1 * (&table, &inner_layer_alpha)) + &t0
     };
     let layer_14 = {
-        let left: QM31Bar = ldm_per_query.read("inner_layer_self_13")?;
-        let right: QM31Bar = ldm_per_query.read("inner_layer_sibling_13")?;
+        let left = ldm_per_query.read_key(&INNER_LAYER_SELF.indexed(13))?;
+        let right = ldm_per_query.read_key(&INNER_LAYER_SIBLING.indexed(13))?;
         layer_15.equalverify(&left)?;
 
         let (hi, lo) = split_hi_lo(&query, 1)?;
         query = hi;
 
         let (left, right) = left.conditional_swap(&right, &lo);
-        let point_x_inv: M31Bar = ldm_per_query.read("twiddle_14")?;
-        let inner_layer_alpha: QM31Bar = ldm.read("inner_layer_alpha_13")?;
+        let point_x_inv = ldm_per_query.read_key(&TWIDDLE.indexed(14))?;
+        let inner_layer_alpha = ldm.read_key(&INNER_LAYER_ALPHA.indexed(13))?;
 
         let t0 = &left + &right;
         let t1 = &(&left - &right) * (&table, &point_x_inv);
         &(&t1 * (&table, &inner_layer_alpha)) + &t0
     };
-    ldm_per_query.write("query_13", &query)?;
-    ldm_per_query.write("layer_14", &layer_14)?;
+    ldm_per_query.write_key(&QUERY.indexed(13), &query)?;
+    ldm_per_query.write_key(&LAYER.indexed(14), &layer_14)?;
 
     ldm.save()?;
     ldm_per_query.save()?;
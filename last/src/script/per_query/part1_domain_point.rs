@@ -1,14 +1,19 @@
+use crate::script::global::keys::{
+    COLUMN_LINE_COEFF, COMPOSITION_COMMITMENT_VAR, OODS_SHIFTED_X, OODS_SHIFTED_Y, OODS_X, OODS_Y,
+    QUERIES,
+};
 use crate::script::hints::decommit::{LastDecommitHints, LastSinglePathMerkleProofBar};
+use crate::script::per_query::keys::{
+    COMPOSITION_2_VAL, COMPOSITION_3_VAL, DENOMINATOR_OODS, DENOMINATOR_OODS_26_SHIFTED,
+    NUMERATOR_COMPOSITION_01, POINT_26_X, POINT_26_Y, POINT_26_Y_INV, POINT_28_X, POINT_28_Y,
+    POINT_28_Y_INV, TWIDDLE,
+};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
-use recursive_stwo_bitcoin_dsl::basic::sha256_hash::Sha256HashBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::ldm::LDM;
 use recursive_stwo_primitives::circle::precomputed::{PrecomputedTree, PrecomputedTreeResultVar};
-use recursive_stwo_primitives::fields::m31::M31Bar;
-use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
-use recursive_stwo_primitives::quotient::ColumnLineCoeffBar;
 use std::path::PathBuf;
 
 pub fn generate_cs(
@@ -24,23 +29,23 @@ pub fn generate_cs(
     let upper_tree = PrecomputedTree::build_upper_tree(
         PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../data/precomputed_tree.bin"),
     )?;
-    let query: M31Bar = ldm.read(format!("query_{}", query_idx))?;
+    let query = ldm.read_key(&QUERIES.indexed(query_idx))?;
     let precomputed_result = PrecomputedTreeResultVar::fetch_and_verify(&upper_tree, &query)?;
 
-    ldm_per_query.write("point_28_x", &precomputed_result.point_28.x)?;
-    ldm_per_query.write("point_28_y", &precomputed_result.point_28.y)?;
-    ldm_per_query.write("point_28_y_inv", &precomputed_result.point_28_y_inv)?;
+    ldm_per_query.write_key(&POINT_28_X, &precomputed_result.point_28.x)?;
+    ldm_per_query.write_key(&POINT_28_Y, &precomputed_result.point_28.y)?;
+    ldm_per_query.write_key(&POINT_28_Y_INV, &precomputed_result.point_28_y_inv)?;
 
-    ldm_per_query.write("point_26_x", &precomputed_result.point_26.x)?;
-    ldm_per_query.write("point_26_y", &precomputed_result.point_26.y)?;
-    ldm_per_query.write("point_26_y_inv", &precomputed_result.point_26_y_inv)?;
+    ldm_per_query.write_key(&POINT_26_X, &precomputed_result.point_26.x)?;
+    ldm_per_query.write_key(&POINT_26_Y, &precomputed_result.point_26.y)?;
+    ldm_per_query.write_key(&POINT_26_Y_INV, &precomputed_result.point_26_y_inv)?;
 
     for (k, v) in precomputed_result.twiddles.iter() {
-        ldm_per_query.write(format!("twiddle_{}", k), v)?;
+        ldm_per_query.write_key(&TWIDDLE.indexed(k), v)?;
     }
 
-    let oods_x: QM31Bar = ldm.read("oods_x")?;
-    let oods_y: QM31Bar = ldm.read("oods_y")?;
+    let oods_x = ldm.read_key(&OODS_X)?;
+    let oods_y = ldm.read_key(&OODS_Y)?;
 
     let prx = oods_x.first;
     let pry = oods_y.first;
@@ -51,7 +56,7 @@ pub fn generate_cs(
         &cs,
         last_decommit_composition_hints.proofs[query_idx].clone(),
     )?;
-    let composition_commitment_var: Sha256HashBar = ldm.read("composition_commitment_var")?;
+    let composition_commitment_var = ldm.read_key(&COMPOSITION_COMMITMENT_VAR)?;
     composition_decommitment.verify(&query, 28, &composition_commitment_var)?;
 
     let table = TableBar::new_constant(&cs, ())?;
@@ -59,16 +64,16 @@ pub fn generate_cs(
     denominator_oods =
         &denominator_oods - &(&(&pry - &precomputed_result.point_28.y) * (&table, &pix));
     denominator_oods = denominator_oods.inverse(&table);
-    ldm_per_query.write("denominator_oods_28", &denominator_oods)?;
+    ldm_per_query.write_key(&DENOMINATOR_OODS.indexed(28), &denominator_oods)?;
 
     let mut denominator_oods = &(&prx - &precomputed_result.point_26.x) * (&table, &piy);
     denominator_oods =
         &denominator_oods - &(&(&pry - &precomputed_result.point_26.y) * (&table, &pix));
     denominator_oods = denominator_oods.inverse(&table);
-    ldm_per_query.write("denominator_oods_26", &denominator_oods)?;
+    ldm_per_query.write_key(&DENOMINATOR_OODS.indexed(26), &denominator_oods)?;
 
-    let oods_shifted_x: QM31Bar = ldm.read("oods_shifted_x")?;
-    let oods_shifted_y: QM31Bar = ldm.read("oods_shifted_y")?;
+    let oods_shifted_x = ldm.read_key(&OODS_SHIFTED_X)?;
+    let oods_shifted_y = ldm.read_key(&OODS_SHIFTED_Y)?;
 
     let prx = oods_shifted_x.first;
     let pry = oods_shifted_y.first;
@@ -79,17 +84,17 @@ pub fn generate_cs(
     denominator_oods_shifted =
         &denominator_oods_shifted - &(&(&pry - &precomputed_result.point_26.y) * (&table, &pix));
     denominator_oods_shifted = denominator_oods_shifted.inverse(&table);
-    ldm_per_query.write("denominator_oods_26_shifted", &denominator_oods_shifted)?;
+    ldm_per_query.write_key(&DENOMINATOR_OODS_26_SHIFTED, &denominator_oods_shifted)?;
 
-    let column_line_coeff_composition_0: ColumnLineCoeffBar =
-        ldm.read("column_line_coeff_composition_0")?;
+    let column_line_coeff_composition_0 =
+        ldm.read_key(&COLUMN_LINE_COEFF.indexed("composition_0"))?;
     let numerator_0 = column_line_coeff_composition_0.apply(
         &table,
         &precomputed_result.point_28.y,
         &composition_decommitment.columns[0],
     );
-    let column_line_coeff_composition_1: ColumnLineCoeffBar =
-        ldm.read("column_line_coeff_composition_1")?;
+    let column_line_coeff_composition_1 =
+        ldm.read_key(&COLUMN_LINE_COEFF.indexed("composition_1"))?;
     let numerator_1 = column_line_coeff_composition_1.apply(
         &table,
         &precomputed_result.point_28.y,
@@ -97,9 +102,9 @@ pub fn generate_cs(
     );
 
     let numerator_01 = &numerator_0 + &numerator_1;
-    ldm_per_query.write("numerator_composition_01", &numerator_01)?;
-    ldm_per_query.write("composition_2_val", &composition_decommitment.columns[2])?;
-    ldm_per_query.write("composition_3_val", &composition_decommitment.columns[3])?;
+    ldm_per_query.write_key(&NUMERATOR_COMPOSITION_01, &numerator_01)?;
+    ldm_per_query.write_key(&COMPOSITION_2_VAL, &composition_decommitment.columns[2])?;
+    ldm_per_query.write_key(&COMPOSITION_3_VAL, &composition_decommitment.columns[3])?;
 
     ldm.save()?;
     ldm_per_query.save()?;
@@ -1,15 +1,16 @@
+use crate::script::global::keys::{COLUMN_LINE_COEFF, PREPROCESSED_COMMITMENT_VAR, QUERIES};
 use crate::script::hints::decommit::{LastDecommitHints, LastSinglePathMerkleProofBar};
+use crate::script::per_query::keys::{
+    COMPOSITION_2_VAL, COMPOSITION_3_VAL, DENOMINATOR_OODS, NUMERATOR_COMPOSITION_01,
+    NUMERATOR_PREPROCESSED_A_WIRE_TO_C_WIRE, POINT_26_Y, POINT_28_Y, PREPROCESSED_MULT_C,
+    PREPROCESSED_OP1, PREPROCESSED_OP2, PREPROCESSED_OP3, PREPROCESSED_OP4, QUERY, ROW,
+};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
-use recursive_stwo_bitcoin_dsl::basic::sha256_hash::Sha256HashBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::ldm::LDM;
 use recursive_stwo_primitives::bits::split_hi_lo;
-use recursive_stwo_primitives::fields::cm31::CM31Bar;
-use recursive_stwo_primitives::fields::m31::M31Bar;
-use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
-use recursive_stwo_primitives::quotient::ColumnLineCoeffBar;
 
 pub fn generate_cs(
     query_idx: usize,
@@ -21,60 +22,60 @@ pub fn generate_cs(
     ldm.init(&cs)?;
     ldm_per_query.init(&cs)?;
 
-    let query_28: M31Bar = ldm.read(format!("query_{}", query_idx))?;
+    let query_28 = ldm.read_key(&QUERIES.indexed(query_idx))?;
     let query = {
         let (hi, lo) = split_hi_lo(&query_28, 2)?;
         lo.drop();
         hi
     };
-    ldm_per_query.write("query_26", &query)?;
+    ldm_per_query.write_key(&QUERY.indexed(26), &query)?;
 
     let preprocessed_decommitment = LastSinglePathMerkleProofBar::new_hint(
         &cs,
         last_decommit_preprocessed_hints.proofs[query_idx].clone(),
     )?;
-    let preprocessed_commitment_var: Sha256HashBar = ldm.read("preprocessed_commitment_var")?;
+    let preprocessed_commitment_var = ldm.read_key(&PREPROCESSED_COMMITMENT_VAR)?;
     preprocessed_decommitment.verify(&query, 26, &preprocessed_commitment_var)?;
 
     let table = TableBar::new_constant(&cs, ())?;
-    let point_28_y: M31Bar = ldm_per_query.read("point_28_y")?;
-    let composition_2_val: M31Bar = ldm_per_query.read("composition_2_val")?;
-    let composition_3_val: M31Bar = ldm_per_query.read("composition_3_val")?;
+    let point_28_y = ldm_per_query.read_key(&POINT_28_Y)?;
+    let composition_2_val = ldm_per_query.read_key(&COMPOSITION_2_VAL)?;
+    let composition_3_val = ldm_per_query.read_key(&COMPOSITION_3_VAL)?;
 
-    let column_line_coeff_composition_2: ColumnLineCoeffBar =
-        ldm.read("column_line_coeff_composition_2")?;
+    let column_line_coeff_composition_2 =
+        ldm.read_key(&COLUMN_LINE_COEFF.indexed("composition_2"))?;
     let numerator_2 =
         column_line_coeff_composition_2.apply(&table, &point_28_y, &composition_2_val);
-    let column_line_coeff_composition_3: ColumnLineCoeffBar =
-        ldm.read("column_line_coeff_composition_3")?;
+    let column_line_coeff_composition_3 =
+        ldm.read_key(&COLUMN_LINE_COEFF.indexed("composition_3"))?;
     let numerator_3 =
         column_line_coeff_composition_3.apply(&table, &point_28_y, &composition_3_val);
-    let numerator_01: QM31Bar = ldm_per_query.read("numerator_composition_01")?;
+    let numerator_01 = ldm_per_query.read_key(&NUMERATOR_COMPOSITION_01)?;
     let numerator_composition = &(&numerator_01 + &numerator_2) + &numerator_3;
-    let denominator_oods_28: CM31Bar = ldm_per_query.read("denominator_oods_28")?;
+    let denominator_oods_28 = ldm_per_query.read_key(&DENOMINATOR_OODS.indexed(28))?;
     let row_28 = &numerator_composition * (&table, &denominator_oods_28);
-    ldm_per_query.write("row_28", &row_28)?;
+    ldm_per_query.write_key(&ROW.indexed(28), &row_28)?;
 
-    let point_26_y: M31Bar = ldm_per_query.read("point_26_y")?;
+    let point_26_y = ldm_per_query.read_key(&POINT_26_Y)?;
 
-    let column_line_coeff_preprocessed_a_wire: ColumnLineCoeffBar =
-        ldm.read("column_line_coeff_preprocessed_a_wire")?;
+    let column_line_coeff_preprocessed_a_wire =
+        ldm.read_key(&COLUMN_LINE_COEFF.indexed("preprocessed_a_wire"))?;
     let numerator_a_wire = column_line_coeff_preprocessed_a_wire.apply(
         &table,
         &point_26_y,
         &preprocessed_decommitment.columns[0],
     );
 
-    let column_line_coeff_preprocessed_b_wire: ColumnLineCoeffBar =
-        ldm.read("column_line_coeff_preprocessed_b_wire")?;
+    let column_line_coeff_preprocessed_b_wire =
+        ldm.read_key(&COLUMN_LINE_COEFF.indexed("preprocessed_b_wire"))?;
     let numerator_b_wire = column_line_coeff_preprocessed_b_wire.apply(
         &table,
         &point_26_y,
         &preprocessed_decommitment.columns[1],
     );
 
-    let column_line_coeff_preprocessed_c_wire: ColumnLineCoeffBar =
-        ldm.read("column_line_coeff_preprocessed_c_wire")?;
+    let column_line_coeff_preprocessed_c_wire =
+        ldm.read_key(&COLUMN_LINE_COEFF.indexed("preprocessed_c_wire"))?;
     let numerator_c_wire = column_line_coeff_preprocessed_c_wire.apply(
         &table,
         &point_26_y,
@@ -84,16 +85,16 @@ pub fn generate_cs(
     let mut numerator_a_wire_to_c_wire = &numerator_a_wire + &numerator_b_wire;
     numerator_a_wire_to_c_wire = &numerator_a_wire_to_c_wire + &numerator_c_wire;
 
-    ldm_per_query.write(
-        "numerator_preprocessed_a_wire_to_c_wire",
+    ldm_per_query.write_key(
+        &NUMERATOR_PREPROCESSED_A_WIRE_TO_C_WIRE,
         &numerator_a_wire_to_c_wire,
     )?;
 
-    ldm_per_query.write("preprocessed_op1", &preprocessed_decommitment.columns[3])?;
-    ldm_per_query.write("preprocessed_op2", &preprocessed_decommitment.columns[4])?;
-    ldm_per_query.write("preprocessed_op3", &preprocessed_decommitment.columns[5])?;
-    ldm_per_query.write("preprocessed_op4", &preprocessed_decommitment.columns[6])?;
-    ldm_per_query.write("preprocessed_mult_c", &preprocessed_decommitment.columns[7])?;
+    ldm_per_query.write_key(&PREPROCESSED_OP1, &preprocessed_decommitment.columns[3])?;
+    ldm_per_query.write_key(&PREPROCESSED_OP2, &preprocessed_decommitment.columns[4])?;
+    ldm_per_query.write_key(&PREPROCESSED_OP3, &preprocessed_decommitment.columns[5])?;
+    ldm_per_query.write_key(&PREPROCESSED_OP4, &preprocessed_decommitment.columns[6])?;
+    ldm_per_query.write_key(&PREPROCESSED_MULT_C, &preprocessed_decommitment.columns[7])?;
 
     ldm.save()?;
     ldm_per_query.save()?;
@@ -1,12 +1,12 @@
+use crate::script::global::keys::COLUMN_LINE_COEFF;
+use crate::script::per_query::keys::{
+    DENOMINATOR_OODS_26_SHIFTED, INTERACTION, POINT_26_Y, ROW, ROW_PREPROCESSED_TO_INTERACTION,
+};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::ldm::LDM;
-use recursive_stwo_primitives::fields::cm31::CM31Bar;
-use recursive_stwo_primitives::fields::m31::M31Bar;
-use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
-use recursive_stwo_primitives::quotient::ColumnLineCoeffBar;
 
 pub fn generate_cs(ldm: &mut LDM, ldm_per_query: &mut LDM) -> Result<BitcoinSystemRef> {
     let cs = BitcoinSystemRef::new_ref();
@@ -14,29 +14,29 @@ pub fn generate_cs(ldm: &mut LDM, ldm_per_query: &mut LDM) -> Result<BitcoinSyst
     ldm_per_query.init(&cs)?;
 
     let table = TableBar::new_constant(&cs, ())?;
-    let point_26_y: M31Bar = ldm_per_query.read("point_26_y")?;
+    let point_26_y = ldm_per_query.read_key(&POINT_26_Y)?;
 
-    let interaction_0: M31Bar = ldm_per_query.read("interaction_0")?;
-    let column_line_coeff_interaction_prev_0: ColumnLineCoeffBar =
-        ldm.read("column_line_coeff_interaction_prev_0")?;
+    let interaction_0 = ldm_per_query.read_key(&INTERACTION.indexed(0))?;
+    let column_line_coeff_interaction_prev_0 =
+        ldm.read_key(&COLUMN_LINE_COEFF.indexed("interaction_prev_0"))?;
     let numerator_interaction_prev_0 =
         column_line_coeff_interaction_prev_0.apply(&table, &point_26_y, &interaction_0);
 
-    let interaction_1: M31Bar = ldm_per_query.read("interaction_1")?;
-    let column_line_coeff_interaction_prev_1: ColumnLineCoeffBar =
-        ldm.read("column_line_coeff_interaction_prev_1")?;
+    let interaction_1 = ldm_per_query.read_key(&INTERACTION.indexed(1))?;
+    let column_line_coeff_interaction_prev_1 =
+        ldm.read_key(&COLUMN_LINE_COEFF.indexed("interaction_prev_1"))?;
     let numerator_interaction_prev_1 =
         column_line_coeff_interaction_prev_1.apply(&table, &point_26_y, &interaction_1);
 
-    let interaction_2: M31Bar = ldm_per_query.read("interaction_2")?;
-    let column_line_coeff_interaction_prev_2: ColumnLineCoeffBar =
-        ldm.read("column_line_coeff_interaction_prev_2")?;
+    let interaction_2 = ldm_per_query.read_key(&INTERACTION.indexed(2))?;
+    let column_line_coeff_interaction_prev_2 =
+        ldm.read_key(&COLUMN_LINE_COEFF.indexed("interaction_prev_2"))?;
     let numerator_interaction_prev_2 =
         column_line_coeff_interaction_prev_2.apply(&table, &point_26_y, &interaction_2);
 
-    let interaction_3: M31Bar = ldm_per_query.read("interaction_3")?;
-    let column_line_coeff_interaction_prev_3: ColumnLineCoeffBar =
-        ldm.read("column_line_coeff_interaction_prev_3")?;
+    let interaction_3 = ldm_per_query.read_key(&INTERACTION.indexed(3))?;
+    let column_line_coeff_interaction_prev_3 =
+        ldm.read_key(&COLUMN_LINE_COEFF.indexed("interaction_prev_3"))?;
     let numerator_interaction_prev_3 =
         column_line_coeff_interaction_prev_3.apply(&table, &point_26_y, &interaction_3);
 
@@ -45,13 +45,13 @@ pub fn generate_cs(ldm: &mut LDM, ldm_per_query: &mut LDM) -> Result<BitcoinSyst
     numerator_interaction_prev = &numerator_interaction_prev + &numerator_interaction_prev_2;
     numerator_interaction_prev = &numerator_interaction_prev + &numerator_interaction_prev_3;
 
-    let denominator_oods_26_shifted: CM31Bar = ldm_per_query.read("denominator_oods_26_shifted")?;
+    let denominator_oods_26_shifted = ldm_per_query.read_key(&DENOMINATOR_OODS_26_SHIFTED)?;
     let row_interaction_prev = &numerator_interaction_prev * (&table, &denominator_oods_26_shifted);
 
-    let row_preprocessed_to_interaction: QM31Bar =
-        ldm_per_query.read("row_preprocessed_to_interaction")?;
+    let row_preprocessed_to_interaction =
+        ldm_per_query.read_key(&ROW_PREPROCESSED_TO_INTERACTION)?;
     let row_26 = &row_interaction_prev + &row_preprocessed_to_interaction;
-    ldm_per_query.write("row_26", &row_26)?;
+    ldm_per_query.write_key(&ROW.indexed(26), &row_26)?;
 
     ldm.save()?;
     ldm_per_query.save()?;
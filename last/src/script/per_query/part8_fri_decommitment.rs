@@ -1,14 +1,19 @@
+use crate::script::global::keys::{
+    FIRST_LAYER_ALPHA, FIRST_LAYER_COMMITMENT, INNER_LAYER_ALPHA, INNER_LAYER_ALPHA_1_SQUARED,
+    INNER_LAYER_COMMITMENT, QUERIES,
+};
 use crate::script::hints::folding::{
     LastFirstLayerHints, LastInnerLayersHints, LastSinglePairMerkleProofBar,
 };
+use crate::script::per_query::keys::{
+    FIRST_LAYER_SELF, FIRST_LAYER_SIBLING, INNER_LAYER_SELF, INNER_LAYER_SIBLING, LAYER,
+    POINT_26_Y_INV, POINT_28_Y_INV, QUERY, ROW, TWIDDLE,
+};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::{AllocBar, Bar};
-use recursive_stwo_bitcoin_dsl::basic::sha256_hash::Sha256HashBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::ldm::LDM;
 use recursive_stwo_primitives::bits::split_hi_lo;
-use recursive_stwo_primitives::fields::m31::M31Bar;
-use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
 use std::collections::BTreeMap;
 
@@ -23,20 +28,20 @@ pub fn generate_cs(
     ldm.init(&cs)?;
     ldm_per_query.init(&cs)?;
 
-    let query_28: M31Bar = ldm.read(format!("query_{}", query_idx))?;
+    let query_28 = ldm.read_key(&QUERIES.indexed(query_idx))?;
     let first_layer_decommitment = LastSinglePairMerkleProofBar::new_hint(
         &cs,
         last_first_layer_hints.merkle_proofs[query_idx].clone(),
     )?;
-    let first_layer_commitment: Sha256HashBar = ldm.read("first_layer_commitment")?;
+    let first_layer_commitment = ldm.read_key(&FIRST_LAYER_COMMITMENT)?;
     first_layer_decommitment.verify(&query_28, 28, &first_layer_commitment)?;
 
-    ldm_per_query.write(
-        "first_layer_self_26",
+    ldm_per_query.write_key(
+        &FIRST_LAYER_SELF.indexed(26),
         &first_layer_decommitment.self_columns[&26],
     )?;
-    ldm_per_query.write(
-        "first_layer_sibling_26",
+    ldm_per_query.write_key(
+        &FIRST_LAYER_SIBLING.indexed(26),
         &first_layer_decommitment.siblings_columns[&26],
     )?;
 
@@ -48,8 +53,7 @@ pub fn generate_cs(
             &cs,
             last_inner_layers_hints.merkle_proofs[&(27 - i)][query_idx].clone(),
         )?;
-        let inner_layer_commitment: Sha256HashBar =
-            ldm.read(format!("inner_layer_commitment_{}", i))?;
+        let inner_layer_commitment = ldm.read_key(&INNER_LAYER_COMMITMENT.indexed(i))?;
         inner_layer_decommitment.verify(&query_28, 28, &inner_layer_commitment)?;
         inner_layer_self_columns.insert(
             i,
@@ -62,25 +66,22 @@ pub fn generate_cs(
     }
 
     for i in 2..18 {
-        ldm_per_query.write(
-            format!("inner_layer_self_{}", i),
-            &inner_layer_self_columns[&i],
-        )?;
-        ldm_per_query.write(
-            format!("inner_layer_sibling_{}", i),
+        ldm_per_query.write_key(&INNER_LAYER_SELF.indexed(i), &inner_layer_self_columns[&i])?;
+        ldm_per_query.write_key(
+            &INNER_LAYER_SIBLING.indexed(i),
             &inner_layer_sibling_columns[&i],
         )?;
     }
 
     let table = TableBar::new_constant(&cs, ())?;
-    let first_layer_alpha: QM31Bar = ldm.read("first_layer_alpha")?;
+    let first_layer_alpha = ldm.read_key(&FIRST_LAYER_ALPHA)?;
 
     let mut query = query_28.clone();
     let folded_into_28 = {
         let left = &first_layer_decommitment.self_columns[&28];
         let right = &first_layer_decommitment.siblings_columns[&28];
 
-        let row_28: QM31Bar = ldm_per_query.read("row_28")?;
+        let row_28 = ldm_per_query.read_key(&ROW.indexed(28))?;
         row_28.equalverify(&left)?;
 
         let (hi, lo) = split_hi_lo(&query, 1)?;
@@ -88,7 +89,7 @@ pub fn generate_cs(
 
         let (left, right) = left.conditional_swap(&right, &lo);
 
-        let point_28_y_inv: M31Bar = ldm_per_query.read("point_28_y_inv")?;
+        let point_28_y_inv = ldm_per_query.read_key(&POINT_28_Y_INV)?;
         let t0 = &left + &right;
         let t1 = &(&left - &right) * (&table, &point_28_y_inv);
         &(&t1 * (&table, &first_layer_alpha)) + &t0
@@ -104,8 +105,8 @@ pub fn generate_cs(
         query = hi;
 
         let (left, right) = left.conditional_swap(&right, &lo);
-        let point_x_inv: M31Bar = ldm_per_query.read("twiddle_27")?;
-        let inner_layer_alpha: QM31Bar = ldm.read("inner_layer_alpha_0")?;
+        let point_x_inv = ldm_per_query.read_key(&TWIDDLE.indexed(27))?;
+        let inner_layer_alpha = ldm.read_key(&INNER_LAYER_ALPHA.indexed(0))?;
 
         let t0 = &left + &right;
         let t1 = &(&left - &right) * (&table, &point_x_inv);
@@ -116,7 +117,7 @@ pub fn generate_cs(
         let left = &first_layer_decommitment.self_columns[&26];
         let right = &first_layer_decommitment.siblings_columns[&26];
 
-        let row_26: QM31Bar = ldm_per_query.read("row_26")?;
+        let row_26 = ldm_per_query.read_key(&ROW.indexed(26))?;
         row_26.equalverify(&left)?;
 
         let (hi, lo) = split_hi_lo(&query, 1)?;
@@ -124,20 +125,20 @@ pub fn generate_cs(
 
         let (left, right) = left.conditional_swap(&right, &lo);
 
-        let point_26_y_inv: M31Bar = ldm_per_query.read("point_26_y_inv")?;
-        let inner_layer_alpha: QM31Bar = ldm.read("inner_layer_alpha_1")?;
+        let point_26_y_inv = ldm_per_query.read_key(&POINT_26_Y_INV)?;
+        let inner_layer_alpha = ldm.read_key(&INNER_LAYER_ALPHA.indexed(1))?;
         let t0 = &left + &right;
         let t1 = &(&left - &right) * (&table, &point_26_y_inv);
         let folded_into = &(&t1 * (&table, &inner_layer_alpha)) + &t0;
 
-        let inner_layer_alpha_squared: QM31Bar = ldm.read("inner_layer_alpha_1_squared")?;
+        let inner_layer_alpha_squared = ldm.read_key(&INNER_LAYER_ALPHA_1_SQUARED)?;
 
         let left = &inner_layer_self_columns[&1];
         let right = &inner_layer_sibling_columns[&1];
         layer_27.equalverify(&left)?;
 
         let (left, right) = left.conditional_swap(&right, &lo);
-        let point_x_inv: M31Bar = ldm_per_query.read("twiddle_26")?;
+        let point_x_inv = ldm_per_query.read_key(&TWIDDLE.indexed(26))?;
 
         let t0 = &left + &right;
         let t1 = &(&left - &right) * (&table, &point_x_inv);
@@ -146,8 +147,8 @@ pub fn generate_cs(
         let folded = &inner_layer_alpha_squared * (&table, &res);
         &folded + &folded_into
     };
-    ldm_per_query.write("layer_26", &layer_26)?;
-    ldm_per_query.write("query_25", &query)?;
+    ldm_per_query.write_key(&LAYER.indexed(26), &layer_26)?;
+    ldm_per_query.write_key(&QUERY.indexed(25), &query)?;
 
     ldm.save()?;
     ldm_per_query.save()?;
@@ -1,10 +1,12 @@
+use crate::script::global::keys::INNER_LAYER_ALPHA;
+use crate::script::per_query::keys::{
+    INNER_LAYER_SELF, INNER_LAYER_SIBLING, LAYER, QUERY, TWIDDLE,
+};
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::{AllocBar, Bar};
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::ldm::LDM;
 use recursive_stwo_primitives::bits::split_hi_lo;
-use recursive_stwo_primitives::fields::m31::M31Bar;
-use recursive_stwo_primitives::fields::qm31::QM31Bar;
 use recursive_stwo_primitives::fields::table::TableBar;
 
 pub fn generate_cs(ldm: &mut LDM, ldm_per_query: &mut LDM) -> Result<BitcoinSystemRef> {
@@ -12,76 +14,76 @@ pub fn generate_cs(ldm: &mut LDM, ldm_per_query: &mut LDM) -> Result<BitcoinSyst
     ldm.init(&cs)?;
     ldm_per_query.init(&cs)?;
 
-    let mut query: M31Bar = ldm_per_query.read("query_25")?;
-    let layer_26: QM31Bar = ldm_per_query.read("layer_26")?;
+    let mut query = ldm_per_query.read_key(&QUERY.indexed(25))?;
+    let layer_26 = ldm_per_query.read_key(&LAYER.indexed(26))?;
     let table = TableBar::new_constant(&cs, ())?;
 
     let layer_25 = {
-        let left: QM31Bar = ldm_per_query.read("inner_layer_self_2")?;
-        let right: QM31Bar = ldm_per_query.read("inner_layer_sibling_2")?;
+        let left = ldm_per_query.read_key(&INNER_LAYER_SELF.indexed(2))?;
+        let right = ldm_per_query.read_key(&INNER_LAYER_SIBLING.indexed(2))?;
         layer_26.equalverify(&left)?;
 
         let (hi, lo) = split_hi_lo(&query, 1)?;
         query = hi;
 
         let (left, right) = left.conditional_swap(&right, &lo);
-        let point_x_inv: M31Bar = ldm_per_query.read("twiddle_25")?;
-        let inner_layer_alpha: QM31Bar = ldm.read("inner_layer_alpha_2")?;
+        let point_x_inv = ldm_per_query.read_key(&TWIDDLE.indexed(25))?;
+        let inner_layer_alpha = ldm.read_key(&INNER_LAYER_ALPHA.indexed(2))?;
 
         let t0 = &left + &right;
         let t1 = &(&left - &right) * (&table, &point_x_inv);
         &(&t1 * (&table, &inner_layer_alpha)) + &t0
     };
     let layer_24 = {
-        let left: QM31Bar = ldm_per_query.read("inner_layer_self_3")?;
-        let right: QM31Bar = ldm_per_query.read("inner_layer_sibling_3")?;
+        let left = ldm_per_query.read_key(&INNER_LAYER_SELF.indexed(3))?;
+        let right = ldm_per_query.read_key(&INNER_LAYER_SIBLING.indexed(3))?;
         layer_25.equalverify(&left)?;
 
         let (hi, lo) = split_hi_lo(&query, 1)?;
         query = hi;
 
         let (left, right) = left.conditional_swap(&right, &lo);
-        let point_x_inv: M31Bar = ldm_per_query.read("twiddle_24")?;
-        let inner_layer_alpha: QM31Bar = ldm.read("inner_layer_alpha_3")?;
+        let point_x_inv = ldm_per_query.read_key(&TWIDDLE.indexed(24))?;
+        let inner_layer_alpha = ldm.read_key(&INNER_LAYER_ALPHA.indexed(3))?;
 
         let t0 = &left + &right;
         let t1 = &(&left - &right) * (&table, &point_x_inv);
         &(&t1 * (&table, &inner_layer_alpha)) + &t0
     };
     let layer_23 = {
-        let left: QM31Bar = ldm_per_query.read("inner_layer_self_4")?;
-        let right: QM31Bar = ldm_per_query.read("inner_layer_sibling_4")?;
+        let left = ldm_per_query.read_key(&INNER_LAYER_SELF.indexed(4))?;
+        let right = ldm_per_query.read_key(&INNER_LAYER_SIBLING.indexed(4))?;
         layer_24.equalverify(&left)?;
 
         let (hi, lo) = split_hi_lo(&query, 1)?;
         query = hi;
 
         let (left, right) = left.conditional_swap(&right, &lo);
-        let point_x_inv: M31Bar = ldm_per_query.read("twiddle_23")?;
-        let inner_layer_alpha: QM31Bar = ldm.read("inner_layer_alpha_4")?;
+        let point_x_inv = ldm_per_query.read_key(&TWIDDLE.indexed(23))?;
+        let inner_layer_alpha = ldm.read_key(&INNER_LAYER_ALPHA.indexed(4))?;
 
         let t0 = &left + &right;
         let t1 = &(&left - &right) * (&table, &point_x_inv);
         &(&t1 * (&table, &inner_layer_alpha)) + &t0
     };
     let layer_22 = {
-        let left: QM31Bar = ldm_per_query.read("inner_layer_self_5")?;
-        let right: QM31Bar = ldm_per_query.read("inner_layer_sibling_5")?;
+        let left = ldm_per_query.read_key(&INNER_LAYER_SELF.indexed(5))?;
+        let right = ldm_per_query.read_key(&INNER_LAYER_SIBLING.indexed(5))?;
         layer_23.equalverify(&left)?;
 
         let (hi, lo) = split_hi_lo(&query, 1)?;
         query = hi;
 
         let (left, right) = left.conditional_swap(&right, &lo);
-        let point_x_inv: M31Bar = ldm_per_query.read("twiddle_22")?;
-        let inner_layer_alpha: QM31Bar = ldm.read("inner_layer_alpha_5")?;
+        let point_x_inv = ldm_per_query.read_key(&TWIDDLE.indexed(22))?;
+        let inner_layer_alpha = ldm.read_key(&INNER_LAYER_ALPHA.indexed(5))?;
 
         let t0 = &left + &right;
         let t1 = &(&left - &right) * (&table, &point_x_inv);
         &(&t1 * (&table, &inner_layer_alpha)) + &t0
     };
-    ldm_per_query.write("query_21", &query)?;
-    ldm_per_query.write("layer_22", &layer_22)?;
+    ldm_per_query.write_key(&QUERY.indexed(21), &query)?;
+    ldm_per_query.write_key(&LAYER.indexed(22), &layer_22)?;
 
     ldm.save()?;
     ldm_per_query.save()?;
@@ -2,30 +2,26 @@ use crate::fields::m31::M31Bar;
 use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::AllocBar;
 use recursive_stwo_bitcoin_dsl::basic::bool::BoolBar;
-use recursive_stwo_bitcoin_dsl::options::Options;
-use recursive_stwo_bitcoin_dsl::stack::Stack;
 use recursive_stwo_bitcoin_dsl::treepp::*;
 use stwo_prover::core::fields::m31::M31;
 
 pub fn enforce_bit_range(a: &M31Bar, log_size: usize) {
     assert!(a.value.0 <= ((1 << log_size) - 1));
-    a.cs.insert_script_complex(
-        enforce_bit_range_gadget,
+    a.cs.insert_script_with(
+        move |_| Ok(enforce_bit_range_gadget(log_size)),
         [a.variable],
-        &Options::new().with_u32("log_size", log_size as u32),
     )
     .unwrap();
 }
 
 /// Gadget for enforcing the number of bits of a number.
-fn enforce_bit_range_gadget(_: &mut Stack, options: &Options) -> Result<Script> {
-    let log_size = options.get_u32("log_size")?;
+fn enforce_bit_range_gadget(log_size: usize) -> Script {
     let max = (1 << log_size) - 1;
 
-    Ok(script! {
+    script! {
         OP_DUP 0 OP_GREATERTHANOREQUAL OP_VERIFY
         { max } OP_LESSTHANOREQUAL OP_VERIFY
-    })
+    }
 }
 
 pub fn split_hi_lo(a: &M31Bar, log_size: usize) -> Result<(M31Bar, M31Bar)> {
@@ -41,24 +37,22 @@ pub fn split_hi_lo(a: &M31Bar, log_size: usize) -> Result<(M31Bar, M31Bar)> {
     enforce_bit_range(&hi_var, 31 - log_size);
     enforce_bit_range(&lo_var, log_size);
 
-    cs.insert_script_complex(
-        get_lo_gadget,
+    cs.insert_script_with(
+        move |_| Ok(get_lo_gadget(log_size)),
         [a.variable, lo_var.variable, hi_var.variable],
-        &Options::new().with_u32("log_size", log_size as u32),
     )?;
 
     Ok((hi_var, lo_var))
 }
 
-fn get_lo_gadget(_: &mut Stack, options: &Options) -> Result<Script> {
-    let log_size = options.get_u32("log_size")?;
-    Ok(script! {
+fn get_lo_gadget(log_size: usize) -> Script {
+    script! {
         for _ in 0..log_size {
             OP_DUP OP_ADD
         }
         OP_ADD
         OP_EQUALVERIFY
-    })
+    }
 }
 
 pub fn split_be_bits(a: &M31Bar, log_size: usize) -> Result<Vec<BoolBar>> {
@@ -72,11 +66,7 @@ pub fn split_be_bits(a: &M31Bar, log_size: usize) -> Result<Vec<BoolBar>> {
     assert_eq!(cur, 0);
 
     let cs = a.cs.clone();
-    cs.insert_script_complex(
-        split_be_bits_gadget,
-        [a.variable],
-        &Options::new().with_u32("log_size", log_size as u32),
-    )?;
+    cs.insert_script_with(move |_| Ok(split_be_bits_gadget(log_size)), [a.variable])?;
 
     let mut bit_vars = vec![];
     for bit in bits {
@@ -86,9 +76,8 @@ pub fn split_be_bits(a: &M31Bar, log_size: usize) -> Result<Vec<BoolBar>> {
     Ok(bit_vars)
 }
 
-fn split_be_bits_gadget(_: &mut Stack, options: &Options) -> Result<Script> {
-    let log_size = options.get_u32("log_size")?;
-    Ok(script! {
+fn split_be_bits_gadget(log_size: usize) -> Script {
+    script! {
         // stack:
         //   num (assumed within log_size bits)
 
@@ -106,7 +95,7 @@ fn split_be_bits_gadget(_: &mut Stack, options: &Options) -> Result<Script> {
         for _ in 1..log_size {
             OP_FROMALTSTACK
         }
-    })
+    }
 }
 
 #[cfg(test)]
@@ -3,7 +3,7 @@ use crate::fields::m31::M31Bar;
 use crate::fields::m31_limbs::M31LimbsBar;
 use crate::fields::table::TableBar;
 use anyhow::Result;
-use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar, CopyBar};
+use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar, CopyBar, FixedLengthBar};
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use std::ops::{Add, Mul, Neg, Sub};
 use stwo_prover::core::fields::cm31::CM31;
@@ -23,7 +23,9 @@ impl Bar for CM31Bar {
     fn variables(&self) -> Vec<usize> {
         vec![self.imag.variable, self.real.variable]
     }
+}
 
+impl FixedLengthBar for CM31Bar {
     fn length() -> usize {
         2
     }
@@ -52,10 +54,7 @@ impl Add for &CM31Bar {
     type Output = CM31Bar;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let imag = &self.imag + &rhs.imag;
-        let real = &self.real + &rhs.real;
-
-        CM31Bar { imag, real }
+        self.try_add(rhs).unwrap()
     }
 }
 
@@ -74,10 +73,7 @@ impl Sub for &CM31Bar {
     type Output = CM31Bar;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let imag = &self.imag - &rhs.imag;
-        let real = &self.real - &rhs.real;
-
-        CM31Bar { imag, real }
+        self.try_sub(rhs).unwrap()
     }
 }
 
@@ -117,12 +113,7 @@ impl Mul<(&TableBar, &CM31Bar)> for &CM31Bar {
     type Output = CM31Bar;
 
     fn mul(self, rhs: (&TableBar, &CM31Bar)) -> Self::Output {
-        let table = rhs.0;
-        let rhs = rhs.1;
-
-        let self_limbs = CM31LimbsBar::from(self);
-        let rhs_limbs = CM31LimbsBar::from(rhs);
-        &self_limbs * (table, &rhs_limbs)
+        self.try_mul(rhs.0, rhs.1).unwrap()
     }
 }
 
@@ -147,14 +138,45 @@ impl Neg for &CM31Bar {
     type Output = CM31Bar;
 
     fn neg(self) -> Self::Output {
-        let real = -&self.real;
-        let imag = -&self.imag;
-
-        CM31Bar { imag, real }
+        self.try_neg().unwrap()
     }
 }
 
 impl CM31Bar {
+    /// Adds two elements, failing instead of panicking if they belong to different systems.
+    pub fn try_add(&self, rhs: &CM31Bar) -> Result<CM31Bar> {
+        let imag = self.imag.try_add(&rhs.imag)?;
+        let real = self.real.try_add(&rhs.real)?;
+
+        Ok(CM31Bar { imag, real })
+    }
+
+    /// Subtracts two elements, failing instead of panicking if they belong to different systems.
+    pub fn try_sub(&self, rhs: &CM31Bar) -> Result<CM31Bar> {
+        let imag = self.imag.try_sub(&rhs.imag)?;
+        let real = self.real.try_sub(&rhs.real)?;
+
+        Ok(CM31Bar { imag, real })
+    }
+
+    /// Multiplies two elements with the table, failing instead of panicking if they or the table
+    /// belong to different systems.
+    pub fn try_mul(&self, table: &TableBar, rhs: &CM31Bar) -> Result<CM31Bar> {
+        self.cs().try_and(&table.cs())?.try_and(&rhs.cs())?;
+
+        let self_limbs = CM31LimbsBar::from(self);
+        let rhs_limbs = CM31LimbsBar::from(rhs);
+        Ok(&self_limbs * (table, &rhs_limbs))
+    }
+
+    /// Negates an element, failing instead of panicking if the system cannot take the script.
+    pub fn try_neg(&self) -> Result<CM31Bar> {
+        let real = self.real.try_neg()?;
+        let imag = self.imag.try_neg()?;
+
+        Ok(CM31Bar { imag, real })
+    }
+
     pub fn from_m31(a: &M31Bar, b: &M31Bar) -> CM31Bar {
         CM31Bar {
             imag: b.clone(),
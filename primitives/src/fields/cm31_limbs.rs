@@ -2,7 +2,7 @@ use crate::fields::cm31::CM31Bar;
 use crate::fields::m31_limbs::M31LimbsBar;
 use crate::fields::table::m31::{M31Limbs, M31LimbsGadget};
 use crate::fields::table::TableBar;
-use recursive_stwo_bitcoin_dsl::bar::{AllocBar, Bar};
+use recursive_stwo_bitcoin_dsl::bar::{AllocBar, Bar, FixedLengthBar};
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use std::ops::{Add, Mul};
 
@@ -22,7 +22,9 @@ impl Bar for CM31LimbsBar {
         variables.extend(self.imag.variables());
         variables
     }
+}
 
+impl FixedLengthBar for CM31LimbsBar {
     fn length() -> usize {
         8
     }
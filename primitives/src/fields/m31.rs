@@ -2,7 +2,7 @@ use crate::fields::m31_limbs::{m31_to_limbs_gadget, M31LimbsBar};
 use crate::fields::table::TableBar;
 use crate::utils;
 use anyhow::Result;
-use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar};
+use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar, FixedLengthBar};
 use recursive_stwo_bitcoin_dsl::basic::str::StrBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::{BitcoinSystemRef, Element};
 use recursive_stwo_bitcoin_dsl::options::Options;
@@ -27,7 +27,9 @@ impl Bar for M31Bar {
     fn variables(&self) -> Vec<usize> {
         vec![self.variable]
     }
+}
 
+impl FixedLengthBar for M31Bar {
     fn length() -> usize {
         1
     }
@@ -57,14 +59,7 @@ impl Add for &M31Bar {
     type Output = M31Bar;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let res = self.value + rhs.value;
-
-        let cs = self.cs.and(&rhs.cs);
-
-        cs.insert_script(rust_bitcoin_m31::m31_add, [self.variable, rhs.variable])
-            .unwrap();
-
-        M31Bar::new_variable(&cs, res, AllocationMode::FunctionOutput).unwrap()
+        self.try_add(rhs).unwrap()
     }
 }
 
@@ -72,14 +67,7 @@ impl Sub for &M31Bar {
     type Output = M31Bar;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let res = self.value - rhs.value;
-
-        let cs = self.cs.and(&rhs.cs);
-
-        cs.insert_script(rust_bitcoin_m31::m31_sub, [self.variable, rhs.variable])
-            .unwrap();
-
-        M31Bar::new_variable(&cs, res, AllocationMode::FunctionOutput).unwrap()
+        self.try_sub(rhs).unwrap()
     }
 }
 
@@ -103,12 +91,7 @@ impl Mul<(&TableBar, &M31Bar)> for &M31Bar {
     type Output = M31Bar;
 
     fn mul(self, rhs: (&TableBar, &M31Bar)) -> Self::Output {
-        let table = rhs.0;
-        let rhs = rhs.1;
-
-        let self_limbs = M31LimbsBar::from(self);
-        let rhs_limbs = M31LimbsBar::from(rhs);
-        &self_limbs * (table, &rhs_limbs)
+        self.try_mul(rhs.0, rhs.1).unwrap()
     }
 }
 
@@ -116,18 +99,49 @@ impl Neg for &M31Bar {
     type Output = M31Bar;
 
     fn neg(self) -> Self::Output {
-        let res = -self.value;
+        self.try_neg().unwrap()
+    }
+}
 
-        let cs = self.cs();
+impl M31Bar {
+    /// Adds two elements, failing instead of panicking if they belong to different systems.
+    pub fn try_add(&self, rhs: &M31Bar) -> Result<M31Bar> {
+        self.binary_op(self.value + rhs.value, rhs, rust_bitcoin_m31::m31_add)
+    }
 
-        cs.insert_script(rust_bitcoin_m31::m31_neg, [self.variable])
-            .unwrap();
+    /// Subtracts two elements, failing instead of panicking if they belong to different systems.
+    pub fn try_sub(&self, rhs: &M31Bar) -> Result<M31Bar> {
+        self.binary_op(self.value - rhs.value, rhs, rust_bitcoin_m31::m31_sub)
+    }
 
-        M31Bar::new_function_output(&cs, res).unwrap()
+    /// Multiplies two elements with the table, failing instead of panicking if they or the table
+    /// belong to different systems.
+    pub fn try_mul(&self, table: &TableBar, rhs: &M31Bar) -> Result<M31Bar> {
+        self.cs.try_and(&table.cs)?.try_and(&rhs.cs)?;
+
+        let self_limbs = M31LimbsBar::from(self);
+        let rhs_limbs = M31LimbsBar::from(rhs);
+        Ok(&self_limbs * (table, &rhs_limbs))
+    }
+
+    /// Negates an element, failing instead of panicking if the system cannot take the script.
+    pub fn try_neg(&self) -> Result<M31Bar> {
+        self.cs
+            .insert_script(rust_bitcoin_m31::m31_neg, [self.variable])?;
+        M31Bar::new_function_output(&self.cs, -self.value)
+    }
+
+    fn binary_op(
+        &self,
+        res: M31,
+        rhs: &M31Bar,
+        script_generator: fn() -> Script,
+    ) -> Result<M31Bar> {
+        let cs = self.cs.try_and(&rhs.cs)?;
+        cs.insert_script(script_generator, [self.variable, rhs.variable])?;
+        M31Bar::new_variable(&cs, res, AllocationMode::FunctionOutput)
     }
-}
 
-impl M31Bar {
     pub fn drop(&self) {
         self.cs
             .insert_script(utils::drop_gadget, [self.variable])
@@ -5,7 +5,7 @@ use crate::fields::table::utils::{
 };
 use crate::fields::table::TableBar;
 use anyhow::Result;
-use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar};
+use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar, FixedLengthBar};
 use recursive_stwo_bitcoin_dsl::bitcoin_system::{BitcoinSystemRef, Element};
 use recursive_stwo_bitcoin_dsl::options::Options;
 use recursive_stwo_bitcoin_dsl::stack::Stack;
@@ -28,7 +28,9 @@ impl Bar for M31LimbsBar {
     fn variables(&self) -> Vec<usize> {
         self.variables.to_vec()
     }
+}
 
+impl FixedLengthBar for M31LimbsBar {
     fn length() -> usize {
         4
     }
@@ -96,7 +98,8 @@ impl Mul<(&TableBar, &M31LimbsBar)> for &M31LimbsBar {
         let q = M31Mult::compute_q(&c_limbs).unwrap();
         let q_var = M31Bar::new_hint(&cs, M31::from(q)).unwrap();
 
-        let options = Options::new().with_u32("table_ref", table.variables[0] as u32);
+        let options =
+            Options::new().with_stack_ref("table_ref", table.variables[0], table.variables.len());
         cs.insert_script_complex(
             m31_limbs_mul_gadget,
             self.variables()
@@ -167,8 +170,8 @@ pub fn m31_to_limbs_gadget() -> Script {
 }
 
 fn m31_limbs_mul_gadget(stack: &mut Stack, options: &Options) -> Result<Script> {
-    let last_table_elem = options.get_u32("table_ref")?;
-    let k = stack.get_relative_position(last_table_elem as usize)? - 512;
+    let last_table_elem = options.get_stack_ref("table_ref")?;
+    let k = stack.get_relative_position(last_table_elem)? - 512;
 
     Ok(script! {
         OP_TOALTSTACK
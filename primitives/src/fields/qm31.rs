@@ -6,7 +6,7 @@ use crate::fields::qm31_limbs::QM31LimbsBar;
 use crate::fields::table::TableBar;
 use anyhow::Result;
 use num_traits::{One, Zero};
-use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar, CopyBar};
+use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar, CopyBar, FixedLengthBar};
 use recursive_stwo_bitcoin_dsl::basic::sha256_hash::Sha256HashBar;
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use recursive_stwo_bitcoin_dsl::treepp::*;
@@ -44,7 +44,9 @@ impl Bar for QM31Bar {
             self.first.real.variable,
         ]
     }
+}
 
+impl FixedLengthBar for QM31Bar {
     fn length() -> usize {
         4
     }
@@ -73,10 +75,7 @@ impl Add for &QM31Bar {
     type Output = QM31Bar;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let second = &self.second + &rhs.second;
-        let first = &self.first + &rhs.first;
-
-        QM31Bar { first, second }
+        self.try_add(rhs).unwrap()
     }
 }
 
@@ -117,10 +116,7 @@ impl Sub for &QM31Bar {
     type Output = QM31Bar;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let second = &self.second - &rhs.second;
-        let first = &self.first - &rhs.first;
-
-        QM31Bar { first, second }
+        self.try_sub(rhs).unwrap()
     }
 }
 
@@ -150,12 +146,7 @@ impl Mul<(&TableBar, &QM31Bar)> for &QM31Bar {
     type Output = QM31Bar;
 
     fn mul(self, rhs: (&TableBar, &QM31Bar)) -> Self::Output {
-        let table = rhs.0;
-        let rhs = rhs.1;
-
-        let self_limbs = QM31LimbsBar::from(self);
-        let rhs_limbs = QM31LimbsBar::from(rhs);
-        &self_limbs * (table, &rhs_limbs)
+        self.try_mul(rhs.0, rhs.1).unwrap()
     }
 }
 
@@ -232,14 +223,45 @@ impl Neg for &QM31Bar {
     type Output = QM31Bar;
 
     fn neg(self) -> Self::Output {
-        let first = -(&self.first);
-        let second = -(&self.second);
-
-        QM31Bar { first, second }
+        self.try_neg().unwrap()
     }
 }
 
 impl QM31Bar {
+    /// Adds two elements, failing instead of panicking if they belong to different systems.
+    pub fn try_add(&self, rhs: &QM31Bar) -> Result<QM31Bar> {
+        let second = self.second.try_add(&rhs.second)?;
+        let first = self.first.try_add(&rhs.first)?;
+
+        Ok(QM31Bar { first, second })
+    }
+
+    /// Subtracts two elements, failing instead of panicking if they belong to different systems.
+    pub fn try_sub(&self, rhs: &QM31Bar) -> Result<QM31Bar> {
+        let second = self.second.try_sub(&rhs.second)?;
+        let first = self.first.try_sub(&rhs.first)?;
+
+        Ok(QM31Bar { first, second })
+    }
+
+    /// Multiplies two elements with the table, failing instead of panicking if they or the table
+    /// belong to different systems.
+    pub fn try_mul(&self, table: &TableBar, rhs: &QM31Bar) -> Result<QM31Bar> {
+        self.cs().try_and(&table.cs())?.try_and(&rhs.cs())?;
+
+        let self_limbs = QM31LimbsBar::from(self);
+        let rhs_limbs = QM31LimbsBar::from(rhs);
+        Ok(&self_limbs * (table, &rhs_limbs))
+    }
+
+    /// Negates an element, failing instead of panicking if the system cannot take the script.
+    pub fn try_neg(&self) -> Result<QM31Bar> {
+        let first = self.first.try_neg()?;
+        let second = self.second.try_neg()?;
+
+        Ok(QM31Bar { first, second })
+    }
+
     pub fn is_zero(&self) {
         assert_eq!(self.value().unwrap(), QM31::zero());
         self.first.is_zero();
@@ -391,9 +413,49 @@ mod test {
     use rand_chacha::ChaCha20Rng;
     use recursive_stwo_bitcoin_dsl::bar::AllocBar;
     use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
+    use recursive_stwo_bitcoin_dsl::error::DslError;
     use recursive_stwo_bitcoin_dsl::treepp::*;
     use recursive_stwo_bitcoin_dsl::{rand_qm31, test_program};
 
+    #[test]
+    fn qm31_try_ops() {
+        let mut prng = ChaCha20Rng::seed_from_u64(0);
+
+        let a_val = rand_qm31(&mut prng);
+
+        let cs = BitcoinSystemRef::new_ref();
+        let other_cs = BitcoinSystemRef::new_ref();
+
+        let a = QM31Bar::new_constant(&cs, a_val).unwrap();
+        let b = QM31Bar::new_constant(&other_cs, a_val).unwrap();
+        let table = TableBar::new_constant(&cs, ()).unwrap();
+
+        for err in [
+            a.try_add(&b).unwrap_err(),
+            a.try_sub(&b).unwrap_err(),
+            a.try_mul(&table, &b).unwrap_err(),
+        ] {
+            assert!(matches!(
+                err.downcast_ref::<DslError>(),
+                Some(DslError::SystemMismatch)
+            ));
+        }
+
+        let res = a.try_sub(&a.try_neg().unwrap().try_neg().unwrap()).unwrap();
+        cs.set_program_output(&res).unwrap();
+
+        test_program(
+            cs,
+            script! {
+                0
+                0
+                0
+                0
+            },
+        )
+        .unwrap();
+    }
+
     #[test]
     fn qm31_inverse() {
         let mut prng = ChaCha20Rng::seed_from_u64(0);
@@ -1,7 +1,7 @@
 use crate::fields::cm31_limbs::CM31LimbsBar;
 use crate::fields::qm31::QM31Bar;
 use crate::fields::table::TableBar;
-use recursive_stwo_bitcoin_dsl::bar::Bar;
+use recursive_stwo_bitcoin_dsl::bar::{Bar, FixedLengthBar};
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use std::ops::Mul;
 
@@ -21,7 +21,9 @@ impl Bar for QM31LimbsBar {
         variables.extend(self.second.variables());
         variables
     }
+}
 
+impl FixedLengthBar for QM31LimbsBar {
     fn length() -> usize {
         16
     }
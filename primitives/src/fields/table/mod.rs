@@ -1,5 +1,5 @@
 use anyhow::Result;
-use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar};
+use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar, FixedLengthBar};
 use recursive_stwo_bitcoin_dsl::bitcoin_system::{BitcoinSystemRef, Element};
 use recursive_stwo_bitcoin_dsl::treepp::pushable::*;
 use std::ops::Index;
@@ -64,7 +64,9 @@ impl Bar for TableBar {
     fn variables(&self) -> Vec<usize> {
         self.variables.clone()
     }
+}
 
+impl FixedLengthBar for TableBar {
     fn length() -> usize {
         513
     }
@@ -86,13 +88,16 @@ impl AllocBar for TableBar {
         Self::new_constant(cs, data)
     }
 
+    /// Returns the table of the program, which is declared only the first time.
     fn new_constant(cs: &BitcoinSystemRef, _: Self::Value) -> Result<Self> {
-        let table = get_table();
-
-        let mut variables = vec![];
-        for &elem in table.data.iter().rev() {
-            variables.push(cs.alloc(Element::Num(elem as i32), AllocationMode::Constant)?);
-        }
+        let variables = cs.get_or_alloc_constant_block("table", || {
+            get_table()
+                .data
+                .iter()
+                .rev()
+                .map(|&elem| Element::Num(elem as i32))
+                .collect()
+        })?;
 
         Ok(Self {
             variables,
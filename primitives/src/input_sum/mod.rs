@@ -2,9 +2,8 @@ use crate::fields::qm31::QM31Bar;
 use crate::fields::table::TableBar;
 use anyhow::Result;
 use num_traits::Zero;
-use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar};
-use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
-use recursive_stwo_bitcoin_dsl::ldm::LDM;
+use recursive_stwo_bitcoin_dsl::bar::{AllocBar, Bar};
+use recursive_stwo_bitcoin_dsl::ldm::{LDMKey, LDM};
 use serde::{Deserialize, Serialize};
 use stwo_prover::core::fields::qm31::QM31;
 
@@ -25,37 +24,14 @@ impl InputSum {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Bar, AllocBar)]
+#[bar(value = InputSum)]
 pub struct InputSumBar {
     pub alpha: QM31Bar,
     pub cur: QM31Bar,
     pub sum: QM31Bar,
 }
 
-impl AllocBar for InputSumBar {
-    type Value = InputSum;
-
-    fn value(&self) -> Result<Self::Value> {
-        Ok(InputSum {
-            alpha: self.alpha.value()?,
-            cur: self.cur.value()?,
-            sum: self.sum.value()?,
-        })
-    }
-
-    fn new_variable(
-        cs: &BitcoinSystemRef,
-        data: Self::Value,
-        mode: AllocationMode,
-    ) -> Result<Self> {
-        let alpha = QM31Bar::new_variable(cs, data.alpha, mode)?;
-        let cur = QM31Bar::new_variable(cs, data.cur, mode)?;
-        let sum = QM31Bar::new_variable(cs, data.sum, mode)?;
-
-        Ok(Self { alpha, cur, sum })
-    }
-}
-
 impl InputSumBar {
     pub fn new(z: &QM31Bar, alpha: &QM31Bar) -> Result<Self> {
         let cs = z.cs().and(&alpha.cs());
@@ -70,9 +46,9 @@ impl InputSumBar {
         &mut self,
         table: &TableBar,
         ldm: &mut LDM,
-        name: impl ToString,
+        key: &LDMKey<QM31Bar>,
     ) -> Result<()> {
-        let new_elem: QM31Bar = ldm.read(name)?;
+        let new_elem = ldm.read_key(key)?;
         self.accumulate(table, &new_elem);
         Ok(())
     }
@@ -3,8 +3,6 @@ use anyhow::Result;
 use recursive_stwo_bitcoin_dsl::bar::{AllocBar, Bar};
 use recursive_stwo_bitcoin_dsl::basic::str::StrBar;
 use recursive_stwo_bitcoin_dsl::basic::u8::U8Bar;
-use recursive_stwo_bitcoin_dsl::options::Options;
-use recursive_stwo_bitcoin_dsl::stack::Stack;
 use recursive_stwo_bitcoin_dsl::treepp::*;
 
 pub fn verify_pow(channel: &Sha256ChannelBar, pow_bits: usize) -> Result<()> {
@@ -24,35 +22,32 @@ pub fn verify_pow(channel: &Sha256ChannelBar, pow_bits: usize) -> Result<()> {
     };
 
     if pow_bits % 8 == 0 {
-        cs.insert_script_complex(
-            verify_pow_without_msb_gadget,
+        cs.insert_script_with(
+            move |_| Ok(verify_pow_without_msb_gadget(pow_bits)),
             [
                 channel.digest.variable,
                 suffix_var.variable,
                 prefix_var.variable,
             ],
-            &Options::new().with_u32("pow_bits", pow_bits as u32),
         )
     } else {
-        cs.insert_script_complex(
-            verify_pow_with_msb_gadget,
+        cs.insert_script_with(
+            move |_| Ok(verify_pow_with_msb_gadget(pow_bits)),
             [
                 channel.digest.variable,
                 suffix_var.variable,
                 prefix_var.variable,
                 msb_var.unwrap().variable,
             ],
-            &Options::new().with_u32("pow_bits", pow_bits as u32),
         )
     }
 }
 
-fn verify_pow_without_msb_gadget(_: &mut Stack, options: &Options) -> Result<Script> {
-    let pow_bits = options.get_u32("pow_bits")?;
+fn verify_pow_without_msb_gadget(pow_bits: usize) -> Script {
     assert_eq!(pow_bits % 8, 0);
-    let zero = vec![0x0u8; (pow_bits / 8) as usize];
+    let zero = vec![0x0u8; pow_bits / 8];
 
-    Ok(script! {
+    script! {
         // input:
         //    digest
         //    suffix
@@ -65,15 +60,14 @@ fn verify_pow_without_msb_gadget(_: &mut Stack, options: &Options) -> Result<Scr
         OP_SIZE 16 OP_EQUALVERIFY
         OP_CAT
         OP_EQUALVERIFY
-    })
+    }
 }
 
-fn verify_pow_with_msb_gadget(_: &mut Stack, options: &Options) -> Result<Script> {
-    let pow_bits = options.get_u32("pow_bits")?;
+fn verify_pow_with_msb_gadget(pow_bits: usize) -> Script {
     assert_ne!(pow_bits % 8, 0);
-    let zero = vec![0x0u8; (pow_bits / 8) as usize];
+    let zero = vec![0x0u8; pow_bits / 8];
     let max_msb = (1 << (pow_bits % 8)) - 1;
-    Ok(script! {
+    script! {
         // input:
         //    digest
         //    suffix
@@ -110,5 +104,5 @@ fn verify_pow_with_msb_gadget(_: &mut Stack, options: &Options) -> Result<Script
         OP_CAT
 
         OP_EQUALVERIFY
-    })
+    }
 }
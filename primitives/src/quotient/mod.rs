@@ -3,7 +3,7 @@ use crate::fields::m31::M31Bar;
 use crate::fields::qm31::QM31Bar;
 use crate::fields::table::TableBar;
 use anyhow::Result;
-use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar};
+use recursive_stwo_bitcoin_dsl::bar::{AllocBar, AllocationMode, Bar, FixedLengthBar};
 use recursive_stwo_bitcoin_dsl::bitcoin_system::BitcoinSystemRef;
 use serde::{Deserialize, Serialize};
 use stwo_prover::core::circle::CirclePoint;
@@ -17,54 +17,14 @@ pub struct ColumnLineCoeff {
     pub c: QM31,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Bar, FixedLengthBar, AllocBar)]
+#[bar(value = ColumnLineCoeff)]
 pub struct ColumnLineCoeffBar {
     pub a: QM31Bar,
     pub b: QM31Bar,
     pub c: QM31Bar,
 }
 
-impl Bar for ColumnLineCoeffBar {
-    fn cs(&self) -> BitcoinSystemRef {
-        self.a.cs().and(&self.b.cs()).and(&self.c.cs())
-    }
-
-    fn variables(&self) -> Vec<usize> {
-        let mut v = Vec::with_capacity(12);
-        v.extend(self.a.variables());
-        v.extend(self.b.variables());
-        v.extend(self.c.variables());
-        v
-    }
-
-    fn length() -> usize {
-        <QM31Bar as Bar>::length() * 3
-    }
-}
-
-impl AllocBar for ColumnLineCoeffBar {
-    type Value = ColumnLineCoeff;
-
-    fn value(&self) -> Result<Self::Value> {
-        Ok(ColumnLineCoeff {
-            a: self.a.value()?,
-            b: self.b.value()?,
-            c: self.c.value()?,
-        })
-    }
-
-    fn new_variable(
-        cs: &BitcoinSystemRef,
-        data: Self::Value,
-        mode: AllocationMode,
-    ) -> Result<Self> {
-        let a = QM31Bar::new_variable(cs, data.a, mode)?;
-        let b = QM31Bar::new_variable(cs, data.b, mode)?;
-        let c = QM31Bar::new_variable(cs, data.c, mode)?;
-        Ok(Self { a, b, c })
-    }
-}
-
 impl ColumnLineCoeffBar {
     pub fn apply(&self, table: &TableBar, y: &M31Bar, value: &M31Bar) -> QM31Bar {
         let value = &self.c * (table, value);
@@ -119,9 +79,11 @@ impl Bar for LineCoeffRandomizerBar {
     fn variables(&self) -> Vec<usize> {
         self.alpha.variables()
     }
+}
 
+impl FixedLengthBar for LineCoeffRandomizerBar {
     fn length() -> usize {
-        <QM31Bar as Bar>::length()
+        <QM31Bar as FixedLengthBar>::length()
     }
 }
 